@@ -0,0 +1,207 @@
+//! The code generated for the `borrowed` and `shared` modules, the forms of the
+//! types that do not copy their variable length opaque and string data out of
+//! the input they are decoded from.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::source::{Def, Kind, Union};
+use crate::ty::Ty;
+
+/// `Form` describes a module of borrowed forms of the types, and the trait
+/// they are decoded with.
+pub struct Form {
+    /// The module, up to the borrowed forms of the types.
+    head: &'static str,
+    /// The description of the form, used in the doc comment of each type.
+    doc: &'static str,
+    /// The lifetime of the data the types borrow, if they have one.
+    lifetime: Option<&'static str>,
+    /// The trait the types are decoded with.
+    trait_name: &'static str,
+    /// The function of the trait that reads the type, with its generics.
+    read_fn: &'static str,
+    /// The reader the types are read from.
+    reader: &'static str,
+}
+
+/// The `borrowed` module, of types that borrow their data from a slice.
+pub const BORROWED: Form = Form {
+    head: include_str!("../templates/borrowed.rs"),
+    doc: "Borrowed form",
+    lifetime: Some("'a"),
+    trait_name: "ReadXdrBorrowed<'a>",
+    read_fn: "read_xdr_borrowed",
+    reader: "Limited<&'a [u8]>",
+};
+
+/// The `shared` module, of types that hold their data as `bytes::Bytes` that
+/// share the buffer they are decoded from.
+pub const SHARED: Form = Form {
+    head: include_str!("../templates/shared.rs"),
+    doc: "Shared form",
+    lifetime: None,
+    trait_name: "ReadXdrShared",
+    read_fn: "read_xdr_shared<B: bytes::Buf>",
+    reader: "Limited<bytes::buf::Reader<B>>",
+};
+
+/// Renders a module of the form, with a borrowed form of each type that
+/// contains variable length opaque or string data.
+pub fn render(form: &Form, defs: &[Def]) -> String {
+    let borrowed = borrowed_types(defs);
+    let is_borrowed = |n: &str| borrowed.contains(n);
+    let mut out = form.head.to_string();
+    for def in defs.iter().filter(|d| is_borrowed(&d.name)) {
+        out += "\n";
+        out += &render_def(form, def, &is_borrowed);
+    }
+    out += "}\n";
+    out
+}
+
+/// Returns the names of the types that contain variable length opaque or
+/// string data, directly or through the types they contain.
+fn borrowed_types(defs: &[Def]) -> HashSet<String> {
+    let mut borrowed = HashSet::new();
+    loop {
+        let is_borrowed = |n: &str| borrowed.contains(n);
+        let more: Vec<String> = defs
+            .iter()
+            .filter(|d| !borrowed.contains(&d.name))
+            .filter(|d| tys(d).iter().any(|t| t.is_borrowed(&is_borrowed)))
+            .map(|d| d.name.clone())
+            .collect();
+        if more.is_empty() {
+            return borrowed;
+        }
+        borrowed.extend(more);
+    }
+}
+
+/// Returns the types contained in a definition, other than the types of
+/// aliases, which have no borrowed form of their own.
+fn tys(def: &Def) -> Vec<&Ty> {
+    match &def.kind {
+        Kind::Struct(fields) => fields.iter().map(|f| &f.ty).collect(),
+        Kind::Union(Union { arms, .. }) => arms
+            .iter()
+            .filter_map(|a| a.ty.as_ref())
+            .map(|(t, _)| t)
+            .collect(),
+        Kind::Typedef {
+            ty, alias: false, ..
+        } => vec![ty],
+        Kind::Typedef { alias: true, .. } | Kind::Enum(_) | Kind::Const => Vec::new(),
+    }
+}
+
+fn render_def(form: &Form, def: &Def, is_borrowed: &impl Fn(&str) -> bool) -> String {
+    let name = &def.name;
+    let trait_path = form.trait_name.split('<').next().expect("trait");
+    let trait_fn = form.read_fn.split('<').next().expect("read fn");
+    let lt = form.lifetime.map(|l| format!("<{l}>")).unwrap_or_default();
+    let borrowed = |ty: &Ty| ty.borrowed(is_borrowed, form.lifetime);
+    let read = |ty: &Ty, read: &str| {
+        if ty.is_borrowed(is_borrowed) {
+            format!("{trait_path}::{trait_fn}(r)?")
+        } else {
+            format!("{read}::read_xdr(r)?")
+        }
+    };
+    let owned = |ty: &Ty, v: &str| {
+        if ty.is_borrowed(is_borrowed) {
+            format!("{v}.into_owned()")
+        } else {
+            v.to_string()
+        }
+    };
+    let (definition, read_xdr, into_owned) = match &def.kind {
+        Kind::Typedef { ty, .. } => (
+            format!("pub struct {name}{lt}(pub {});", borrowed(ty)),
+            format!("r.with_limited_depth(|r| Ok({name}({trait_path}::{trait_fn}(r)?)))"),
+            format!("super::{name}(self.0.into_owned())"),
+        ),
+        Kind::Struct(fields) => {
+            let mut members = String::new();
+            let mut reads = String::new();
+            let mut owneds = String::new();
+            for f in fields {
+                let n = &f.name;
+                let _ = writeln!(members, "pub {n}: {},", borrowed(&f.ty));
+                let _ = writeln!(reads, "{n}: {},", read(&f.ty, &f.read));
+                let _ = writeln!(owneds, "{n}: {},", owned(&f.ty, &format!("self.{n}")));
+            }
+            (
+                format!("pub struct {name}{lt} {{\n{members}}}"),
+                format!("r.with_limited_depth(|r| {{ Ok(Self {{\n{reads}}}) }})"),
+                format!("super::{name} {{\n{owneds}}}"),
+            )
+        }
+        Kind::Union(Union {
+            discriminant: d,
+            arms,
+        }) => {
+            let mut members = String::new();
+            let mut reads = String::new();
+            let mut owneds = String::new();
+            for a in arms {
+                let (case, n) = (&a.case, &a.name);
+                if let Some((ty, r)) = &a.ty {
+                    let _ = writeln!(members, "{n}({}),", borrowed(ty));
+                    let _ = writeln!(reads, "{case} => Self::{n}({}),", read(ty, r));
+                    let _ = writeln!(
+                        owneds,
+                        "Self::{n}(v) => super::{name}::{n}({}),",
+                        owned(ty, "v")
+                    );
+                } else {
+                    let _ = writeln!(members, "{n},");
+                    let _ = writeln!(reads, "{case} => Self::{n},");
+                    let _ = writeln!(owneds, "Self::{n} => super::{name}::{n},");
+                }
+            }
+            (
+                format!("pub enum {name}{lt} {{\n{members}}}"),
+                format!(
+                    "r.with_limited_depth(|r| {{
+    let dv: {d} = <{d} as ReadXdr>::read_xdr(r)?;
+    #[allow(clippy::match_same_arms, clippy::match_wildcard_for_single_variants)]
+    let v = match dv {{
+{reads}        #[allow(unreachable_patterns)]
+        _ => return Err(Error::Invalid),
+    }};
+    Ok(v)
+}})"
+                ),
+                format!("match self {{\n{owneds}}}"),
+            )
+        }
+        Kind::Enum(_) | Kind::Const => unreachable!("{name} has no borrowed form"),
+    };
+    let allows: String = def
+        .definition()
+        .lines()
+        .filter(|l| l.starts_with("#[allow("))
+        .map(|l| format!("{l}\n"))
+        .collect();
+    let (doc, trait_name, read_fn, reader) = (form.doc, form.trait_name, form.read_fn, form.reader);
+    format!(
+        "/// {doc} of [`super::{name}`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+{allows}{definition}
+
+impl{lt} {trait_name} for {name}{lt} {{
+    type Owned = super::{name};
+
+    fn {read_fn}(r: &mut {reader}) -> Result<Self> {{
+        {read_xdr}
+    }}
+
+    fn into_owned(self) -> Self::Owned {{
+        {into_owned}
+    }}
+}}
+"
+    )
+}
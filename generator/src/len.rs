@@ -0,0 +1,92 @@
+//! The code generated for computing the length of the XDR of each type from
+//! its structure.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::source::{Def, Kind, Union};
+use crate::ty::Ty;
+
+/// The definitions of the types, by name.
+pub type Defs<'a> = HashMap<&'a str, &'a Def>;
+
+/// Returns the length of the XDR of a type and the depth it is written to, if
+/// every value of the type has the same length.
+pub fn fixed(ty: &Ty, defs: &Defs) -> Option<(usize, u32)> {
+    match ty {
+        Ty::Prim(p) => match p.as_str() {
+            "i32" | "u32" => Some((4, 1)),
+            "i64" | "u64" => Some((8, 1)),
+            "bool" => Some((4, 2)),
+            _ => None,
+        },
+        Ty::Named(n) => fixed_def(defs[n.as_str()], defs),
+        Ty::Box(t) => fixed(t, defs).map(|(len, levels)| (len, levels + 1)),
+        Ty::Array(t, n) if **t == Ty::Prim("u8".to_string()) => {
+            let n = *n as usize;
+            Some((n + (4 - n % 4) % 4, 1))
+        }
+        Ty::Array(t, n) => fixed(t, defs).map(|(len, levels)| (len * *n as usize, levels + 1)),
+        Ty::Option(_) | Ty::VecM(..) | Ty::BytesM(_) | Ty::StringM(_) => None,
+    }
+}
+
+fn fixed_def(def: &Def, defs: &Defs) -> Option<(usize, u32)> {
+    match &def.kind {
+        Kind::Enum(_) => Some((4, 2)),
+        Kind::Struct(fields) => fields.iter().try_fold((0, 1), |(len, levels), f| {
+            let (l, d) = fixed(&f.ty, defs)?;
+            Some((len + l, levels.max(d + 1)))
+        }),
+        Kind::Typedef {
+            ty, alias: true, ..
+        } => fixed(ty, defs),
+        Kind::Typedef { ty, .. } => fixed(ty, defs).map(|(len, levels)| (len, levels + 1)),
+        Kind::Union(_) | Kind::Const => None,
+    }
+}
+
+/// Renders the `xdr_len_at_depth` method of the `WriteXdr` impl of a type.
+pub fn render(def: &Def, defs: &Defs) -> String {
+    let body = if let Some((len, levels)) = fixed_def(def, defs) {
+        format!("fixed_xdr_len({len}, {levels}, depth)\n")
+    } else {
+        match &def.kind {
+            Kind::Typedef { .. } => "self.0.xdr_len_at_depth(inner_depth(depth)?)\n".to_string(),
+            Kind::Struct(fields) => {
+                let lens: Vec<String> = fields
+                    .iter()
+                    .map(|f| format!("self.{}.xdr_len_at_depth(depth)", f.name))
+                    .collect();
+                let len = if let [len] = &lens[..] {
+                    len.clone()
+                } else {
+                    format!("Ok({}?)", lens.join("? + "))
+                };
+                format!("let depth = inner_depth(depth)?;\n{len}\n")
+            }
+            Kind::Union(Union { arms, .. }) => {
+                let mut lens = String::new();
+                for a in arms {
+                    let n = &a.name;
+                    if a.ty.is_some() {
+                        let _ = writeln!(lens, "Self::{n}(v) => v.xdr_len_at_depth(depth)?,");
+                    } else {
+                        let _ = writeln!(lens, "Self::{n} => 0,");
+                    }
+                }
+                format!(
+                    "let depth = inner_depth(depth)?;
+let len = self.discriminant().xdr_len_at_depth(depth)?;
+#[allow(clippy::match_same_arms)]
+let arm = match self {{
+{lens}}};
+Ok(len + arm)
+"
+                )
+            }
+            Kind::Enum(_) | Kind::Const => unreachable!("{} has a fixed length", def.name),
+        }
+    };
+    format!("    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {{\n{body}    }}\n")
+}
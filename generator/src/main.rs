@@ -0,0 +1,141 @@
+//! Generates the Rust code for the Stellar XDR types from the code generated
+//! by xdrgen.
+//!
+//! The code generated by xdrgen is read from the file given as the only
+//! argument, and the file is overwritten with the generated code, formatted
+//! with rustfmt.
+
+mod borrowed;
+mod len;
+mod schema;
+mod source;
+mod ty;
+mod type_enum;
+mod types;
+
+use std::{
+    env, fs,
+    io::Write,
+    process::{self, Command, Stdio},
+};
+
+use source::Source;
+
+const HEADER: &str = include_str!("../templates/header.rs");
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (Some(path), None) = (args.next(), args.next()) else {
+        eprintln!("usage: stellar-xdr-generator <generated.rs>");
+        process::exit(2);
+    };
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("error reading {path}: {e}");
+        process::exit(1);
+    });
+    let src = Source::parse(&input);
+    let files: Vec<(String, String)> = src
+        .files
+        .iter()
+        .map(|f| {
+            let contents = fs::read_to_string(f).unwrap_or_else(|e| {
+                eprintln!("error reading {f}: {e}");
+                process::exit(1);
+            });
+            (f.clone(), contents)
+        })
+        .collect();
+    let output = allow_too_many_lines(&rustfmt(&generate(&src, &files)));
+    fs::write(&path, output).unwrap_or_else(|e| {
+        eprintln!("error writing {path}: {e}");
+        process::exit(1);
+    });
+}
+
+fn generate(src: &Source, files: &[(String, String)]) -> String {
+    let mut out = src.prelude.clone();
+    out += HEADER;
+    out += "\n";
+    let defs: len::Defs = src.defs.iter().map(|d| (d.name.as_str(), d)).collect();
+    for def in &src.defs {
+        out += &types::render(def, &defs);
+    }
+    out += "\n";
+    out += &type_enum::render(&src.type_head, &src.variants);
+    out += "\n";
+    out += &borrowed::render(&borrowed::BORROWED, &src.defs);
+    out += "\n";
+    out += &borrowed::render(&borrowed::SHARED, &src.defs);
+    out += "\n";
+    out += &schema::render(src, files);
+    out += "\n";
+    out += &type_enum::render_from_any(&src.defs, &src.variants);
+    out
+}
+
+fn rustfmt(code: &str) -> String {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| {
+            eprintln!("error running rustfmt: {e}");
+            process::exit(1);
+        });
+    let mut stdin = child.stdin.take().expect("stdin");
+    let code = code.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(code.as_bytes()));
+    let output = child.wait_with_output().expect("rustfmt output");
+    writer
+        .join()
+        .expect("rustfmt input")
+        .expect("rustfmt input");
+    if !output.status.success() {
+        eprintln!("error formatting the generated code");
+        process::exit(1);
+    }
+    String::from_utf8(output.stdout).expect("utf-8")
+}
+
+/// Allows `clippy::too_many_lines` on the functions that have more lines than
+/// the lint allows, counted the same way as the lint counts them.
+fn allow_too_many_lines(code: &str) -> String {
+    const ALLOW: &str = "#[allow(clippy::too_many_lines)]";
+    let lines: Vec<&str> = code.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    for (i, l) in lines.iter().enumerate() {
+        let t = l.trim_start();
+        let indent = &l[..l.len() - t.len()];
+        let is_fn = ["fn ", "pub fn ", "pub const fn ", "pub(crate) fn "]
+            .iter()
+            .any(|p| t.starts_with(p));
+        if is_fn && !t.ends_with(';') {
+            let allowed = lines[..i]
+                .iter()
+                .rev()
+                .take_while(|a| a.starts_with(&format!("{indent}#")))
+                .any(|a| a.trim_start() == ALLOW);
+            let start = i + lines[i..]
+                .iter()
+                .position(|l| l.ends_with('{'))
+                .expect("function body");
+            let close = format!("{indent}}}");
+            let end = start
+                + lines[start..]
+                    .iter()
+                    .position(|l| *l == close)
+                    .expect("function end");
+            let count = lines[start..=end]
+                .iter()
+                .map(|l| l.trim())
+                .filter(|l| !l.is_empty() && !l.starts_with("//"))
+                .count();
+            if !allowed && count > 100 {
+                out.push(format!("{indent}{ALLOW}"));
+            }
+        }
+        out.push((*l).to_string());
+    }
+    out.join("\n") + "\n"
+}
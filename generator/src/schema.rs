@@ -0,0 +1,149 @@
+//! The code generated for the `schema` module, the descriptions of the XDR
+//! types for walking the structure of the types at runtime.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::source::{xdr_name, Def, Kind, Source, Union};
+use crate::ty::Ty;
+
+const HEAD: &str = include_str!("../templates/schema.rs");
+
+/// Renders the `schema` module, with the schema of each type in the order of
+/// `TypeVariant`, given the path and contents of each XDR file the code was
+/// generated from.
+pub fn render(src: &Source, files: &[(String, String)]) -> String {
+    let defs: HashMap<&str, &Def> = src.defs.iter().map(|d| (d.name.as_str(), d)).collect();
+    let def_files = def_files(&src.defs, files);
+    let mut out = HEAD.to_string();
+    let _ = writeln!(out, "static SCHEMAS: [Schema; {}] = [", src.variants.len());
+    for v in &src.variants {
+        let def = defs[v.as_str()];
+        let _ = writeln!(
+            out,
+            "Schema {{ name: \"{v}\", file: \"{}\", kind: {}, }},",
+            def_files[v.as_str()],
+            kind(def, &defs)
+        );
+    }
+    out += "];\n}\n";
+    out
+}
+
+/// Returns the file each definition is in. Nested definitions are in the file
+/// of the definition they are nested in, which follows them.
+fn def_files<'a>(defs: &'a [Def], files: &'a [(String, String)]) -> HashMap<&'a str, &'a str> {
+    let mut names = HashMap::new();
+    for (path, contents) in files {
+        for line in contents
+            .lines()
+            .filter(|l| !l.starts_with(char::is_whitespace))
+        {
+            if let Some(name) = xdr_name(line) {
+                names.insert(name, path.as_str());
+            }
+        }
+    }
+    let mut def_files = HashMap::new();
+    let mut nested = Vec::new();
+    for def in defs {
+        nested.push(def.name.as_str());
+        if let Some(name) = &def.xdr_name {
+            let file = *names
+                .get(name.as_str())
+                .unwrap_or_else(|| panic!("{name} is not defined in the XDR files"));
+            for n in nested.drain(..) {
+                def_files.insert(n, file);
+            }
+        }
+    }
+    assert!(
+        nested.is_empty(),
+        "nested definitions {nested:?} have no parent"
+    );
+    def_files
+}
+
+fn kind(def: &Def, defs: &HashMap<&str, &Def>) -> String {
+    match &def.kind {
+        Kind::Struct(fields) => {
+            let fields: String = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "Field {{ name: \"{}\", ty: {}, }},",
+                        f.name,
+                        type_ref(&f.ty)
+                    )
+                })
+                .collect();
+            format!("Kind::Struct(&[{fields}])")
+        }
+        Kind::Enum(cases) => {
+            let cases: String = cases
+                .iter()
+                .map(|c| format!("EnumCase {{ name: \"{}\", value: {}, }},", c.name, c.value))
+                .collect();
+            format!("Kind::Enum(&[{cases}])")
+        }
+        Kind::Union(Union { discriminant, arms }) => {
+            let arms: String = arms
+                .iter()
+                .map(|a| {
+                    let value = match a.case.split_once("::") {
+                        Some((e, c)) => match &defs[e].kind {
+                            Kind::Enum(cases) => {
+                                cases
+                                    .iter()
+                                    .find(|ec| ec.name == c)
+                                    .unwrap_or_else(|| panic!("{e}::{c} is not defined"))
+                                    .value
+                            }
+                            _ => panic!("{e} is not an enum"),
+                        },
+                        None => a.case.parse().expect("union case"),
+                    };
+                    let ty = match &a.ty {
+                        Some((ty, _)) => format!("Some({})", type_ref(ty)),
+                        None => "None".to_string(),
+                    };
+                    format!(
+                        "UnionArm {{ name: \"{}\", value: {value}, ty: {ty}, }},",
+                        a.name
+                    )
+                })
+                .collect();
+            format!(
+                "Kind::Union(Union {{ discriminant: {}, arms: &[{arms}], }})",
+                type_ref(&Ty::parse(discriminant))
+            )
+        }
+        Kind::Typedef { ty, .. } => format!("Kind::Typedef({})", type_ref(ty)),
+        Kind::Const => unreachable!("constants have no schema"),
+    }
+}
+
+fn type_ref(ty: &Ty) -> String {
+    let max = |m: &Option<u32>| m.map_or_else(|| "u32::MAX".to_string(), |m| m.to_string());
+    match ty {
+        Ty::Prim(p) => match p.as_str() {
+            "i32" => "TypeRef::Int32",
+            "u32" => "TypeRef::Uint32",
+            "i64" => "TypeRef::Int64",
+            "u64" => "TypeRef::Uint64",
+            "bool" => "TypeRef::Bool",
+            _ => panic!("{p} has no schema"),
+        }
+        .to_string(),
+        Ty::Named(n) => format!("TypeRef::Type(TypeVariant::{n})"),
+        Ty::Box(t) => type_ref(t),
+        Ty::Option(t) => format!("TypeRef::Optional(&{})", type_ref(t)),
+        Ty::Array(t, n) if **t == Ty::Prim("u8".to_string()) => {
+            format!("TypeRef::FixedOpaque({n})")
+        }
+        Ty::Array(t, n) => format!("TypeRef::FixedArray(&{}, {n})", type_ref(t)),
+        Ty::BytesM(m) => format!("TypeRef::VarOpaque({})", max(m)),
+        Ty::StringM(m) => format!("TypeRef::String({})", max(m)),
+        Ty::VecM(t, m) => format!("TypeRef::VarArray(&{}, {})", type_ref(t), max(m)),
+    }
+}
@@ -0,0 +1,413 @@
+//! Parsing of the code generated by xdrgen into the definitions it contains.
+
+use crate::ty::Ty;
+
+/// `Source` is the code generated by xdrgen, split into the parts that are
+/// kept, replaced or extended.
+pub struct Source {
+    /// The module comment and `XDR_FILES_SHA256`, kept as they are.
+    pub prelude: String,
+    /// The XDR files the code was generated from.
+    pub files: Vec<String>,
+    /// The constants and types, in the order they are defined.
+    pub defs: Vec<Def>,
+    /// The names of the types, in the order of `TypeVariant`.
+    pub variants: Vec<String>,
+    /// `TypeVariant`, and `Type` up to its constants, kept as they are.
+    pub type_head: String,
+}
+
+/// `Def` is the code generated for one XDR constant or type.
+pub struct Def {
+    pub name: String,
+    /// The name of the definition in the XDR, or `None` if it is nested in
+    /// another definition.
+    pub xdr_name: Option<String>,
+    pub kind: Kind,
+    /// The top level items generated for the definition, each including its
+    /// doc comments and attributes.
+    pub items: Vec<String>,
+}
+
+pub enum Kind {
+    Const,
+    Struct(Vec<Field>),
+    Enum(Vec<EnumCase>),
+    Union(Union),
+    /// A typedef generated as a newtype, or as a type alias.
+    Typedef {
+        ty: Ty,
+        read: String,
+        alias: bool,
+    },
+}
+
+pub struct Field {
+    pub name: String,
+    pub ty: Ty,
+    /// The expression xdrgen uses to read the field, such as `VecM::<Hash>`.
+    pub read: String,
+}
+
+pub struct EnumCase {
+    pub name: String,
+    pub value: i32,
+}
+
+pub struct Union {
+    /// The discriminant type, `i32` or an enum.
+    pub discriminant: String,
+    pub arms: Vec<Arm>,
+}
+
+pub struct Arm {
+    /// The pattern that matches the discriminant, such as `MemoType::Text` or
+    /// `0`.
+    pub case: String,
+    pub name: String,
+    /// The type and read expression of the arm, or `None` if it is void.
+    pub ty: Option<(Ty, String)>,
+}
+
+impl Def {
+    /// Returns the item with the first line of code `sig`.
+    pub fn item(&self, sig: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|i| signature(i) == sig)
+            .map(String::as_str)
+    }
+
+    /// Returns the item that defines the constant or type.
+    pub fn definition(&self) -> &str {
+        self.items
+            .iter()
+            .find(|i| {
+                let s = signature(i);
+                ["pub const ", "pub struct ", "pub enum ", "pub type "]
+                    .iter()
+                    .any(|p| s.starts_with(p))
+            })
+            .expect("definition")
+    }
+}
+
+impl Source {
+    pub fn parse(s: &str) -> Source {
+        let lines: Vec<&str> = s.lines().collect();
+        let header = lines
+            .iter()
+            .position(|l| l.starts_with("use core::"))
+            .expect("header");
+        let first = lines
+            .iter()
+            .position(|l| l.starts_with("/// ") && l.contains(" is an XDR "))
+            .expect("definitions");
+        let type_variant = lines
+            .iter()
+            .position(|l| *l == "pub enum TypeVariant {")
+            .expect("TypeVariant");
+        let mut end = type_variant;
+        while !lines[end - 1].is_empty() {
+            end -= 1;
+        }
+
+        let prelude = lines[..header].join("\n") + "\n";
+        let files = lines[..header]
+            .iter()
+            .filter_map(|l| l.strip_prefix("//  "))
+            .map(str::to_string)
+            .collect();
+
+        let mut defs: Vec<Def> = Vec::new();
+        for item in items(&lines[first..end]) {
+            let mut doc = item
+                .lines()
+                .skip_while(|l| l.is_empty())
+                .map_while(|l| l.strip_prefix("///"));
+            if let Some((name, xdr_kind)) = doc
+                .next()
+                .and_then(|l| l.strip_prefix(' '))
+                .and_then(|l| l.split_once(" is an XDR "))
+            {
+                let xdr = doc
+                    .skip_while(|l| *l != " ```text")
+                    .nth(1)
+                    .and_then(|l| l.strip_prefix(' '))
+                    .unwrap_or_default();
+                defs.push(Def {
+                    name: name.to_string(),
+                    xdr_name: if xdr_kind.starts_with("Nested") {
+                        None
+                    } else {
+                        xdr_name(xdr).map(str::to_string)
+                    },
+                    kind: Kind::Const,
+                    items: Vec::new(),
+                });
+            }
+            defs.last_mut().expect("definition").items.push(item);
+        }
+        for def in &mut defs {
+            def.kind = kind(def);
+        }
+
+        let read_xdr = lines
+            .iter()
+            .position(|l| l.starts_with("    pub fn read_xdr<R: Read>(v: TypeVariant"))
+            .expect("Type::read_xdr");
+        let mut type_head = lines[end..read_xdr].to_vec();
+        while type_head.last().is_some_and(|l| l.starts_with("    #[")) {
+            type_head.pop();
+        }
+        let type_head = type_head.join("\n") + "\n";
+
+        let variants = lines[type_variant + 1..]
+            .iter()
+            .take_while(|l| **l != "}")
+            .map(|l| l.trim().trim_end_matches(',').to_string())
+            .collect();
+
+        Source {
+            prelude,
+            files,
+            defs,
+            variants,
+            type_head,
+        }
+    }
+}
+
+/// Returns the name defined by the first line of an XDR definition, such as
+/// `SCPBallot` for `struct SCPBallot` or `Value` for `typedef opaque Value<>;`.
+pub fn xdr_name(line: &str) -> Option<&str> {
+    let mut words = line.split_whitespace();
+    match words.next()? {
+        "struct" | "enum" | "union" | "const" => words.next().map(|w| w.trim_end_matches('{')),
+        "typedef" => line
+            .trim_end()
+            .strip_suffix(';')?
+            .rsplit(|c: char| c.is_whitespace() || c == '*')
+            .next()?
+            .split(['<', '['])
+            .next(),
+        _ => None,
+    }
+}
+
+/// Splits lines into top level items, each with the blank lines before it,
+/// and its doc comments and attributes.
+pub fn items(lines: &[&str]) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut cur: Vec<&str> = Vec::new();
+    let mut depth = 0i32;
+    for l in lines {
+        cur.push(l);
+        let t = l.trim_start();
+        if t.is_empty() || t.starts_with("//") {
+            continue;
+        }
+        depth += braces(l);
+        if depth == 0
+            && !t.starts_with('#')
+            && !l.starts_with(' ')
+            && !l.starts_with(')')
+            && (t.ends_with('}') || t.ends_with(';'))
+        {
+            items.push(cur.join("\n"));
+            cur.clear();
+        }
+    }
+    assert!(
+        cur.iter().all(|l| l.is_empty()),
+        "unterminated item: {}",
+        cur.join("\n")
+    );
+    items
+}
+
+/// Returns the change in brace depth of a line, ignoring string literals.
+fn braces(l: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_str = false;
+    let mut escaped = false;
+    for c in l.chars() {
+        if in_str {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_str = false,
+                _ => {}
+            }
+        } else {
+            match c {
+                '"' => in_str = true,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    depth
+}
+
+/// Returns the first line of code of an item, after its doc comments and
+/// attributes.
+pub fn signature(item: &str) -> &str {
+    item.lines()
+        .find(|l| {
+            !(l.is_empty()
+                || l.starts_with("//")
+                || l.starts_with('#')
+                || l.starts_with(' ')
+                || l.starts_with(')'))
+        })
+        .unwrap_or_default()
+}
+
+/// Joins the trimmed lines of code, removing the line breaks and indentation
+/// added by rustfmt.
+fn compact(s: &str) -> String {
+    let mut out = String::new();
+    for l in s.lines() {
+        let t = l.trim();
+        if out.ends_with(',') && !t.starts_with(')') && !t.starts_with('}') {
+            out.push(' ');
+        }
+        out.push_str(t);
+    }
+    out.replace(",)", ")").replace(", )", ")")
+}
+
+fn kind(def: &Def) -> Kind {
+    let name = &def.name;
+    let read = def
+        .item(&format!("impl ReadXdr for {name} {{"))
+        .map(compact);
+    let item = def.definition();
+    let definition = signature(item);
+    let body = |item: &str| -> Vec<String> {
+        item.lines()
+            .skip_while(|l| *l != definition)
+            .skip(1)
+            .take_while(|l| *l != "}")
+            .map(|l| l.trim().trim_end_matches(',').to_string())
+            .collect()
+    };
+    if definition.starts_with("pub const ") {
+        Kind::Const
+    } else if let Some(ty) = definition
+        .strip_prefix(&format!("pub type {name} = "))
+        .and_then(|t| t.strip_suffix(';'))
+    {
+        Kind::Typedef {
+            ty: Ty::parse(ty),
+            read: String::new(),
+            alias: true,
+        }
+    } else if let Some(ty) = definition
+        .strip_prefix(&format!("pub struct {name}(pub "))
+        .and_then(|t| t.strip_suffix(");"))
+    {
+        let read = read.expect("read");
+        let read = between(&read, "let i = ", "::read_xdr(r)?;").to_string();
+        Kind::Typedef {
+            ty: Ty::parse(ty),
+            read,
+            alias: false,
+        }
+    } else if definition.starts_with("pub struct ") {
+        let read = read.expect("read");
+        let mut rest = between(&read, "Ok(Self {", "})").to_string();
+        let fields = body(item)
+            .iter()
+            .map(|l| {
+                let (n, t) = l
+                    .strip_prefix("pub ")
+                    .and_then(|l| l.split_once(": "))
+                    .expect("field");
+                let start = rest.find(&format!("{n}: ")).expect("field read") + n.len() + 2;
+                let end = rest[start..].find("::read_xdr(r)?").expect("field read") + start;
+                let read = rest[start..end].to_string();
+                rest = rest[end..].to_string();
+                Field {
+                    name: n.to_string(),
+                    ty: Ty::parse(t),
+                    read,
+                }
+            })
+            .collect();
+        Kind::Struct(fields)
+    } else if definition.starts_with("pub enum ")
+        && read
+            .as_deref()
+            .is_some_and(|r| r.contains("let e = i32::read_xdr(r)?;"))
+    {
+        let cases = body(item)
+            .iter()
+            .map(|l| {
+                let (n, v) = l.split_once(" = ").expect("enum case");
+                EnumCase {
+                    name: n.to_string(),
+                    value: v.parse().expect("enum value"),
+                }
+            })
+            .collect();
+        Kind::Enum(cases)
+    } else {
+        let read = read.expect("read");
+        let discriminant = between(&read, "let dv: ", " =").to_string();
+        let mut rest = between(&read, "match dv {", "#[allow(unreachable_patterns)]");
+        let variants = body(item);
+        let mut arms = Vec::new();
+        while !rest.is_empty() {
+            let (case, r) = rest.split_once(" => ").expect("arm");
+            let (arm, r) = if let Some(r) = r.strip_prefix('{') {
+                let end = r.find('}').expect("arm block");
+                (&r[..end], r[end + 1..].trim_start_matches(','))
+            } else {
+                let end = top_level_comma(r);
+                (&r[..end], &r[end + 1..])
+            };
+            rest = r.trim_start();
+            let arm = arm.strip_prefix("Self::").expect("arm variant");
+            let (name, ty) = match arm.split_once('(') {
+                Some((n, r)) => {
+                    let read = r.strip_suffix("::read_xdr(r)?)").expect("arm read");
+                    let ty = variants
+                        .iter()
+                        .find_map(|v| v.strip_prefix(&format!("{n}(")))
+                        .and_then(|t| t.strip_suffix(')'))
+                        .expect("arm type");
+                    (n, Some((Ty::parse(ty), read.to_string())))
+                }
+                None => (arm, None),
+            };
+            arms.push(Arm {
+                case: case.to_string(),
+                name: name.to_string(),
+                ty,
+            });
+        }
+        Kind::Union(Union { discriminant, arms })
+    }
+}
+
+fn between<'a>(s: &'a str, start: &str, end: &str) -> &'a str {
+    let i = s.find(start).unwrap_or_else(|| panic!("{start} in {s}")) + start.len();
+    let j = s[i..].find(end).unwrap_or_else(|| panic!("{end} in {s}")) + i;
+    &s[i..j]
+}
+
+fn top_level_comma(s: &str) -> usize {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => return i,
+            _ => {}
+        }
+    }
+    s.len()
+}
@@ -0,0 +1,196 @@
+//! Rust types as they appear in the code generated by xdrgen.
+
+use std::fmt;
+
+/// `Ty` is a Rust type used by a field, union arm or typedef of the code
+/// generated by xdrgen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ty {
+    /// A primitive, one of `i32`, `u32`, `i64`, `u64` or `bool`.
+    Prim(String),
+    /// A type defined in the XDR.
+    Named(String),
+    Box(Box<Ty>),
+    Option(Box<Ty>),
+    /// `VecM<T, MAX>`.
+    VecM(Box<Ty>, Option<u32>),
+    /// `BytesM<MAX>`.
+    BytesM(Option<u32>),
+    /// `StringM<MAX>`.
+    StringM(Option<u32>),
+    /// `[T; N]`, which is fixed length opaque data when `T` is `u8`.
+    Array(Box<Ty>, u32),
+}
+
+impl Ty {
+    /// Parses a type as it is written by xdrgen, such as `VecM<Hash, 10>`.
+    pub fn parse(s: &str) -> Ty {
+        let tokens = tokenize(s);
+        let mut p = Parser { tokens, pos: 0 };
+        let ty = p.ty();
+        assert!(p.pos == p.tokens.len(), "unparsed tokens in type: {s}");
+        ty
+    }
+
+    /// Returns true if the type is, or contains, variable length opaque or
+    /// string data, or an XDR type for which `borrowed` returns true.
+    pub fn is_borrowed(&self, borrowed: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Ty::BytesM(_) | Ty::StringM(_) => true,
+            Ty::Named(n) => borrowed(n),
+            Ty::Box(t) | Ty::Option(t) | Ty::VecM(t, _) | Ty::Array(t, _) => {
+                t.is_borrowed(borrowed)
+            }
+            Ty::Prim(_) => false,
+        }
+    }
+
+    /// Renders the borrowed form of the type, as it is named inside the
+    /// `borrowed` or `shared` module, with the lifetime of the borrowed types
+    /// if they have one.
+    pub fn borrowed(&self, borrowed: &impl Fn(&str) -> bool, lifetime: Option<&str>) -> String {
+        let params = |max: &Option<u32>| {
+            let params: Vec<String> = lifetime
+                .map(str::to_string)
+                .into_iter()
+                .chain(max.map(|m| m.to_string()))
+                .collect();
+            if params.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", params.join(", "))
+            }
+        };
+        let inner = |t: &Ty| t.borrowed(borrowed, lifetime);
+        match self {
+            Ty::Named(n) if borrowed(n) => format!("{n}{}", params(&None)),
+            Ty::BytesM(m) => format!("BytesM{}", params(m)),
+            Ty::StringM(m) => format!("StringM{}", params(m)),
+            Ty::Box(t) => format!("Box<{}>", inner(t)),
+            Ty::Option(t) => format!("Option<{}>", inner(t)),
+            Ty::VecM(t, None) => format!("VecM<{}>", inner(t)),
+            Ty::VecM(t, Some(m)) => format!("VecM<{}, {m}>", inner(t)),
+            Ty::Array(t, n) => format!("[{}; {n}]", inner(t)),
+            t => t.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Prim(n) | Ty::Named(n) => write!(f, "{n}"),
+            Ty::Box(t) => write!(f, "Box<{t}>"),
+            Ty::Option(t) => write!(f, "Option<{t}>"),
+            Ty::VecM(t, None) => write!(f, "VecM<{t}>"),
+            Ty::VecM(t, Some(m)) => write!(f, "VecM<{t}, {m}>"),
+            Ty::BytesM(None) => write!(f, "BytesM"),
+            Ty::BytesM(Some(m)) => write!(f, "BytesM<{m}>"),
+            Ty::StringM(None) => write!(f, "StringM"),
+            Ty::StringM(Some(m)) => write!(f, "StringM<{m}>"),
+            Ty::Array(t, n) => write!(f, "[{t}; {n}]"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut cur = String::new();
+    for c in s.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            cur.push(c);
+        } else {
+            if !cur.is_empty() {
+                tokens.push(std::mem::take(&mut cur));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        }
+    }
+    if !cur.is_empty() {
+        tokens.push(cur);
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> String {
+        let t = self.tokens[self.pos].clone();
+        self.pos += 1;
+        t
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn expect(&mut self, t: &str) {
+        let n = self.next();
+        assert!(n == t, "expected {t}, found {n}");
+    }
+
+    fn len(&mut self) -> u32 {
+        let n = self.next();
+        n.parse()
+            .unwrap_or_else(|_| panic!("expected a length, found {n}"))
+    }
+
+    fn ty(&mut self) -> Ty {
+        if self.peek() == Some("[") {
+            self.next();
+            let t = self.ty();
+            self.expect(";");
+            let n = self.len();
+            self.expect("]");
+            return Ty::Array(Box::new(t), n);
+        }
+        let name = self.next();
+        match name.as_str() {
+            "i32" | "u32" | "i64" | "u64" | "bool" | "u8" => Ty::Prim(name),
+            "Box" | "Option" => {
+                self.expect("<");
+                let t = Box::new(self.ty());
+                self.expect(">");
+                if name == "Box" {
+                    Ty::Box(t)
+                } else {
+                    Ty::Option(t)
+                }
+            }
+            "VecM" => {
+                self.expect("<");
+                let t = Box::new(self.ty());
+                let max = if self.peek() == Some(",") {
+                    self.next();
+                    Some(self.len())
+                } else {
+                    None
+                };
+                self.expect(">");
+                Ty::VecM(t, max)
+            }
+            "BytesM" | "StringM" => {
+                let max = if self.peek() == Some("<") {
+                    self.next();
+                    let m = self.len();
+                    self.expect(">");
+                    Some(m)
+                } else {
+                    None
+                };
+                if name == "BytesM" {
+                    Ty::BytesM(max)
+                } else {
+                    Ty::StringM(max)
+                }
+            }
+            _ => Ty::Named(name),
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! The code generated for `Type`, the enum of all the XDR types, extending the
+//! code generated by xdrgen with error paths, skipping, framing, visiting and
+//! diffing.
+
+use std::collections::HashMap;
+
+use crate::source::{Def, Kind};
+use crate::ty::Ty;
+use crate::types::unit_arm;
+
+/// Renders `Type`'s methods and trait impls, following the `TypeVariant` and
+/// `Type` definitions and constants that are kept from xdrgen in `head`.
+pub fn render(head: &str, variants: &[String]) -> String {
+    let each = |f: &dyn Fn(&str) -> String| -> String { variants.iter().map(|t| f(t)).collect() };
+    let read = each(&|t| {
+        format!("TypeVariant::{t} => r.with_limited_depth(|r| Ok(Self::{t}(Box::new({t}::read_xdr(r)?)))),\n")
+    });
+    let read_iter = |reader: &str, frame: bool| {
+        each(&|t| {
+            let (ty, v) = if frame {
+                (format!("Frame<{t}>"), "t.0")
+            } else {
+                (t.to_string(), "t")
+            };
+            format!(
+                "TypeVariant::{t} => Box::new(ReadXdrIter::<_, {ty}>::new({reader}, r.limits.clone(), r.error_context).map(|r| r.map(|t| Self::{t}(Box::new({v}))))),\n"
+            )
+        })
+    };
+    let read_iter_plain = read_iter("&mut r.inner", false);
+    let read_iter_framed = read_iter("&mut r.inner", true);
+    let read_iter_base64 = read_iter("dec", false);
+    let skip = each(&|t| format!("TypeVariant::{t} => r.with_limited_depth({t}::skip_xdr),\n"));
+    let read_json = each(&|t| {
+        format!("TypeVariant::{t} => Ok(Self::{t}(Box::new(serde_json::from_reader(r)?))),\n")
+    });
+    let value = each(&|t| format!("Self::{t}(ref v) => v.as_ref(),\n"));
+    let name = each(&|t| format!("Self::{t}(_) => \"{t}\",\n"));
+    let variant = each(&|t| format!("Self::{t}(_) => TypeVariant::{t},\n"));
+    let write = each(&|t| format!("Self::{t}(v) => v.write_xdr(w),\n"));
+    let xdr_len = each(&|t| format!("Self::{t}(v) => v.xdr_len_at_depth(depth),\n"));
+    let walk = each(&|t| {
+        format!("Self::{t}(v) => {{ walk_at(path, PathSegment::Type(\"{t}\"), v, visitor); }}\n")
+    });
+    let walk_mut = each(&|t| {
+        format!(
+            "Self::{t}(v) => {{ walk_mut_at(path, PathSegment::Type(\"{t}\"), v, visitor); }}\n"
+        )
+    });
+    let diff = each(&|t| {
+        unit_arm(
+            12,
+            &format!("(Self::{t}(a), Self::{t}(b))"),
+            &format!("diff_at(path, PathSegment::Type(\"{t}\"), a, b, differences)"),
+        )
+    });
+    let n = variants.len();
+    format!(
+        r#"{head}    #[cfg(feature = "alloc")]
+    #[allow(clippy::too_many_lines)]
+    pub fn read_xdr<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<Self> {{
+        r.with_path(PathSegment::Type(v.name()), |r| match v {{
+{read}        }})
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn read_xdr_base64<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<Self> {{
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        let t = Self::read_xdr(v, &mut dec)?;
+        Ok(t)
+    }}
+
+    #[cfg(feature = "alloc")]
+    pub fn read_xdr_to_end<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<Self> {{
+        let s = Self::read_xdr(v, r)?;
+        // Check that any further reads, such as this read of one byte, read no
+        // data, indicating EOF. If a byte is read the data is invalid.
+        if r.read(&mut [0u8; 1])? == 0 {{
+            Ok(s)
+        }} else {{
+            Err(Error::Invalid)
+        }}
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn read_xdr_base64_to_end<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<Self> {{
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        let t = Self::read_xdr_to_end(v, &mut dec)?;
+        Ok(t)
+    }}
+
+    #[cfg(feature = "std")]
+    #[allow(clippy::too_many_lines)]
+    pub fn read_xdr_iter<R: Read>(
+        v: TypeVariant,
+        r: &mut Limited<R>,
+    ) -> Box<dyn Iterator<Item = Result<Self>> + '_> {{
+        let iter: Box<dyn Iterator<Item = Result<Self>> + '_> = match v {{
+{read_iter_plain}        }};
+        Box::new(iter.map(move |r| r.map_err(|e| e.with_root(PathSegment::Type(v.name())))))
+    }}
+
+    #[cfg(feature = "std")]
+    #[allow(clippy::too_many_lines)]
+    pub fn read_xdr_framed_iter<R: Read>(
+        v: TypeVariant,
+        r: &mut Limited<R>,
+    ) -> Box<dyn Iterator<Item = Result<Self>> + '_> {{
+        let iter: Box<dyn Iterator<Item = Result<Self>> + '_> = match v {{
+{read_iter_framed}        }};
+        Box::new(iter.map(move |r| r.map_err(|e| e.with_root(PathSegment::Type(v.name())))))
+    }}
+
+    #[cfg(feature = "base64")]
+    #[allow(clippy::too_many_lines)]
+    pub fn read_xdr_base64_iter<R: Read>(
+        v: TypeVariant,
+        r: &mut Limited<R>,
+    ) -> Box<dyn Iterator<Item = Result<Self>> + '_> {{
+        let dec = base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD);
+        let iter: Box<dyn Iterator<Item = Result<Self>> + '_> = match v {{
+{read_iter_base64}        }};
+        Box::new(iter.map(move |r| r.map_err(|e| e.with_root(PathSegment::Type(v.name())))))
+    }}
+
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::too_many_lines)]
+    pub fn skip_xdr<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<()> {{
+        r.with_path(PathSegment::Type(v.name()), |r| match v {{
+{skip}        }})
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn skip_xdr_base64<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<()> {{
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        Self::skip_xdr(v, &mut dec)
+    }}
+
+    #[cfg(feature = "alloc")]
+    pub fn skip_xdr_to_end<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<()> {{
+        Self::skip_xdr(v, r)?;
+        // Check that any further reads, such as this read of one byte, read no
+        // data, indicating EOF. If a byte is read the data is invalid.
+        if r.read(&mut [0u8; 1])? == 0 {{
+            Ok(())
+        }} else {{
+            Err(Error::Invalid)
+        }}
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn skip_xdr_base64_to_end<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> Result<()> {{
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        Self::skip_xdr_to_end(v, &mut dec)
+    }}
+
+    #[cfg(feature = "std")]
+    pub fn skip_xdr_iter<R: Read>(v: TypeVariant, r: &mut Limited<R>) -> SkipXdrIter<&mut R> {{
+        SkipXdrIter::new(&mut r.inner, r.limits.clone(), r.error_context, v, false)
+    }}
+
+    #[cfg(feature = "std")]
+    pub fn skip_xdr_framed_iter<R: Read>(
+        v: TypeVariant,
+        r: &mut Limited<R>,
+    ) -> SkipXdrIter<&mut R> {{
+        SkipXdrIter::new(&mut r.inner, r.limits.clone(), r.error_context, v, true)
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn skip_xdr_base64_iter<R: Read>(
+        v: TypeVariant,
+        r: &mut Limited<R>,
+    ) -> SkipXdrIter<base64::read::DecoderReader<'_, R>> {{
+        let dec = base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD);
+        SkipXdrIter::new(dec, r.limits.clone(), r.error_context, v, false)
+    }}
+
+    #[cfg(feature = "alloc")]
+    pub fn from_xdr<B: AsRef<[u8]>>(v: TypeVariant, bytes: B, limits: Limits) -> Result<Self> {{
+        let mut r = Limited::new(bytes.as_ref(), limits);
+        let t = Self::read_xdr_to_end(v, &mut r)?;
+        Ok(t)
+    }}
+
+    #[cfg(feature = "base64")]
+    pub fn from_xdr_base64(v: TypeVariant, b64: impl AsRef<[u8]>, limits: Limits) -> Result<Self> {{
+        let mut b64_reader = b64.as_ref();
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut b64_reader, base64::STANDARD),
+            limits,
+        );
+        let t = Self::read_xdr_to_end(v, &mut dec)?;
+        Ok(t)
+    }}
+
+    #[cfg(feature = "bytes")]
+    pub fn read_xdr_buf<B: bytes::Buf>(
+        v: TypeVariant,
+        buf: &mut B,
+        limits: Limits,
+    ) -> Result<Self> {{
+        let mut r = Limited::new(bytes::Buf::reader(buf), limits);
+        let t = Self::read_xdr(v, &mut r)?;
+        Ok(t)
+    }}
+
+    #[cfg(feature = "bytes")]
+    pub fn from_xdr_buf(v: TypeVariant, buf: impl bytes::Buf, limits: Limits) -> Result<Self> {{
+        let mut r = Limited::new(buf.reader(), limits);
+        let t = Self::read_xdr_to_end(v, &mut r)?;
+        Ok(t)
+    }}
+
+    /// Write the value as a single record with fragment headers.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be written, or exceeds the limits.
+    #[cfg(feature = "alloc")]
+    pub fn write_xdr_framed<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {{
+        write_framed(w, |w| self.write_xdr(w))
+    }}
+
+    #[cfg(all(feature = "std", feature = "serde_json"))]
+    #[allow(clippy::too_many_lines)]
+    pub fn read_json(v: TypeVariant, r: impl Read) -> Result<Self> {{
+        match v {{
+{read_json}        }}
+    }}
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub fn value(&self) -> &dyn core::any::Any {{
+        #[allow(clippy::match_same_arms)]
+        match self {{
+{value}        }}
+    }}
+
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub const fn name(&self) -> &'static str {{
+        match self {{
+{name}        }}
+    }}
+
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub const fn variants() -> [TypeVariant; {n}] {{
+        Self::VARIANTS
+    }}
+
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub const fn variant(&self) -> TypeVariant {{
+        match self {{
+{variant}        }}
+    }}
+}}
+
+impl Name for Type {{
+    #[must_use]
+    fn name(&self) -> &'static str {{
+        Self::name(self)
+    }}
+}}
+
+impl Variants<TypeVariant> for Type {{
+    fn variants() -> slice::Iter<'static, TypeVariant> {{
+        Self::VARIANTS.iter()
+    }}
+}}
+
+impl WriteXdr for Type {{
+    #[allow(clippy::too_many_lines)]
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {{
+        match self {{
+{write}        }}
+    }}
+
+    #[allow(clippy::too_many_lines)]
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {{
+        match self {{
+{xdr_len}        }}
+    }}
+}}
+
+/// Visiting a `Type` visits the value it contains, located at a path starting
+/// with the name of the type.
+#[cfg(feature = "alloc")]
+impl Visit for Type {{
+    #[allow(clippy::too_many_lines)]
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {{
+        match self {{
+{walk}        }}
+    }}
+}}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for Type {{
+    #[allow(clippy::too_many_lines)]
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {{
+        match self {{
+{walk_mut}        }}
+    }}
+}}
+
+/// Diffing a `Type` compares the values it contains if they are the same
+/// type, located at a path starting with the name of the type, and otherwise
+/// reports the values as changed.
+#[cfg(feature = "alloc")]
+impl Diff for Type {{
+    #[allow(clippy::too_many_lines)]
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {{
+        match (self, other) {{
+{diff}            _ => differ(path, DifferenceKind::Changed, differences),
+        }}
+    }}
+}}
+"#
+    )
+}
+
+/// Renders `Type::from_any`, which converts the values of each of the types
+/// back into a `Type`.
+pub fn render_from_any(defs: &[Def], variants: &[String]) -> String {
+    let defs: HashMap<&str, &Def> = defs.iter().map(|d| (d.name.as_str(), d)).collect();
+    let downcasts: String = variants
+        .iter()
+        .map(|t| {
+            let copy = matches!(
+                defs[t.as_str()].kind,
+                Kind::Enum(_) | Kind::Typedef { ty: Ty::Prim(_), alias: true, .. }
+            );
+            let v = if copy { "*v" } else { "v.clone()" };
+            format!(
+                "if let Some(v) = v.downcast_ref::<{t}>() {{ return Some(Self::{t}(Box::new({v}))); }}\n"
+            )
+        })
+        .collect();
+    format!(
+        r#"#[cfg(feature = "alloc")]
+impl Type {{
+    /// Returns the value as a `Type` if it is one of the XDR types, such as the
+    /// values passed to a [`Visitor`].
+    ///
+    /// Types that are aliases of the same Rust type, such as `Uint32` and
+    /// `u32`, are returned as the first of the aliases in [`TypeVariant`].
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub fn from_any(v: &dyn Any) -> Option<Self> {{
+{downcasts}        None
+    }}
+}}
+"#
+    )
+}
@@ -0,0 +1,305 @@
+//! The code generated for each XDR type, extending the code generated by
+//! xdrgen with skipping, error paths, visiting and diffing.
+
+use std::fmt::Write;
+
+use crate::len::{self, Defs};
+use crate::source::{signature, Def, Kind, Union};
+use crate::ty::Ty;
+
+/// Renders the items of a definition, replacing or extending the items
+/// generated by xdrgen.
+pub fn render(def: &Def, defs: &Defs) -> String {
+    let name = &def.name;
+    let mut out = String::new();
+    for item in &def.items {
+        let sig = signature(item);
+        if sig == format!("impl ReadXdr for {name} {{") {
+            let code = item.trim_start_matches('\n');
+            out += &item[..item.len() - code.len()];
+            out += &read_xdr(def, code);
+            out += "\n";
+        } else if sig == format!("impl WriteXdr for {name} {{") {
+            let item = item.replace("    #[cfg(feature = \"std\")]\n", "");
+            let end = item.rfind('}').expect("impl end");
+            out += &item[..end];
+            out += "\n";
+            out += &len::render(def, defs);
+            out += "}\n\n";
+            out += &visit(def);
+            out += "\n";
+        } else {
+            out += item;
+            out += "\n";
+        }
+        if sig == format!("impl TryFrom<Vec<u8>> for {name} {{") {
+            if let Kind::Typedef {
+                ty: Ty::BytesM(_), ..
+            } = def.kind
+            {
+                out += &bytes(name);
+            }
+        }
+    }
+    out
+}
+
+/// Returns the expression for skipping a type, given the expression xdrgen
+/// uses for reading it.
+fn skip(read: &str) -> String {
+    match read {
+        "BytesM" | "StringM" => format!("<{read}>"),
+        _ => read.to_string(),
+    }
+}
+
+fn read_xdr(def: &Def, item: &str) -> String {
+    let name = &def.name;
+    let item = item.replace(
+        "    #[cfg(feature = \"std\")]\n",
+        "    #[cfg(feature = \"alloc\")]\n",
+    );
+    match &def.kind {
+        Kind::Typedef { read, .. } => {
+            let skip = skip(read);
+            let end = item.rfind("\n}").expect("impl end");
+            format!(
+                "{}
+
+    #[cfg(feature = \"alloc\")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {{
+        r.with_limited_depth(|r| {skip}::skip_xdr(r))
+    }}
+}}",
+                &item[..end]
+            )
+        }
+        Kind::Struct(fields) => {
+            let mut reads = String::new();
+            let mut skips = String::new();
+            for f in fields {
+                let n = &f.name;
+                let _ = writeln!(
+                    reads,
+                    "{n}: r.with_path(PathSegment::Field(\"{n}\"), {}::read_xdr)?,",
+                    f.read
+                );
+                let _ = writeln!(
+                    skips,
+                    "r.with_path(PathSegment::Field(\"{n}\"), {}::skip_xdr)?;",
+                    skip(&f.read)
+                );
+            }
+            format!(
+                "impl ReadXdr for {name} {{
+    #[cfg(feature = \"alloc\")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {{
+        r.with_limited_depth(|r| {{
+            Ok(Self {{
+{reads}            }})
+        }})
+    }}
+
+    #[cfg(feature = \"alloc\")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {{
+        r.with_limited_depth(|r| {{
+{skips}            Ok(())
+        }})
+    }}
+}}"
+            )
+        }
+        Kind::Union(Union {
+            discriminant: d,
+            arms,
+        }) => {
+            let mut reads = String::new();
+            let mut skips = String::new();
+            for a in arms {
+                let (case, n) = (&a.case, &a.name);
+                if let Some((_, read)) = &a.ty {
+                    let _ = writeln!(
+                        reads,
+                        "{case} => Self::{n}(r.with_path(PathSegment::Arm(\"{n}\"), {read}::read_xdr)?),"
+                    );
+                    let _ = writeln!(
+                        skips,
+                        "{case} => r.with_path(PathSegment::Arm(\"{n}\"), {}::skip_xdr),",
+                        skip(read)
+                    );
+                } else {
+                    let _ = writeln!(reads, "{case} => Self::{n},");
+                    let _ = writeln!(skips, "{case} => Ok(()),");
+                }
+            }
+            format!(
+                "impl ReadXdr for {name} {{
+    #[cfg(feature = \"alloc\")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {{
+        r.with_limited_depth(|r| {{
+            let dv: {d} = <{d} as ReadXdr>::read_xdr(r)?;
+            #[allow(clippy::match_same_arms, clippy::match_wildcard_for_single_variants)]
+            let v = match dv {{
+{reads}                #[allow(unreachable_patterns)]
+                _ => return Err(Error::Invalid),
+            }};
+            Ok(v)
+        }})
+    }}
+
+    #[cfg(feature = \"alloc\")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {{
+        r.with_limited_depth(|r| {{
+            let dv: {d} = <{d} as ReadXdr>::read_xdr(r)?;
+            #[allow(clippy::match_same_arms, clippy::match_wildcard_for_single_variants)]
+            match dv {{
+{skips}                #[allow(unreachable_patterns)]
+                _ => Err(Error::Invalid),
+            }}
+        }})
+    }}
+}}"
+            )
+        }
+        Kind::Enum(_) | Kind::Const => item,
+    }
+}
+
+/// Renders a match arm that calls a function returning `()`, in the form
+/// that rustfmt formats the same as the arm with the call as an expression,
+/// and that ends the call with a semicolon when rustfmt would move it into a
+/// block.
+pub fn unit_arm(indent: usize, pat: &str, call: &str) -> String {
+    let open = call.find('(').expect("call") + 1;
+    let args = call.len() - open - 1;
+    let fits = |width: usize| width <= 100 && args <= 60;
+    let same_line = fits(indent + pat.len() + 4 + call.len() + 1);
+    let next_line = fits(indent + 4 + call.len() + 1);
+    let pat_line = indent + pat.len() + 5 <= 100;
+    let call_line = indent + pat.len() + 4 + open < 100;
+    if same_line || (!next_line && (call_line || !pat_line)) {
+        format!("{pat} => {call},\n")
+    } else {
+        format!("{pat} => {{ {call}; }}\n")
+    }
+}
+
+fn visit(def: &Def) -> String {
+    let name = &def.name;
+    let (walk, walk_mut, diff) = match &def.kind {
+        Kind::Typedef { .. } => (
+            "self.0.walk(path, visitor);\n".to_string(),
+            "self.0.walk_mut(path, visitor);\n".to_string(),
+            Some("self.0.diff_into(&other.0, path, differences);\n".to_string()),
+        ),
+        Kind::Struct(fields) => {
+            let mut walk = String::new();
+            let mut walk_mut = String::new();
+            let mut diff = String::new();
+            for f in fields {
+                let n = &f.name;
+                let _ = writeln!(
+                    walk,
+                    "walk_at(path, PathSegment::Field(\"{n}\"), &self.{n}, visitor);"
+                );
+                let _ = writeln!(
+                    walk_mut,
+                    "walk_mut_at(path, PathSegment::Field(\"{n}\"), &mut self.{n}, visitor);"
+                );
+                let _ = writeln!(
+                    diff,
+                    "diff_at(path, PathSegment::Field(\"{n}\"), &self.{n}, &other.{n}, differences);"
+                );
+            }
+            (walk, walk_mut, Some(diff))
+        }
+        Kind::Union(Union { arms, .. }) => {
+            let mut walk = String::new();
+            let mut walk_mut = String::new();
+            let mut diff = String::new();
+            for a in arms {
+                let n = &a.name;
+                if a.ty.is_some() {
+                    let _ = writeln!(
+                        walk,
+                        "Self::{n}(v) => {{ walk_at(path, PathSegment::Arm(\"{n}\"), v, visitor); }}"
+                    );
+                    let _ = writeln!(
+                        walk_mut,
+                        "Self::{n}(v) => {{ walk_mut_at(path, PathSegment::Arm(\"{n}\"), v, visitor); }}"
+                    );
+                    diff += &unit_arm(
+                        12,
+                        &format!("(Self::{n}(a), Self::{n}(b))"),
+                        &format!("diff_at(path, PathSegment::Arm(\"{n}\"), a, b, differences)"),
+                    );
+                } else {
+                    let _ = writeln!(walk, "Self::{n} => (),");
+                    let _ = writeln!(walk_mut, "Self::{n} => (),");
+                    let _ = writeln!(diff, "(Self::{n}, Self::{n}) => (),");
+                }
+            }
+            if arms.len() > 1 {
+                diff += "_ => differ(path, DifferenceKind::ArmChanged { from: self.name(), to: other.name() }, differences),\n";
+            }
+            let diff = (arms.len() > 1 || arms[0].ty.is_some()).then(|| {
+                format!("#[allow(clippy::match_same_arms)]\nmatch (self, other) {{\n{diff}}}\n")
+            });
+            (
+                format!("#[allow(clippy::match_same_arms)]\nmatch self {{\n{walk}}}\n"),
+                format!("#[allow(clippy::match_same_arms)]\nmatch self {{\n{walk_mut}}}\n"),
+                diff,
+            )
+        }
+        Kind::Enum(_) => (String::new(), String::new(), None),
+        Kind::Const => unreachable!("constants have no WriteXdr"),
+    };
+    let diff = match diff {
+        Some(diff) => format!(
+            "#[cfg(feature = \"alloc\")]
+impl Diff for {name} {{
+    fn diff_into(&self, other: &Self, path: &mut Vec<PathSegment>, differences: &mut Vec<Difference>) {{
+{diff}    }}
+}}"
+        ),
+        None => format!("#[cfg(feature = \"alloc\")]\nimpl Diff for {name} {{}}"),
+    };
+    format!(
+        "#[cfg(feature = \"alloc\")]
+impl Visit for {name} {{
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {{
+        visitor.visit(path, self);
+{walk}    }}
+}}
+
+#[cfg(feature = \"alloc\")]
+impl VisitMut for {name} {{
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {{
+        visitor.visit_mut(path, self);
+{walk_mut}    }}
+}}
+
+{diff}"
+    )
+}
+
+fn bytes(name: &str) -> String {
+    format!(
+        "
+#[cfg(feature = \"bytes\")]
+impl TryFrom<bytes::Bytes> for {name} {{
+    type Error = Error;
+    fn try_from(x: bytes::Bytes) -> Result<Self> {{
+        Ok({name}(x.try_into()?))
+    }}
+}}
+
+#[cfg(feature = \"bytes\")]
+impl From<{name}> for bytes::Bytes {{
+    fn from(x: {name}) -> Self {{
+        x.0.into()
+    }}
+}}
+"
+    )
+}
@@ -0,0 +1,237 @@
+/// Borrowed forms of the types that contain variable length opaque or string
+/// data, decoded with [`ReadXdrBorrowed`].
+///
+/// Each type in this module borrows its opaque and string data from the slice
+/// it was decoded from, and converts into the owned type of the same name with
+/// [`ReadXdrBorrowed::into_owned`].
+#[cfg(feature = "alloc")]
+pub mod borrowed {
+    #[allow(clippy::wildcard_imports)]
+    use super::*;
+
+    /// A variable length opaque borrowed from the slice it was decoded from.
+    #[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct BytesM<'a, const MAX: u32 = { u32::MAX }>(&'a [u8]);
+
+    impl<const MAX: u32> core::fmt::Display for BytesM<'_, MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for b in self.0 {
+                write!(f, "{b:02x}")?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> core::fmt::Debug for BytesM<'_, MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "BytesM(")?;
+            for b in self.0 {
+                write!(f, "{b:02x}")?;
+            }
+            write!(f, ")")?;
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> Deref for BytesM<'_, MAX> {
+        type Target = [u8];
+
+        fn deref(&self) -> &Self::Target {
+            self.0
+        }
+    }
+
+    impl<const MAX: u32> AsRef<[u8]> for BytesM<'_, MAX> {
+        fn as_ref(&self) -> &[u8] {
+            self.0
+        }
+    }
+
+    impl<'a, const MAX: u32> BytesM<'a, MAX> {
+        #[must_use]
+        pub fn as_slice(&self) -> &'a [u8] {
+            self.0
+        }
+    }
+
+    impl<'a, const MAX: u32> TryFrom<&'a [u8]> for BytesM<'a, MAX> {
+        type Error = Error;
+
+        fn try_from(v: &'a [u8]) -> Result<Self> {
+            let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            if len <= MAX {
+                Ok(BytesM(v))
+            } else {
+                Err(Error::LengthExceedsMax)
+            }
+        }
+    }
+
+    impl<'a, const MAX: u32> ReadXdrBorrowed<'a> for BytesM<'a, MAX> {
+        type Owned = super::BytesM<MAX>;
+
+        fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+            r.with_limited_depth(|r| Ok(BytesM(r.read_borrowed_var(MAX)?)))
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            super::BytesM(self.0.to_vec())
+        }
+    }
+
+    /// A string borrowed from the slice it was decoded from.
+    #[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct StringM<'a, const MAX: u32 = { u32::MAX }>(&'a [u8]);
+
+    impl<const MAX: u32> core::fmt::Display for StringM<'_, MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for b in escape_bytes::Escape::new(self.0) {
+                write!(f, "{}", b as char)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> core::fmt::Debug for StringM<'_, MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "StringM(")?;
+            for b in escape_bytes::Escape::new(self.0) {
+                write!(f, "{}", b as char)?;
+            }
+            write!(f, ")")?;
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> Deref for StringM<'_, MAX> {
+        type Target = [u8];
+
+        fn deref(&self) -> &Self::Target {
+            self.0
+        }
+    }
+
+    impl<const MAX: u32> AsRef<[u8]> for StringM<'_, MAX> {
+        fn as_ref(&self) -> &[u8] {
+            self.0
+        }
+    }
+
+    impl<'a, const MAX: u32> StringM<'a, MAX> {
+        #[must_use]
+        pub fn as_slice(&self) -> &'a [u8] {
+            self.0
+        }
+
+        pub fn to_utf8_str(&self) -> Result<&'a str> {
+            Ok(core::str::from_utf8(self.0)?)
+        }
+    }
+
+    impl<'a, const MAX: u32> TryFrom<&'a [u8]> for StringM<'a, MAX> {
+        type Error = Error;
+
+        fn try_from(v: &'a [u8]) -> Result<Self> {
+            let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            if len <= MAX {
+                Ok(StringM(v))
+            } else {
+                Err(Error::LengthExceedsMax)
+            }
+        }
+    }
+
+    impl<'a, const MAX: u32> ReadXdrBorrowed<'a> for StringM<'a, MAX> {
+        type Owned = super::StringM<MAX>;
+
+        fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+            r.with_limited_depth(|r| Ok(StringM(r.read_borrowed_var(MAX)?)))
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            super::StringM(self.0.to_vec())
+        }
+    }
+
+    /// A value of type `T` held as XDR borrowed from the slice it was decoded
+    /// from, without decoding it.
+    ///
+    /// The borrowed form of [`super::Lazy`], that holds the XDR of the value as
+    /// a subslice of the input rather than a copy of it. Reading a `Lazy` skips
+    /// over the value with [`ReadXdr::skip_xdr`], validating it without
+    /// constructing it or allocating.
+    pub struct Lazy<'a, T> {
+        xdr: &'a [u8],
+        _t: core::marker::PhantomData<fn() -> T>,
+    }
+
+    impl<'a, T> Lazy<'a, T> {
+        /// Decodes the value from the XDR held.
+        ///
+        /// ### Errors
+        ///
+        /// If the value cannot be read within the limits.
+        pub fn decode(&self, limits: Limits) -> Result<T>
+        where
+            T: ReadXdr,
+        {
+            T::from_xdr(self.xdr, limits)
+        }
+
+        /// Returns the XDR of the value, borrowed from the slice it was decoded
+        /// from.
+        #[must_use]
+        pub fn as_xdr(&self) -> &'a [u8] {
+            self.xdr
+        }
+    }
+
+    impl<T> Clone for Lazy<'_, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T> Copy for Lazy<'_, T> {}
+
+    impl<T> PartialEq for Lazy<'_, T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.xdr == other.xdr
+        }
+    }
+
+    impl<T> Eq for Lazy<'_, T> {}
+
+    impl<T> core::hash::Hash for Lazy<'_, T> {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            self.xdr.hash(state);
+        }
+    }
+
+    impl<T> core::fmt::Debug for Lazy<'_, T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "Lazy(")?;
+            for b in self.xdr {
+                write!(f, "{b:02x}")?;
+            }
+            write!(f, ")")?;
+            Ok(())
+        }
+    }
+
+    impl<'a, T: ReadXdr> ReadXdrBorrowed<'a> for Lazy<'a, T> {
+        type Owned = super::Lazy<T>;
+
+        fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+            let start = r.inner;
+            T::skip_xdr(r)?;
+            Ok(Lazy {
+                xdr: &start[..start.len() - r.inner.len()],
+                _t: core::marker::PhantomData,
+            })
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            super::Lazy::from_skipped_xdr(self.xdr.to_vec())
+        }
+    }
@@ -0,0 +1,4460 @@
+use core::{array::TryFromSliceError, fmt, fmt::Debug, marker::Sized, ops::Deref, slice};
+
+#[cfg(feature = "alloc")]
+use core::any::Any;
+
+#[cfg(feature = "std")]
+use core::marker::PhantomData;
+
+// When feature alloc is turned off use static lifetime Box and Vec types.
+#[cfg(not(feature = "alloc"))]
+mod noalloc {
+    pub mod boxed {
+        pub type Box<T> = &'static T;
+    }
+    pub mod vec {
+        pub type Vec<T> = &'static [T];
+    }
+}
+#[cfg(not(feature = "alloc"))]
+use noalloc::{boxed::Box, vec::Vec};
+
+// When feature std is turned off, but feature alloc is turned on import the
+// alloc crate and use its Box and Vec types.
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+extern crate alloc;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{FromUtf8Error, String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::string::FromUtf8Error;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+
+#[cfg(feature = "std")]
+use std::{
+    error, io,
+    io::{BufRead, BufReader, Read, Write},
+};
+
+#[cfg(not(feature = "std"))]
+use io::{Read, Write};
+
+#[cfg(feature = "std")]
+use super::frame::{read_frame, Frame};
+#[cfg(feature = "alloc")]
+use super::{
+    frame::write_framed,
+    lazy::Lazy,
+    query::{snake_case, Query},
+};
+
+/// `io` contains minimal forms of the `std::io` [`Read`](io::Read) and
+/// [`Write`](io::Write) traits, used for encoding and decoding XDR when std is
+/// not available.
+///
+/// Implementations are provided for reading from `&[u8]` and writing to
+/// `&mut [u8]`, and for writing to `Vec<u8>` when the alloc feature is enabled.
+#[cfg(not(feature = "std"))]
+pub mod io {
+    use core::fmt;
+
+    /// `ErrorKind` is the category of an [`Error`].
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    pub enum ErrorKind {
+        /// A read could not be completed because the end of the reader was
+        /// reached.
+        UnexpectedEof,
+        /// A write could not be completed because the writer could not accept
+        /// any more bytes.
+        WriteZero,
+    }
+
+    /// `Error` is an error that occurred reading or writing.
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    pub struct Error {
+        kind: ErrorKind,
+    }
+
+    impl Error {
+        #[must_use]
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Error { kind }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.kind {
+                ErrorKind::UnexpectedEof => write!(f, "failed to fill whole buffer"),
+                ErrorKind::WriteZero => write!(f, "failed to write whole buffer"),
+            }
+        }
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// `Read` is a source of bytes.
+    pub trait Read {
+        /// Read bytes into the buffer, returning the number of bytes read. A
+        /// return value of zero indicates the end of the reader.
+        ///
+        /// ### Errors
+        ///
+        /// If the reader fails.
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        /// Read exactly enough bytes to fill the buffer.
+        ///
+        /// ### Errors
+        ///
+        /// If the end of the reader is reached before the buffer is filled.
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf)? {
+                    0 => return Err(ErrorKind::UnexpectedEof.into()),
+                    n => buf = &mut buf[n..],
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// `Write` is a sink of bytes.
+    pub trait Write {
+        /// Write bytes from the buffer, returning the number of bytes written.
+        ///
+        /// ### Errors
+        ///
+        /// If the writer fails.
+        fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+        /// Flush any buffered bytes.
+        ///
+        /// ### Errors
+        ///
+        /// If the writer fails.
+        fn flush(&mut self) -> Result<()>;
+
+        /// Write all the bytes from the buffer.
+        ///
+        /// ### Errors
+        ///
+        /// If the writer stops accepting bytes before all bytes are written.
+        fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.write(buf)? {
+                    0 => return Err(ErrorKind::WriteZero.into()),
+                    n => buf = &buf[n..],
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            (**self).read(buf)
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            (**self).write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            (**self).flush()
+        }
+    }
+
+    impl Read for &[u8] {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let n = core::cmp::min(buf.len(), self.len());
+            let (a, b) = self.split_at(n);
+            buf[..n].copy_from_slice(a);
+            *self = b;
+            Ok(n)
+        }
+    }
+
+    impl Write for &mut [u8] {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            let n = core::cmp::min(buf.len(), self.len());
+            let (a, b) = core::mem::take(self).split_at_mut(n);
+            a.copy_from_slice(&buf[..n]);
+            *self = b;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Write for super::Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+}
+
+/// Error contains all errors returned by functions in this crate. It can be
+/// compared via `PartialEq`, however any contained IO errors will only be
+/// compared on their `ErrorKind`.
+#[derive(Debug)]
+pub enum Error {
+    Invalid,
+    Unsupported,
+    LengthExceedsMax,
+    LengthMismatch,
+    NonZeroPadding,
+    Utf8Error(core::str::Utf8Error),
+    #[cfg(feature = "alloc")]
+    InvalidHex,
+    Io(io::Error),
+    DepthLimitExceeded,
+    #[cfg(feature = "serde_json")]
+    Json(serde_json::Error),
+    LengthLimitExceeded,
+    AllocLimitExceeded,
+    ElementLimitExceeded,
+    /// An error that occurred decoding, with the context describing where in
+    /// the XDR it occurred. Only returned by readers that have error context
+    /// enabled with [`Limited::with_error_context`].
+    #[cfg(feature = "alloc")]
+    Context(Box<ErrorContext>),
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Utf8Error(l), Self::Utf8Error(r)) => l == r,
+            // IO errors cannot be compared, but in the absence of any more
+            // meaningful way to compare the errors we compare the kind of error
+            // and ignore the embedded source error or OS error. The main use
+            // case for comparing errors outputted by the XDR library is for
+            // error case testing, and a lack of the ability to compare has a
+            // detrimental affect on failure testing, so this is a tradeoff.
+            (Self::Io(l), Self::Io(r)) => l.kind() == r.kind(),
+            #[cfg(feature = "alloc")]
+            (Self::Context(l), Self::Context(r)) => l == r,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {
+    #[must_use]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            #[cfg(feature = "serde_json")]
+            Self::Json(e) => Some(e),
+            Self::Context(c) => Some(&c.error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Invalid => write!(f, "xdr value invalid"),
+            Error::Unsupported => write!(f, "xdr value unsupported"),
+            Error::LengthExceedsMax => write!(f, "xdr value max length exceeded"),
+            Error::LengthMismatch => write!(f, "xdr value length does not match"),
+            Error::NonZeroPadding => write!(f, "xdr padding contains non-zero bytes"),
+            Error::Utf8Error(e) => write!(f, "{e}"),
+            #[cfg(feature = "alloc")]
+            Error::InvalidHex => write!(f, "hex invalid"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::DepthLimitExceeded => write!(f, "depth limit exceeded"),
+            #[cfg(feature = "serde_json")]
+            Error::Json(e) => write!(f, "{e}"),
+            Error::LengthLimitExceeded => write!(f, "length limit exceeded"),
+            Error::AllocLimitExceeded => write!(f, "allocation limit exceeded"),
+            Error::ElementLimitExceeded => write!(f, "element limit exceeded"),
+            #[cfg(feature = "alloc")]
+            Error::Context(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+impl Error {
+    /// Returns the error without any context describing where it occurred.
+    #[must_use]
+    pub fn without_context(&self) -> &Error {
+        match self {
+            #[cfg(feature = "alloc")]
+            Error::Context(c) => &c.error,
+            e => e,
+        }
+    }
+
+    /// Adds the segment as the root of the path of the error, if the error has
+    /// context.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_root(self, segment: PathSegment) -> Self {
+        match self {
+            Error::Context(mut c) => {
+                c.path.insert(0, segment);
+                Error::Context(c)
+            }
+            e => e,
+        }
+    }
+}
+
+/// `ErrorContext` describes where in the XDR an error occurred when decoding.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq)]
+pub struct ErrorContext {
+    error: Error,
+    value_offset: usize,
+    path: Vec<PathSegment>,
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorContext {
+    /// Returns the error that occurred.
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the byte offset, from the start of the reader, at which the
+    /// innermost value in the path begins.
+    ///
+    /// This is the offset of the value that failed to decode, and not the
+    /// offset of the byte within that value at which decoding failed. For
+    /// example, an invalid enum value is reported at the offset of the enum,
+    /// and non-zero padding at the offset of the variable length opaque value
+    /// it pads.
+    #[must_use]
+    pub fn value_offset(&self) -> usize {
+        self.value_offset
+    }
+
+    /// Returns the path, from the outermost value, of the value in which the
+    /// error occurred.
+    #[must_use]
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at ", self.error)?;
+        for (i, segment) in self.path.iter().enumerate() {
+            match segment {
+                PathSegment::Type(name) => write!(f, "{name}")?,
+                PathSegment::Field(name) | PathSegment::Arm(name) => {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{name}")?;
+                }
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        write!(f, " (value at byte offset {})", self.value_offset)
+    }
+}
+
+/// `PathSegment` is a single step in the path to a value within a decoded
+/// XDR value.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The named type being decoded.
+    Type(&'static str),
+    /// A field of a struct.
+    Field(&'static str),
+    /// The arm of a union.
+    Arm(&'static str),
+    /// An element of an array.
+    Index(usize),
+}
+
+impl From<TryFromSliceError> for Error {
+    fn from(_: TryFromSliceError) -> Error {
+        Error::LengthMismatch
+    }
+}
+
+impl From<core::str::Utf8Error> for Error {
+    #[must_use]
+    fn from(e: core::str::Utf8Error) -> Self {
+        Error::Utf8Error(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FromUtf8Error> for Error {
+    #[must_use]
+    fn from(e: FromUtf8Error) -> Self {
+        Error::Utf8Error(e.utf8_error())
+    }
+}
+
+impl From<io::Error> for Error {
+    #[must_use]
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Error> for Error {
+    #[must_use]
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<Error> for () {
+    fn from(_: Error) {}
+}
+
+#[allow(dead_code)]
+type Result<T> = core::result::Result<T, Error>;
+
+/// Name defines types that assign a static name to their value, such as the
+/// name given to an identifier in an XDR enum, or the name given to the case in
+/// a union.
+pub trait Name {
+    fn name(&self) -> &'static str;
+}
+
+/// Discriminant defines types that may contain a one-of value determined
+/// according to the discriminant, and exposes the value of the discriminant for
+/// that type, such as in an XDR union.
+pub trait Discriminant<D> {
+    fn discriminant(&self) -> D;
+}
+
+/// Iter defines types that have variants that can be iterated.
+pub trait Variants<V> {
+    fn variants() -> slice::Iter<'static, V>
+    where
+        V: Sized;
+}
+
+// Enum defines a type that is represented as an XDR enumeration when encoded.
+pub trait Enum: Name + Variants<Self> + Sized {}
+
+// Union defines a type that is represented as an XDR union when encoded.
+pub trait Union<D>: Name + Discriminant<D> + Variants<D>
+where
+    D: Sized,
+{
+}
+
+/// `Limits` contains the limits that a limited reader or writer will be
+/// constrained to.
+///
+/// Limits are constructed with [`Limits::none`], or one of the functions that
+/// sets a single limit, and further limits set with the `with_` functions, such
+/// as `Limits::depth(500).with_len(1 << 20)`.
+///
+/// New limits may be added in future versions, and so `Limits` cannot be
+/// constructed with a struct expression outside of this crate.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub struct Limits {
+    /// Defines the maximum depth for recursive calls in `Read/WriteXdr` to
+    /// prevent stack overflow.
+    ///
+    /// The depth limit is akin to limiting stack depth. Its purpose is to
+    /// prevent the program from hitting the maximum stack size allowed by Rust,
+    /// which would result in an unrecoverable `SIGABRT`.  For more information
+    /// about Rust's stack size limit, refer to the [Rust
+    /// documentation](https://doc.rust-lang.org/std/thread/#stack-size).
+    pub depth: u32,
+
+    /// Defines the maximum number of bytes that will be read or written.
+    pub len: usize,
+
+    /// Defines the maximum number of bytes that will be allocated when
+    /// reading, for the storage of variable length opaques, strings and
+    /// arrays, and of boxed values.
+    ///
+    /// The allocation is counted before it is made, so that a length prefix
+    /// that is larger than the data that follows it cannot cause a large
+    /// allocation.
+    pub alloc: usize,
+
+    /// Defines the maximum number of elements in any single variable length
+    /// array that will be read.
+    pub elements: usize,
+}
+
+impl Limits {
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            depth: u32::MAX,
+            len: usize::MAX,
+            alloc: usize::MAX,
+            elements: usize::MAX,
+        }
+    }
+
+    #[must_use]
+    pub fn depth(depth: u32) -> Self {
+        Limits {
+            depth,
+            ..Limits::none()
+        }
+    }
+
+    #[must_use]
+    pub fn len(len: usize) -> Self {
+        Limits {
+            len,
+            ..Limits::none()
+        }
+    }
+
+    #[must_use]
+    pub fn alloc(alloc: usize) -> Self {
+        Limits {
+            alloc,
+            ..Limits::none()
+        }
+    }
+
+    #[must_use]
+    pub fn elements(elements: usize) -> Self {
+        Limits {
+            elements,
+            ..Limits::none()
+        }
+    }
+
+    /// Returns the limits with the depth limit set to `depth`.
+    #[must_use]
+    pub fn with_depth(self, depth: u32) -> Self {
+        Limits { depth, ..self }
+    }
+
+    /// Returns the limits with the length limit set to `len`.
+    #[must_use]
+    pub fn with_len(self, len: usize) -> Self {
+        Limits { len, ..self }
+    }
+
+    /// Returns the limits with the allocation limit set to `alloc`.
+    #[must_use]
+    pub fn with_alloc(self, alloc: usize) -> Self {
+        Limits { alloc, ..self }
+    }
+
+    /// Returns the limits with the element limit set to `elements`.
+    #[must_use]
+    pub fn with_elements(self, elements: usize) -> Self {
+        Limits { elements, ..self }
+    }
+}
+
+/// `Limited` wraps an object and provides functions for enforcing limits.
+///
+/// Intended for use with readers and writers and limiting their reads and
+/// writes.
+pub struct Limited<L> {
+    pub inner: L,
+    pub(crate) limits: Limits,
+    pub(crate) offset: usize,
+    pub(crate) error_context: bool,
+}
+
+impl<L> Limited<L> {
+    /// Constructs a new `Limited`.
+    ///
+    /// - `inner`: The value being limited.
+    /// - `limits`: The limits to enforce.
+    pub fn new(inner: L, limits: Limits) -> Self {
+        Limited {
+            inner,
+            limits,
+            offset: 0,
+            error_context: false,
+        }
+    }
+
+    /// Enables error context, so that errors that occur decoding are returned
+    /// as an [`Error::Context`] describing the byte offset and the path of
+    /// types, fields, union arms and array indexes at which they occurred.
+    ///
+    /// Error context is off by default, and costs nothing unless an error
+    /// occurs.
+    #[must_use]
+    pub fn with_error_context(mut self) -> Self {
+        self.error_context = true;
+        self
+    }
+
+    /// Returns the number of bytes consumed from the inner value.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Consume the given length from the internal remaining length limit.
+    ///
+    /// ### Errors
+    ///
+    /// If the length would consume more length than the remaining length limit
+    /// allows.
+    pub(crate) fn consume_len(&mut self, len: usize) -> Result<()> {
+        if let Some(remaining) = self.limits.len.checked_sub(len) {
+            self.limits.len = remaining;
+            self.offset += len;
+            Ok(())
+        } else {
+            Err(Error::LengthLimitExceeded)
+        }
+    }
+
+    /// Consume the given number of bytes from the internal remaining
+    /// allocation limit.
+    ///
+    /// ### Errors
+    ///
+    /// If the allocation would exceed the remaining allocation limit.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn consume_alloc(&mut self, len: usize) -> Result<()> {
+        if let Some(remaining) = self.limits.alloc.checked_sub(len) {
+            self.limits.alloc = remaining;
+            Ok(())
+        } else {
+            Err(Error::AllocLimitExceeded)
+        }
+    }
+
+    /// Consume the allocation for storing the given number of elements of
+    /// type `T`, after checking that the number of elements is within the
+    /// element limit.
+    ///
+    /// ### Errors
+    ///
+    /// If the number of elements exceeds the element limit, or the allocation
+    /// would exceed the remaining allocation limit.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn consume_elements<T>(&mut self, count: usize) -> Result<()> {
+        if count > self.limits.elements {
+            return Err(Error::ElementLimitExceeded);
+        }
+        let len = count
+            .checked_mul(core::mem::size_of::<T>())
+            .ok_or(Error::AllocLimitExceeded)?;
+        self.consume_alloc(len)
+    }
+
+    /// Consumes a single depth for the duration of the given function.
+    ///
+    /// ### Errors
+    ///
+    /// If the depth limit is already exhausted.
+    pub(crate) fn with_limited_depth<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        if let Some(depth) = self.limits.depth.checked_sub(1) {
+            self.limits.depth = depth;
+            let res = f(self);
+            self.limits.depth = self.limits.depth.saturating_add(1);
+            res
+        } else {
+            Err(Error::DepthLimitExceeded)
+        }
+    }
+
+    /// Calls the given function to read the value at the path segment, and if
+    /// error context is enabled adds the segment to the path of any error.
+    ///
+    /// The value offset of the error is the offset at which the innermost value
+    /// in the path begins.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_path<T, F>(&mut self, segment: PathSegment, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        let value_offset = self.offset;
+        f(self).map_err(|e| {
+            if !self.error_context {
+                return e;
+            }
+            match e {
+                Error::Context(_) => e.with_root(segment),
+                e => Error::Context(Box::new(ErrorContext {
+                    error: e,
+                    value_offset,
+                    path: vec![segment],
+                })),
+            }
+        })
+    }
+}
+
+impl<R: Read> Read for Limited<R> {
+    /// Forwards the read operation to the wrapped object.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> BufRead for Limited<R> {
+    /// Forwards the read operation to the wrapped object.
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    /// Forwards the read operation to the wrapped object.
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+impl<W: Write> Write for Limited<W> {
+    /// Forwards the write operation to the wrapped object.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    /// Forwards the flush operation to the wrapped object.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "std")]
+pub struct ReadXdrIter<R: Read, S: ReadXdr> {
+    reader: Limited<BufReader<R>>,
+    _s: PhantomData<S>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read, S: ReadXdr> ReadXdrIter<R, S> {
+    pub(crate) fn new(r: R, limits: Limits, error_context: bool) -> Self {
+        Self {
+            reader: Limited {
+                inner: BufReader::new(r),
+                limits,
+                offset: 0,
+                error_context,
+            },
+            _s: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read, S: ReadXdr> Iterator for ReadXdrIter<R, S> {
+    type Item = Result<S>;
+
+    // Next reads the internal reader and XDR decodes it into the Self type. If
+    // the EOF is reached without reading any new bytes `None` is returned. If
+    // EOF is reached after reading some bytes a truncated entry is assumed an
+    // an `Error::Io` containing an `UnexpectedEof`. If any other IO error
+    // occurs it is returned. Iteration of this iterator stops naturally when
+    // `None` is returned, but not when a `Some(Err(...))` is returned. The
+    // caller is responsible for checking each Result.
+    fn next(&mut self) -> Option<Self::Item> {
+        // Try to fill the buffer to see if the EOF has been reached or not.
+        // This happens to effectively peek to see if the stream has finished
+        // and there are no more items.  It is necessary to do this because the
+        // xdr types in this crate heavily use the `std::io::Read::read_exact`
+        // method that doesn't distinguish between an EOF at the beginning of a
+        // read and an EOF after a partial fill of a read_exact.
+        match self.reader.fill_buf() {
+            // If the reader has no more data and is unable to fill any new data
+            // into its internal buf, then the EOF has been reached.
+            Ok([]) => return None,
+            // If an error occurs filling the buffer, treat that as an error and stop.
+            Err(e) => return Some(Err(Error::Io(e))),
+            // If there is data in the buf available for reading, continue.
+            Ok([..]) => (),
+        };
+        // Read the buf into the type.
+        let r = self.reader.with_limited_depth(|dlr| S::read_xdr(dlr));
+        match r {
+            Ok(s) => Some(Ok(s)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// `SkipXdrIter` skips over a stream of values of a type, without
+/// constructing them, yielding the range of bytes in the stream that each
+/// value occupies.
+///
+/// Created by [`Type::skip_xdr_iter`], [`Type::skip_xdr_framed_iter`] and
+/// [`Type::skip_xdr_base64_iter`].
+#[cfg(feature = "std")]
+pub struct SkipXdrIter<R: Read> {
+    reader: Limited<BufReader<R>>,
+    v: TypeVariant,
+    framed: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> SkipXdrIter<R> {
+    fn new(r: R, limits: Limits, error_context: bool, v: TypeVariant, framed: bool) -> Self {
+        Self {
+            reader: Limited {
+                inner: BufReader::new(r),
+                limits,
+                offset: 0,
+                error_context,
+            },
+            v,
+            framed,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for SkipXdrIter<R> {
+    type Item = Result<core::ops::Range<usize>>;
+
+    // Next skips over the next value in the internal reader. The end of the
+    // stream is detected, and errors are returned, the same as by
+    // `ReadXdrIter`.
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.fill_buf() {
+            Ok([]) => return None,
+            Err(e) => return Some(Err(Error::Io(e))),
+            Ok([..]) => (),
+        }
+        let start = self.reader.offset;
+        let v = self.v;
+        let r = if self.framed {
+            self.reader
+                .with_limited_depth(|dlr| read_frame(dlr, |fr| Type::skip_xdr(v, fr)))
+        } else {
+            self.reader.with_limited_depth(|dlr| Type::skip_xdr(v, dlr))
+        };
+        Some(r.map(|()| start..self.reader.offset))
+    }
+}
+
+pub trait ReadXdr
+where
+    Self: Sized,
+{
+    /// Read the XDR and construct the type.
+    ///
+    /// Read bytes from the given read implementation, decoding the bytes as
+    /// XDR, and construct the type implementing this interface from those
+    /// bytes.
+    ///
+    /// Just enough bytes are read from the read implementation to construct the
+    /// type. Any residual bytes remain in the read implementation.
+    ///
+    /// All implementations should continue if the read implementation returns
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted).
+    ///
+    /// Use [`ReadXdR: Read_xdr_to_end`] when the intent is for all bytes in the
+    /// read implementation to be consumed by the read.
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self>;
+
+    /// Read the XDR and discard it, without constructing the type.
+    ///
+    /// The bytes are read and validated, and the limits are consumed, the same
+    /// as by [`ReadXdr::read_xdr`], except that nothing is allocated and so the
+    /// allocation limit is not consumed.
+    ///
+    /// The default implementation reads the type and drops it, and is
+    /// overridden by types that allocate when read.
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        Self::read_xdr(r)?;
+        Ok(())
+    }
+
+    /// Construct the type from the XDR bytes base64 encoded.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    #[cfg(feature = "base64")]
+    fn read_xdr_base64<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        let t = Self::read_xdr(&mut dec)?;
+        Ok(t)
+    }
+
+    /// Read the XDR and construct the type, and consider it an error if the
+    /// read does not completely consume the read implementation.
+    ///
+    /// Read bytes from the given read implementation, decoding the bytes as
+    /// XDR, and construct the type implementing this interface from those
+    /// bytes.
+    ///
+    /// Just enough bytes are read from the read implementation to construct the
+    /// type, and then confirm that no further bytes remain. To confirm no
+    /// further bytes remain additional bytes are attempted to be read from the
+    /// read implementation. If it is possible to read any residual bytes from
+    /// the read implementation an error is returned. The read implementation
+    /// may not be exhaustively read if there are residual bytes, and it is
+    /// considered undefined how many residual bytes or how much of the residual
+    /// buffer are consumed in this case.
+    ///
+    /// All implementations should continue if the read implementation returns
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted).
+    #[cfg(feature = "alloc")]
+    fn read_xdr_to_end<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let s = Self::read_xdr(r)?;
+        // Check that any further reads, such as this read of one byte, read no
+        // data, indicating EOF. If a byte is read the data is invalid.
+        if r.read(&mut [0u8; 1])? == 0 {
+            Ok(s)
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+
+    /// Construct the type from the XDR bytes base64 encoded.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    #[cfg(feature = "base64")]
+    fn read_xdr_base64_to_end<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD),
+            r.limits.clone(),
+        );
+        dec.error_context = r.error_context;
+        let t = Self::read_xdr_to_end(&mut dec)?;
+        Ok(t)
+    }
+
+    /// Read the XDR and construct the type.
+    ///
+    /// Read bytes from the given read implementation, decoding the bytes as
+    /// XDR, and construct the type implementing this interface from those
+    /// bytes.
+    ///
+    /// Just enough bytes are read from the read implementation to construct the
+    /// type. Any residual bytes remain in the read implementation.
+    ///
+    /// All implementations should continue if the read implementation returns
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted).
+    ///
+    /// Use [`ReadXdR: Read_xdr_into_to_end`] when the intent is for all bytes
+    /// in the read implementation to be consumed by the read.
+    #[cfg(feature = "alloc")]
+    fn read_xdr_into<R: Read>(&mut self, r: &mut Limited<R>) -> Result<()> {
+        *self = Self::read_xdr(r)?;
+        Ok(())
+    }
+
+    /// Read the XDR into the existing value, and consider it an error if the
+    /// read does not completely consume the read implementation.
+    ///
+    /// Read bytes from the given read implementation, decoding the bytes as
+    /// XDR, and construct the type implementing this interface from those
+    /// bytes.
+    ///
+    /// Just enough bytes are read from the read implementation to construct the
+    /// type, and then confirm that no further bytes remain. To confirm no
+    /// further bytes remain additional bytes are attempted to be read from the
+    /// read implementation. If it is possible to read any residual bytes from
+    /// the read implementation an error is returned. The read implementation
+    /// may not be exhaustively read if there are residual bytes, and it is
+    /// considered undefined how many residual bytes or how much of the residual
+    /// buffer are consumed in this case.
+    ///
+    /// All implementations should continue if the read implementation returns
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted).
+    #[cfg(feature = "alloc")]
+    fn read_xdr_into_to_end<R: Read>(&mut self, r: &mut Limited<R>) -> Result<()> {
+        Self::read_xdr_into(self, r)?;
+        // Check that any further reads, such as this read of one byte, read no
+        // data, indicating EOF. If a byte is read the data is invalid.
+        if r.read(&mut [0u8; 1])? == 0 {
+            Ok(())
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+
+    /// Create an iterator that reads the read implementation as a stream of
+    /// values that are read into the implementing type.
+    ///
+    /// Read bytes from the given read implementation, decoding the bytes as
+    /// XDR, and construct the type implementing this interface from those
+    /// bytes.
+    ///
+    /// Just enough bytes are read from the read implementation to construct the
+    /// type, and then confirm that no further bytes remain. To confirm no
+    /// further bytes remain additional bytes are attempted to be read from the
+    /// read implementation. If it is possible to read any residual bytes from
+    /// the read implementation an error is returned. The read implementation
+    /// may not be exhaustively read if there are residual bytes, and it is
+    /// considered undefined how many residual bytes or how much of the residual
+    /// buffer are consumed in this case.
+    ///
+    /// All implementations should continue if the read implementation returns
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted).
+    #[cfg(feature = "std")]
+    fn read_xdr_iter<R: Read>(r: &mut Limited<R>) -> ReadXdrIter<&mut R, Self> {
+        ReadXdrIter::new(&mut r.inner, r.limits.clone(), r.error_context)
+    }
+
+    /// Create an iterator that reads the read implementation as a stream of
+    /// values that are read into the implementing type.
+    #[cfg(feature = "base64")]
+    fn read_xdr_base64_iter<R: Read>(
+        r: &mut Limited<R>,
+    ) -> ReadXdrIter<base64::read::DecoderReader<R>, Self> {
+        let dec = base64::read::DecoderReader::new(&mut r.inner, base64::STANDARD);
+        ReadXdrIter::new(dec, r.limits.clone(), r.error_context)
+    }
+
+    /// Construct the type from the XDR bytes.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    #[cfg(feature = "alloc")]
+    fn from_xdr(bytes: impl AsRef<[u8]>, limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(bytes.as_ref(), limits);
+        let t = Self::read_xdr_to_end(&mut r)?;
+        Ok(t)
+    }
+
+    /// Construct the type from the XDR bytes base64 encoded.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    #[cfg(feature = "base64")]
+    fn from_xdr_base64(b64: impl AsRef<[u8]>, limits: Limits) -> Result<Self> {
+        let mut b64_reader = b64.as_ref();
+        let mut dec = Limited::new(
+            base64::read::DecoderReader::new(&mut b64_reader, base64::STANDARD),
+            limits,
+        );
+        let t = Self::read_xdr_to_end(&mut dec)?;
+        Ok(t)
+    }
+
+    /// Read the XDR from the buffer and construct the type, advancing the
+    /// buffer past the bytes read.
+    ///
+    /// Just enough bytes are read from the buffer to construct the type. Any
+    /// residual bytes remain in the buffer.
+    ///
+    /// Opaque and string data is copied into the type. The types of the
+    /// [`shared`] module hold the data as `Bytes` taken from the buffer.
+    #[cfg(feature = "bytes")]
+    fn read_xdr_buf<B: bytes::Buf>(buf: &mut B, limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(bytes::Buf::reader(buf), limits);
+        let t = Self::read_xdr(&mut r)?;
+        Ok(t)
+    }
+
+    /// Construct the type from the XDR in the buffer.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    #[cfg(feature = "bytes")]
+    fn from_xdr_buf(buf: impl bytes::Buf, limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(buf.reader(), limits);
+        let t = Self::read_xdr_to_end(&mut r)?;
+        Ok(t)
+    }
+}
+
+pub trait WriteXdr {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()>;
+
+    #[cfg(feature = "alloc")]
+    fn to_xdr(&self, limits: Limits) -> Result<Vec<u8>> {
+        let mut w = Limited::new(Vec::new(), limits);
+        self.write_xdr(&mut w)?;
+        Ok(w.inner)
+    }
+
+    /// Write the XDR into the given buffer, returning the number of bytes
+    /// written.
+    ///
+    /// An error is returned if the buffer is too small to contain the XDR.
+    fn write_xdr_to_slice(&self, buf: &mut [u8], limits: Limits) -> Result<usize> {
+        let len = buf.len();
+        let mut w = Limited::new(buf, limits);
+        self.write_xdr(&mut w)?;
+        Ok(len - w.inner.len())
+    }
+
+    /// Returns the length of the XDR of the value, as it would be written by
+    /// [`WriteXdr::to_xdr`], including any padding.
+    ///
+    /// The length is computed from the structure of the value, without
+    /// writing the XDR.
+    ///
+    /// An error is returned if the value would exceed the limits when written.
+    fn xdr_len(&self, limits: Limits) -> Result<usize> {
+        let len = self.xdr_len_at_depth(limits.depth)?;
+        if len > limits.len {
+            return Err(Error::LengthLimitExceeded);
+        }
+        Ok(len)
+    }
+
+    /// Returns the length of the XDR of the value, when written with `depth`
+    /// remaining of the depth limit.
+    ///
+    /// The types of this crate compute the length from their structure, and
+    /// fixed length types return a constant. Types that do not implement this
+    /// method count the bytes written by [`WriteXdr::write_xdr`].
+    ///
+    /// An error is returned if the value would exceed the depth limit when
+    /// written.
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        let mut w = Limited::new(LenCounter { len: 0 }, Limits::depth(depth));
+        self.write_xdr(&mut w)?;
+        Ok(w.inner.len)
+    }
+
+    #[cfg(feature = "base64")]
+    fn to_xdr_base64(&self, limits: Limits) -> Result<String> {
+        let mut enc = Limited::new(
+            base64::write::EncoderStringWriter::new(base64::STANDARD),
+            limits,
+        );
+        self.write_xdr(&mut enc)?;
+        let b64 = enc.inner.into_inner();
+        Ok(b64)
+    }
+
+    /// Write the XDR into the buffer, advancing the buffer past the bytes
+    /// written.
+    ///
+    /// An error is returned if the buffer does not have the capacity remaining
+    /// to contain the XDR, in which case some of the XDR may have been
+    /// written.
+    #[cfg(feature = "bytes")]
+    fn write_xdr_buf<B: bytes::BufMut>(&self, buf: &mut B, limits: Limits) -> Result<()> {
+        let mut w = Limited::new(bytes::BufMut::writer(buf), limits);
+        self.write_xdr(&mut w)
+    }
+
+    /// Write the XDR into a new `Bytes`.
+    ///
+    /// The `Bytes` takes the buffer the XDR is written into, without copying.
+    #[cfg(feature = "bytes")]
+    fn to_xdr_bytes(&self, limits: Limits) -> Result<bytes::Bytes> {
+        Ok(self.to_xdr(limits)?.into())
+    }
+}
+
+/// `LenCounter` is a writer that discards the bytes written to it, and counts
+/// the number of bytes written.
+struct LenCounter {
+    len: usize,
+}
+
+impl Write for LenCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `Pad_len` returns the number of bytes to pad an XDR value of the given
+/// length to make the final serialized size a multiple of 4.
+fn pad_len(len: usize) -> usize {
+    (4 - (len % 4)) % 4
+}
+
+/// `inner_depth` returns the depth remaining for the values contained in a
+/// value written with `depth` remaining, as limited by
+/// [`Limited::with_limited_depth`].
+fn inner_depth(depth: u32) -> Result<u32> {
+    depth.checked_sub(1).ok_or(Error::DepthLimitExceeded)
+}
+
+/// `fixed_xdr_len` returns `len`, the length of the XDR of a fixed length
+/// value that is written `levels` deep, if `depth` allows that many levels.
+fn fixed_xdr_len(len: usize, levels: u32, depth: u32) -> Result<usize> {
+    if depth < levels {
+        return Err(Error::DepthLimitExceeded);
+    }
+    Ok(len)
+}
+
+/// `var_opaque_xdr_len` returns the length of the XDR of variable length
+/// opaque or string data of `len` bytes, if `depth` allows its length prefix.
+fn var_opaque_xdr_len(len: usize, depth: u32) -> Result<usize> {
+    fixed_xdr_len(4 + len + pad_len(len), 2, depth)
+}
+
+/// `Skip_padded` reads and discards opaque data of the given length followed
+/// by its padding, without allocating.
+#[cfg(feature = "alloc")]
+fn skip_padded<R: Read>(r: &mut Limited<R>, len: usize) -> Result<()> {
+    r.consume_len(len)?;
+    let padding = pad_len(len);
+    r.consume_len(padding)?;
+
+    let mut buf = [0u8; 256];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = core::cmp::min(remaining, buf.len());
+        r.read_exact(&mut buf[..n])?;
+        remaining -= n;
+    }
+
+    let pad = &mut [0u8; 3][..padding];
+    r.read_exact(pad)?;
+    if pad.iter().any(|b| *b != 0) {
+        return Err(Error::NonZeroPadding);
+    }
+
+    Ok(())
+}
+
+impl ReadXdr for i32 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut b = [0u8; 4];
+        r.with_limited_depth(|r| {
+            r.consume_len(b.len())?;
+            r.read_exact(&mut b)?;
+            Ok(i32::from_be_bytes(b))
+        })
+    }
+}
+
+impl WriteXdr for i32 {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        let b: [u8; 4] = self.to_be_bytes();
+        w.with_limited_depth(|w| {
+            w.consume_len(b.len())?;
+            Ok(w.write_all(&b)?)
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(4, 1, depth)
+    }
+}
+
+impl ReadXdr for u32 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut b = [0u8; 4];
+        r.with_limited_depth(|r| {
+            r.consume_len(b.len())?;
+            r.read_exact(&mut b)?;
+            Ok(u32::from_be_bytes(b))
+        })
+    }
+}
+
+impl WriteXdr for u32 {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        let b: [u8; 4] = self.to_be_bytes();
+        w.with_limited_depth(|w| {
+            w.consume_len(b.len())?;
+            Ok(w.write_all(&b)?)
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(4, 1, depth)
+    }
+}
+
+impl ReadXdr for i64 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut b = [0u8; 8];
+        r.with_limited_depth(|r| {
+            r.consume_len(b.len())?;
+            r.read_exact(&mut b)?;
+            Ok(i64::from_be_bytes(b))
+        })
+    }
+}
+
+impl WriteXdr for i64 {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        let b: [u8; 8] = self.to_be_bytes();
+        w.with_limited_depth(|w| {
+            w.consume_len(b.len())?;
+            Ok(w.write_all(&b)?)
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(8, 1, depth)
+    }
+}
+
+impl ReadXdr for u64 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        let mut b = [0u8; 8];
+        r.with_limited_depth(|r| {
+            r.consume_len(b.len())?;
+            r.read_exact(&mut b)?;
+            Ok(u64::from_be_bytes(b))
+        })
+    }
+}
+
+impl WriteXdr for u64 {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        let b: [u8; 8] = self.to_be_bytes();
+        w.with_limited_depth(|w| {
+            w.consume_len(b.len())?;
+            Ok(w.write_all(&b)?)
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(8, 1, depth)
+    }
+}
+
+impl ReadXdr for f32 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(_r: &mut Limited<R>) -> Result<Self> {
+        todo!()
+    }
+}
+
+impl WriteXdr for f32 {
+    fn write_xdr<W: Write>(&self, _w: &mut Limited<W>) -> Result<()> {
+        todo!()
+    }
+}
+
+impl ReadXdr for f64 {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(_r: &mut Limited<R>) -> Result<Self> {
+        todo!()
+    }
+}
+
+impl WriteXdr for f64 {
+    fn write_xdr<W: Write>(&self, _w: &mut Limited<W>) -> Result<()> {
+        todo!()
+    }
+}
+
+impl ReadXdr for bool {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let i = u32::read_xdr(r)?;
+            let b = i == 1;
+            Ok(b)
+        })
+    }
+}
+
+impl WriteXdr for bool {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            let i = u32::from(*self); // true = 1, false = 0
+            i.write_xdr(w)
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(4, 2, depth)
+    }
+}
+
+impl<T: ReadXdr> ReadXdr for Option<T> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let i = u32::read_xdr(r)?;
+            match i {
+                0 => Ok(None),
+                1 => {
+                    let t = T::read_xdr(r)?;
+                    Ok(Some(t))
+                }
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            let i = u32::read_xdr(r)?;
+            match i {
+                0 => Ok(()),
+                1 => T::skip_xdr(r),
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+}
+
+impl<T: WriteXdr> WriteXdr for Option<T> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            if let Some(t) = self {
+                1u32.write_xdr(w)?;
+                t.write_xdr(w)?;
+            } else {
+                0u32.write_xdr(w)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        let depth = inner_depth(depth)?;
+        let len = 0u32.xdr_len_at_depth(depth)?;
+        match self {
+            Some(t) => Ok(len + t.xdr_len_at_depth(depth)?),
+            None => Ok(len),
+        }
+    }
+}
+
+impl<T: ReadXdr> ReadXdr for Box<T> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            r.consume_alloc(core::mem::size_of::<T>())?;
+            Ok(Box::new(T::read_xdr(r)?))
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(T::skip_xdr)
+    }
+}
+
+impl<T: WriteXdr> WriteXdr for Box<T> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| T::write_xdr(self, w))
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        T::xdr_len_at_depth(self, inner_depth(depth)?)
+    }
+}
+
+impl ReadXdr for () {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(_r: &mut Limited<R>) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl WriteXdr for () {
+    fn write_xdr<W: Write>(&self, _w: &mut Limited<W>) -> Result<()> {
+        Ok(())
+    }
+
+    fn xdr_len_at_depth(&self, _depth: u32) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+impl<const N: usize> ReadXdr for [u8; N] {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            r.consume_len(N)?;
+            let padding = pad_len(N);
+            r.consume_len(padding)?;
+            let mut arr = [0u8; N];
+            r.read_exact(&mut arr)?;
+            let pad = &mut [0u8; 3][..padding];
+            r.read_exact(pad)?;
+            if pad.iter().any(|b| *b != 0) {
+                return Err(Error::NonZeroPadding);
+            }
+            Ok(arr)
+        })
+    }
+}
+
+impl<const N: usize> WriteXdr for [u8; N] {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            w.consume_len(N)?;
+            let padding = pad_len(N);
+            w.consume_len(padding)?;
+            w.write_all(self)?;
+            w.write_all(&[0u8; 3][..padding])?;
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        fixed_xdr_len(N + pad_len(N), 1, depth)
+    }
+}
+
+impl<T: ReadXdr, const N: usize> ReadXdr for [T; N] {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let mut vec = Vec::with_capacity(N);
+            for i in 0..N {
+                let t = r.with_path(PathSegment::Index(i), T::read_xdr)?;
+                vec.push(t);
+            }
+            let arr: [T; N] = vec.try_into().unwrap_or_else(|_: Vec<T>| unreachable!());
+            Ok(arr)
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            for i in 0..N {
+                r.with_path(PathSegment::Index(i), T::skip_xdr)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<T: WriteXdr, const N: usize> WriteXdr for [T; N] {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            for t in self {
+                t.write_xdr(w)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        let depth = inner_depth(depth)?;
+        let mut len = 0;
+        for t in self {
+            len += t.xdr_len_at_depth(depth)?;
+        }
+        Ok(len)
+    }
+}
+
+// VecM ------------------------------------------------------------------------
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct VecM<T, const MAX: u32 = { u32::MAX }>(Vec<T>);
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct VecM<T, const MAX: u32 = { u32::MAX }>(Vec<T>)
+where
+    T: 'static;
+
+impl<T, const MAX: u32> Deref for VecM<T, MAX> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MAX: u32> Default for VecM<T, MAX> {
+    fn default() -> Self {
+        Self(Vec::default())
+    }
+}
+
+impl<T, const MAX: u32> VecM<T, MAX> {
+    pub const MAX_LEN: usize = { MAX as usize };
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn max_len(&self) -> usize {
+        Self::MAX_LEN
+    }
+
+    #[must_use]
+    pub fn as_vec(&self) -> &Vec<T> {
+        self.as_ref()
+    }
+}
+
+impl<T: Clone, const MAX: u32> VecM<T, MAX> {
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.into()
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+}
+
+impl<const MAX: u32> VecM<u8, MAX> {
+    #[cfg(feature = "alloc")]
+    pub fn to_string(&self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn into_string(self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_string_lossy(self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+}
+
+impl<T: Clone> VecM<T, 1> {
+    #[must_use]
+    pub fn to_option(&self) -> Option<T> {
+        if self.len() > 0 {
+            Some(self.0[0].clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<T: Clone> From<VecM<T, 1>> for Option<T> {
+    #[must_use]
+    fn from(v: VecM<T, 1>) -> Self {
+        v.to_option()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> VecM<T, 1> {
+    #[must_use]
+    pub fn into_option(mut self) -> Option<T> {
+        self.0.drain(..).next()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<VecM<T, 1>> for Option<T> {
+    #[must_use]
+    fn from(v: VecM<T, 1>) -> Self {
+        v.into_option()
+    }
+}
+
+impl<T, const MAX: u32> TryFrom<Vec<T>> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: Vec<T>) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<T, const MAX: u32> From<VecM<T, MAX>> for Vec<T> {
+    #[must_use]
+    fn from(v: VecM<T, MAX>) -> Self {
+        v.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const MAX: u32> From<&VecM<T, MAX>> for Vec<T> {
+    #[must_use]
+    fn from(v: &VecM<T, MAX>) -> Self {
+        v.0.clone()
+    }
+}
+
+impl<T, const MAX: u32> AsRef<Vec<T>> for VecM<T, MAX> {
+    #[must_use]
+    fn as_ref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const MAX: u32> TryFrom<&Vec<T>> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &Vec<T>) -> Result<Self> {
+        v.as_slice().try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const MAX: u32> TryFrom<&[T]> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[T]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<T, const MAX: u32> AsRef<[T]> for VecM<T, MAX> {
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn as_ref(&self) -> &[T] {
+        self.0.as_ref()
+    }
+    #[cfg(not(feature = "alloc"))]
+    #[must_use]
+    fn as_ref(&self) -> &[T] {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const N: usize, const MAX: u32> TryFrom<[T; N]> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: [T; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const N: usize, const MAX: u32> TryFrom<VecM<T, MAX>> for [T; N] {
+    type Error = VecM<T, MAX>;
+
+    fn try_from(v: VecM<T, MAX>) -> core::result::Result<Self, Self::Error> {
+        let s: [T; N] = v.0.try_into().map_err(|v: Vec<T>| VecM::<T, MAX>(v))?;
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone, const N: usize, const MAX: u32> TryFrom<&[T; N]> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[T; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<T: Clone, const N: usize, const MAX: u32> TryFrom<&'static [T; N]> for VecM<T, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static [T; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&String> for VecM<u8, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.as_bytes().to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<String> for VecM<u8, MAX> {
+    type Error = Error;
+
+    fn try_from(v: String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<VecM<u8, MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: VecM<u8, MAX>) -> Result<Self> {
+        Ok(String::from_utf8(v.0)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&VecM<u8, MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: &VecM<u8, MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?.to_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&str> for VecM<u8, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const MAX: u32> TryFrom<&'static str> for VecM<u8, MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(VecM(v.as_bytes()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<'a, const MAX: u32> TryFrom<&'a VecM<u8, MAX>> for &'a str {
+    type Error = Error;
+
+    fn try_from(v: &'a VecM<u8, MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?)
+    }
+}
+
+impl<const MAX: u32> ReadXdr for VecM<u8, MAX> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            r.consume_len(len as usize)?;
+            let padding = pad_len(len as usize);
+            r.consume_len(padding)?;
+            r.consume_alloc(len as usize)?;
+
+            let mut vec = vec![0u8; len as usize];
+            r.read_exact(&mut vec)?;
+
+            let pad = &mut [0u8; 3][..padding];
+            r.read_exact(pad)?;
+            if pad.iter().any(|b| *b != 0) {
+                return Err(Error::NonZeroPadding);
+            }
+
+            Ok(VecM(vec))
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            skip_padded(r, len as usize)
+        })
+    }
+}
+
+impl<const MAX: u32> WriteXdr for VecM<u8, MAX> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            let len: u32 = self.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            len.write_xdr(w)?;
+
+            w.consume_len(self.len())?;
+            let padding = pad_len(self.len());
+            w.consume_len(padding)?;
+
+            w.write_all(&self[..])?;
+
+            w.write_all(&[0u8; 3][..padding])?;
+
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        var_opaque_xdr_len(self.len(), depth)
+    }
+}
+
+impl<T: ReadXdr, const MAX: u32> ReadXdr for VecM<T, MAX> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+            r.consume_elements::<T>(len as usize)?;
+
+            let mut vec = Vec::new();
+            for i in 0..len as usize {
+                let t = r.with_path(PathSegment::Index(i), T::read_xdr)?;
+                vec.push(t);
+            }
+
+            Ok(VecM(vec))
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            let len = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+            if len as usize > r.limits.elements {
+                return Err(Error::ElementLimitExceeded);
+            }
+
+            for i in 0..len as usize {
+                r.with_path(PathSegment::Index(i), T::skip_xdr)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<T: WriteXdr, const MAX: u32> WriteXdr for VecM<T, MAX> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            let len: u32 = self.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            len.write_xdr(w)?;
+
+            for t in self.iter() {
+                t.write_xdr(w)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        let depth = inner_depth(depth)?;
+        let mut len = 0u32.xdr_len_at_depth(depth)?;
+        for t in self.iter() {
+            len += t.xdr_len_at_depth(depth)?;
+        }
+        Ok(len)
+    }
+}
+
+// BytesM ------------------------------------------------------------------------
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct BytesM<const MAX: u32 = { u32::MAX }>(Vec<u8>);
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct BytesM<const MAX: u32 = { u32::MAX }>(Vec<u8>);
+
+impl<const MAX: u32> core::fmt::Display for BytesM<MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(feature = "alloc")]
+        let v = &self.0;
+        #[cfg(not(feature = "alloc"))]
+        let v = self.0;
+        for b in v {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const MAX: u32> core::fmt::Debug for BytesM<MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(feature = "alloc")]
+        let v = &self.0;
+        #[cfg(not(feature = "alloc"))]
+        let v = self.0;
+        write!(f, "BytesM(")?;
+        for b in v {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> core::str::FromStr for BytesM<MAX> {
+    type Err = Error;
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        hex::decode(s).map_err(|_| Error::InvalidHex)?.try_into()
+    }
+}
+
+impl<const MAX: u32> Deref for BytesM<MAX> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: u32> Default for BytesM<MAX> {
+    fn default() -> Self {
+        Self(Vec::default())
+    }
+}
+
+impl<const MAX: u32> BytesM<MAX> {
+    pub const MAX_LEN: usize = { MAX as usize };
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn max_len(&self) -> usize {
+        Self::MAX_LEN
+    }
+
+    #[must_use]
+    pub fn as_vec(&self) -> &Vec<u8> {
+        self.as_ref()
+    }
+}
+
+impl<const MAX: u32> BytesM<MAX> {
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.into()
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+}
+
+impl<const MAX: u32> BytesM<MAX> {
+    #[cfg(feature = "alloc")]
+    pub fn to_string(&self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn into_string(self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_string_lossy(self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+}
+
+impl<const MAX: u32> TryFrom<Vec<u8>> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: Vec<u8>) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<const MAX: u32> From<BytesM<MAX>> for Vec<u8> {
+    #[must_use]
+    fn from(v: BytesM<MAX>) -> Self {
+        v.0
+    }
+}
+
+/// Converts the `Bytes` into the `Vec<u8>` that `BytesM` holds. The
+/// [`shared::BytesM`] holds a `Bytes` as it is.
+#[cfg(feature = "bytes")]
+impl<const MAX: u32> TryFrom<bytes::Bytes> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: bytes::Bytes) -> Result<Self> {
+        Vec::from(v).try_into()
+    }
+}
+
+/// Converts to `Bytes` without copying.
+#[cfg(feature = "bytes")]
+impl<const MAX: u32> From<BytesM<MAX>> for bytes::Bytes {
+    fn from(v: BytesM<MAX>) -> Self {
+        v.0.into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> From<&BytesM<MAX>> for Vec<u8> {
+    #[must_use]
+    fn from(v: &BytesM<MAX>) -> Self {
+        v.0.clone()
+    }
+}
+
+impl<const MAX: u32> AsRef<Vec<u8>> for BytesM<MAX> {
+    #[must_use]
+    fn as_ref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&Vec<u8>> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &Vec<u8>) -> Result<Self> {
+        v.as_slice().try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&[u8]> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[u8]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<const MAX: u32> AsRef<[u8]> for BytesM<MAX> {
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+    #[cfg(not(feature = "alloc"))]
+    #[must_use]
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<[u8; N]> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: [u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<BytesM<MAX>> for [u8; N] {
+    type Error = BytesM<MAX>;
+
+    fn try_from(v: BytesM<MAX>) -> core::result::Result<Self, Self::Error> {
+        let s: [u8; N] = v.0.try_into().map_err(BytesM::<MAX>)?;
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<&[u8; N]> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const N: usize, const MAX: u32> TryFrom<&'static [u8; N]> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static [u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&String> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.as_bytes().to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<String> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<BytesM<MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: BytesM<MAX>) -> Result<Self> {
+        Ok(String::from_utf8(v.0)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&BytesM<MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: &BytesM<MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?.to_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&str> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const MAX: u32> TryFrom<&'static str> for BytesM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(BytesM(v.as_bytes()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<'a, const MAX: u32> TryFrom<&'a BytesM<MAX>> for &'a str {
+    type Error = Error;
+
+    fn try_from(v: &'a BytesM<MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?)
+    }
+}
+
+impl<const MAX: u32> ReadXdr for BytesM<MAX> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            r.consume_len(len as usize)?;
+            let padding = pad_len(len as usize);
+            r.consume_len(padding)?;
+            r.consume_alloc(len as usize)?;
+
+            let mut vec = vec![0u8; len as usize];
+            r.read_exact(&mut vec)?;
+
+            let pad = &mut [0u8; 3][..padding];
+            r.read_exact(pad)?;
+            if pad.iter().any(|b| *b != 0) {
+                return Err(Error::NonZeroPadding);
+            }
+
+            Ok(BytesM(vec))
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            skip_padded(r, len as usize)
+        })
+    }
+}
+
+impl<const MAX: u32> WriteXdr for BytesM<MAX> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            let len: u32 = self.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            len.write_xdr(w)?;
+
+            w.consume_len(self.len())?;
+            let padding = pad_len(self.len());
+            w.consume_len(padding)?;
+
+            w.write_all(&self[..])?;
+
+            w.write_all(&[0u8; 3][..pad_len(len as usize)])?;
+
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        var_opaque_xdr_len(self.len(), depth)
+    }
+}
+
+// StringM ------------------------------------------------------------------------
+
+/// A string type that contains arbitrary bytes.
+///
+/// Convertible, fallibly, to/from a Rust UTF-8 String using
+/// [`TryFrom`]/[`TryInto`]/[`StringM::to_utf8_string`].
+///
+/// Convertible, lossyly, to a Rust UTF-8 String using
+/// [`StringM::to_utf8_string_lossy`].
+///
+/// Convertible to/from escaped printable-ASCII using
+/// [`Display`]/[`ToString`]/[`FromStr`].
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct StringM<const MAX: u32 = { u32::MAX }>(Vec<u8>);
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct StringM<const MAX: u32 = { u32::MAX }>(Vec<u8>);
+
+impl<const MAX: u32> core::fmt::Display for StringM<MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(feature = "alloc")]
+        let v = &self.0;
+        #[cfg(not(feature = "alloc"))]
+        let v = self.0;
+        for b in escape_bytes::Escape::new(v) {
+            write!(f, "{}", b as char)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const MAX: u32> core::fmt::Debug for StringM<MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(feature = "alloc")]
+        let v = &self.0;
+        #[cfg(not(feature = "alloc"))]
+        let v = self.0;
+        write!(f, "StringM(")?;
+        for b in escape_bytes::Escape::new(v) {
+            write!(f, "{}", b as char)?;
+        }
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> core::str::FromStr for StringM<MAX> {
+    type Err = Error;
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let b = escape_bytes::unescape(s.as_bytes()).map_err(|_| Error::Invalid)?;
+        Ok(Self(b))
+    }
+}
+
+impl<const MAX: u32> Deref for StringM<MAX> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: u32> Default for StringM<MAX> {
+    fn default() -> Self {
+        Self(Vec::default())
+    }
+}
+
+impl<const MAX: u32> StringM<MAX> {
+    pub const MAX_LEN: usize = { MAX as usize };
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn max_len(&self) -> usize {
+        Self::MAX_LEN
+    }
+
+    #[must_use]
+    pub fn as_vec(&self) -> &Vec<u8> {
+        self.as_ref()
+    }
+}
+
+impl<const MAX: u32> StringM<MAX> {
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.into()
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+}
+
+impl<const MAX: u32> StringM<MAX> {
+    #[cfg(feature = "alloc")]
+    pub fn to_utf8_string(&self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn into_utf8_string(self) -> Result<String> {
+        self.try_into()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_utf8_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_utf8_string_lossy(self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
+}
+
+impl<const MAX: u32> TryFrom<Vec<u8>> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: Vec<u8>) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<const MAX: u32> From<StringM<MAX>> for Vec<u8> {
+    #[must_use]
+    fn from(v: StringM<MAX>) -> Self {
+        v.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> From<&StringM<MAX>> for Vec<u8> {
+    #[must_use]
+    fn from(v: &StringM<MAX>) -> Self {
+        v.0.clone()
+    }
+}
+
+impl<const MAX: u32> AsRef<Vec<u8>> for StringM<MAX> {
+    #[must_use]
+    fn as_ref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&Vec<u8>> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &Vec<u8>) -> Result<Self> {
+        v.as_slice().try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&[u8]> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[u8]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<const MAX: u32> AsRef<[u8]> for StringM<MAX> {
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+    #[cfg(not(feature = "alloc"))]
+    #[must_use]
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<[u8; N]> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: [u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<StringM<MAX>> for [u8; N] {
+    type Error = StringM<MAX>;
+
+    fn try_from(v: StringM<MAX>) -> core::result::Result<Self, Self::Error> {
+        let s: [u8; N] = v.0.try_into().map_err(StringM::<MAX>)?;
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, const MAX: u32> TryFrom<&[u8; N]> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &[u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const N: usize, const MAX: u32> TryFrom<&'static [u8; N]> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static [u8; N]) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&String> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.as_bytes().to_vec()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<String> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: String) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<StringM<MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: StringM<MAX>) -> Result<Self> {
+        Ok(String::from_utf8(v.0)?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&StringM<MAX>> for String {
+    type Error = Error;
+
+    fn try_from(v: &StringM<MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?.to_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> TryFrom<&str> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.into()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<const MAX: u32> TryFrom<&'static str> for StringM<MAX> {
+    type Error = Error;
+
+    fn try_from(v: &'static str) -> Result<Self> {
+        let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+        if len <= MAX {
+            Ok(StringM(v.as_bytes()))
+        } else {
+            Err(Error::LengthExceedsMax)
+        }
+    }
+}
+
+impl<'a, const MAX: u32> TryFrom<&'a StringM<MAX>> for &'a str {
+    type Error = Error;
+
+    fn try_from(v: &'a StringM<MAX>) -> Result<Self> {
+        Ok(core::str::from_utf8(v.as_ref())?)
+    }
+}
+
+impl<const MAX: u32> ReadXdr for StringM<MAX> {
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            r.consume_len(len as usize)?;
+            let padding = pad_len(len as usize);
+            r.consume_len(padding)?;
+            r.consume_alloc(len as usize)?;
+
+            let mut vec = vec![0u8; len as usize];
+            r.read_exact(&mut vec)?;
+
+            let pad = &mut [0u8; 3][..padding];
+            r.read_exact(pad)?;
+            if pad.iter().any(|b| *b != 0) {
+                return Err(Error::NonZeroPadding);
+            }
+
+            Ok(StringM(vec))
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<()> {
+        r.with_limited_depth(|r| {
+            let len: u32 = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+
+            skip_padded(r, len as usize)
+        })
+    }
+}
+
+impl<const MAX: u32> WriteXdr for StringM<MAX> {
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<()> {
+        w.with_limited_depth(|w| {
+            let len: u32 = self.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            len.write_xdr(w)?;
+
+            w.consume_len(self.len())?;
+            let padding = pad_len(self.len());
+            w.consume_len(padding)?;
+
+            w.write_all(&self[..])?;
+
+            w.write_all(&[0u8; 3][..padding])?;
+
+            Ok(())
+        })
+    }
+
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize> {
+        var_opaque_xdr_len(self.len(), depth)
+    }
+}
+
+// Visit ------------------------------------------------------------------------
+
+/// `Visitor` is called with each value visited by [`Visit::visit`].
+///
+/// Closures of the form `FnMut(&[PathSegment], &dyn Any)` are visitors.
+#[cfg(feature = "alloc")]
+pub trait Visitor {
+    /// Visit the value located at the path, relative to the value that the
+    /// visit started at.
+    ///
+    /// The value is one of the XDR types, or a primitive or container type that
+    /// they contain, such as `u32`, `Option<T>` or `VecM<T>`, and can be downcast
+    /// to the types of interest with `downcast_ref`.
+    fn visit(&mut self, path: &[PathSegment], value: &dyn Any);
+}
+
+#[cfg(feature = "alloc")]
+impl<F: FnMut(&[PathSegment], &dyn Any)> Visitor for F {
+    fn visit(&mut self, path: &[PathSegment], value: &dyn Any) {
+        self(path, value);
+    }
+}
+
+/// `VisitorMut` is called with each value visited by [`VisitMut::visit_mut`],
+/// and may modify or replace the value.
+///
+/// Closures of the form `FnMut(&[PathSegment], &mut dyn Any)` are visitors.
+#[cfg(feature = "alloc")]
+pub trait VisitorMut {
+    /// Visit the value located at the path, relative to the value that the
+    /// visit started at.
+    ///
+    /// The value can be downcast to the types of interest with `downcast_mut`.
+    /// The values nested within the value are visited after it, so a value
+    /// that is replaced has the values nested within its replacement visited.
+    fn visit_mut(&mut self, path: &[PathSegment], value: &mut dyn Any);
+}
+
+#[cfg(feature = "alloc")]
+impl<F: FnMut(&[PathSegment], &mut dyn Any)> VisitorMut for F {
+    fn visit_mut(&mut self, path: &[PathSegment], value: &mut dyn Any) {
+        self(path, value);
+    }
+}
+
+/// `Visit` defines types that can be traversed by a [`Visitor`].
+#[cfg(feature = "alloc")]
+pub trait Visit: Any {
+    /// Visit the value and every value nested within it.
+    ///
+    /// Each value is visited before the values nested within it, and nested
+    /// values are visited in the order they are encoded in the XDR. Boxes are
+    /// not visited themselves, only the values they contain.
+    fn visit(&self, visitor: &mut dyn Visitor) {
+        self.walk(&mut Vec::new(), visitor);
+    }
+
+    /// Visit the value, located at the path, and every value nested within it.
+    ///
+    /// The path is extended while visiting the nested values, and restored
+    /// before returning.
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor);
+}
+
+/// `VisitMut` defines types that can be traversed and modified in place by a
+/// [`VisitorMut`].
+#[cfg(feature = "alloc")]
+pub trait VisitMut: Any {
+    /// Visit the value and every value nested within it, allowing each to be
+    /// modified.
+    ///
+    /// Values are visited in the same order as [`Visit::visit`].
+    fn visit_mut(&mut self, visitor: &mut dyn VisitorMut) {
+        self.walk_mut(&mut Vec::new(), visitor);
+    }
+
+    /// Visit the value, located at the path, and every value nested within it,
+    /// allowing each to be modified.
+    ///
+    /// The path is extended while visiting the nested values, and restored
+    /// before returning.
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut);
+}
+
+#[cfg(feature = "alloc")]
+fn walk_at<T: Visit>(
+    path: &mut Vec<PathSegment>,
+    segment: PathSegment,
+    value: &T,
+    visitor: &mut dyn Visitor,
+) {
+    path.push(segment);
+    value.walk(path, visitor);
+    path.pop();
+}
+
+#[cfg(feature = "alloc")]
+fn walk_mut_at<T: VisitMut>(
+    path: &mut Vec<PathSegment>,
+    segment: PathSegment,
+    value: &mut T,
+    visitor: &mut dyn VisitorMut,
+) {
+    path.push(segment);
+    value.walk_mut(path, visitor);
+    path.pop();
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for i32 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for i32 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for u32 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for u32 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for i64 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for i64 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for u64 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for u64 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for f32 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for f32 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for f64 {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for f64 {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for bool {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VisitMut for bool {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Visit> Visit for Option<T> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+        if let Some(v) = self {
+            v.walk(path, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: VisitMut> VisitMut for Option<T> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+        if let Some(v) = self {
+            v.walk_mut(path, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Visit> Visit for Box<T> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        T::walk(self, path, visitor);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: VisitMut> VisitMut for Box<T> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        T::walk_mut(self, path, visitor);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> Visit for [u8; N] {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> VisitMut for [u8; N] {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Visit, const N: usize> Visit for [T; N] {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+        for (i, v) in self.iter().enumerate() {
+            walk_at(path, PathSegment::Index(i), v, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: VisitMut, const N: usize> VisitMut for [T; N] {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+        for (i, v) in self.iter_mut().enumerate() {
+            walk_mut_at(path, PathSegment::Index(i), v, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Visit for VecM<u8, MAX> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> VisitMut for VecM<u8, MAX> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Visit, const MAX: u32> Visit for VecM<T, MAX> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+        for (i, v) in self.iter().enumerate() {
+            walk_at(path, PathSegment::Index(i), v, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: VisitMut, const MAX: u32> VisitMut for VecM<T, MAX> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+        for (i, v) in self.0.iter_mut().enumerate() {
+            walk_mut_at(path, PathSegment::Index(i), v, visitor);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Visit for BytesM<MAX> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> VisitMut for BytesM<MAX> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Visit for StringM<MAX> {
+    fn walk(&self, path: &mut Vec<PathSegment>, visitor: &mut dyn Visitor) {
+        visitor.visit(path, self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> VisitMut for StringM<MAX> {
+    fn walk_mut(&mut self, path: &mut Vec<PathSegment>, visitor: &mut dyn VisitorMut) {
+        visitor.visit_mut(path, self);
+    }
+}
+
+// Diff ------------------------------------------------------------------------
+
+/// `Difference` is a difference between two values found by [`Diff::diff`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Difference {
+    /// The path to the value that differs, relative to the values compared.
+    pub path: Vec<PathSegment>,
+    pub kind: DifferenceKind,
+}
+
+/// `DifferenceKind` is the kind of a [`Difference`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DifferenceKind {
+    /// The value is different. Reported for values that have no nested values
+    /// that are compared, such as integers, enums and opaque data, and for
+    /// options where only one is none.
+    Changed,
+    /// The union has a different arm, named the same as the variants. The
+    /// values of the arms are not compared.
+    ArmChanged {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// The element of an array was added, and the index in the path is the
+    /// index in the other value.
+    Added,
+    /// The element of an array was removed, and the index in the path is the
+    /// index in the value.
+    Removed,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = Query::from(self.path.as_slice());
+        let path = if path.segments().is_empty() {
+            ".".to_string()
+        } else {
+            path.to_string()
+        };
+        match self.kind {
+            DifferenceKind::Changed => write!(f, "~ {path}"),
+            DifferenceKind::ArmChanged { from, to } => {
+                let from: String = snake_case(from).collect();
+                let to: String = snake_case(to).collect();
+                write!(f, "~ {path} ({from} -> {to})")
+            }
+            DifferenceKind::Added => write!(f, "+ {path}"),
+            DifferenceKind::Removed => write!(f, "- {path}"),
+        }
+    }
+}
+
+/// `Diff` defines types that can be compared structurally, finding the paths
+/// to the nested values that differ.
+#[cfg(feature = "alloc")]
+pub trait Diff: PartialEq {
+    /// Returns the differences between the value and the other value, in the
+    /// order the values are encoded in the XDR. Values that are equal have no
+    /// differences.
+    ///
+    /// Arrays are compared element by element, after skipping the elements
+    /// that are equal at the start and end of both arrays, so that inserting
+    /// or removing elements is reported as elements being added or removed.
+    fn diff(&self, other: &Self) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        self.diff_into(other, &mut Vec::new(), &mut differences);
+        differences
+    }
+
+    /// Add the differences between the value and the other value, located at
+    /// the path, to the differences.
+    ///
+    /// The default implementation compares the values as a whole.
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {
+        if self != other {
+            differ(path, DifferenceKind::Changed, differences);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn differ(path: &[PathSegment], kind: DifferenceKind, differences: &mut Vec<Difference>) {
+    differences.push(Difference {
+        path: path.to_vec(),
+        kind,
+    });
+}
+
+#[cfg(feature = "alloc")]
+fn diff_at<T: Diff>(
+    path: &mut Vec<PathSegment>,
+    segment: PathSegment,
+    value: &T,
+    other: &T,
+    differences: &mut Vec<Difference>,
+) {
+    path.push(segment);
+    value.diff_into(other, path, differences);
+    path.pop();
+}
+
+#[cfg(feature = "alloc")]
+fn diff_elements<T: Diff>(
+    values: &[T],
+    others: &[T],
+    path: &mut Vec<PathSegment>,
+    differences: &mut Vec<Difference>,
+) {
+    let prefix = values
+        .iter()
+        .zip(others)
+        .take_while(|(v, o)| v == o)
+        .count();
+    let (values, others) = (&values[prefix..], &others[prefix..]);
+    let suffix = values
+        .iter()
+        .rev()
+        .zip(others.iter().rev())
+        .take_while(|(v, o)| v == o)
+        .count();
+    let values = &values[..values.len() - suffix];
+    let others = &others[..others.len() - suffix];
+    for i in 0..values.len().max(others.len()) {
+        let segment = PathSegment::Index(prefix + i);
+        match (values.get(i), others.get(i)) {
+            (Some(v), Some(o)) => diff_at(path, segment, v, o, differences),
+            (Some(_), None) => {
+                path.push(segment);
+                differ(path, DifferenceKind::Removed, differences);
+                path.pop();
+            }
+            (None, Some(_)) => {
+                path.push(segment);
+                differ(path, DifferenceKind::Added, differences);
+                path.pop();
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Diff for i32 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for u32 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for i64 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for u64 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for f32 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for f64 {}
+
+#[cfg(feature = "alloc")]
+impl Diff for bool {}
+
+#[cfg(feature = "alloc")]
+impl<T: Diff> Diff for Option<T> {
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {
+        match (self, other) {
+            (Some(v), Some(o)) => v.diff_into(o, path, differences),
+            (None, None) => (),
+            _ => differ(path, DifferenceKind::Changed, differences),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Diff> Diff for Box<T> {
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {
+        T::diff_into(self, other, path, differences);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> Diff for [u8; N] {}
+
+#[cfg(feature = "alloc")]
+impl<T: Diff, const N: usize> Diff for [T; N] {
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {
+        diff_elements(self, other, path, differences);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Diff for VecM<u8, MAX> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Diff, const MAX: u32> Diff for VecM<T, MAX> {
+    fn diff_into(
+        &self,
+        other: &Self,
+        path: &mut Vec<PathSegment>,
+        differences: &mut Vec<Difference>,
+    ) {
+        diff_elements(self, other, path, differences);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Diff for BytesM<MAX> {}
+
+#[cfg(feature = "alloc")]
+impl<const MAX: u32> Diff for StringM<MAX> {}
+
+// Borrowed ------------------------------------------------------------------------
+
+/// `ReadXdrBorrowed` defines types that can be decoded from a byte slice while
+/// borrowing their variable length opaque and string data from that slice,
+/// rather than copying it into owned allocations.
+///
+/// The borrowed form of each type that contains variable length opaque or
+/// string data is in the [`borrowed`] module, and is named the same as the
+/// owned type. Types that contain no such data are their own borrowed form and
+/// are decoded with [`ReadXdr`].
+#[cfg(feature = "alloc")]
+pub trait ReadXdrBorrowed<'a>
+where
+    Self: Sized,
+{
+    /// The owned type that the borrowed type converts into.
+    type Owned;
+
+    /// Read the XDR and construct the borrowed type.
+    ///
+    /// Read bytes from the given slice, decoding the bytes as XDR, and
+    /// construct the type implementing this interface, borrowing opaque and
+    /// string data from the slice.
+    ///
+    /// Just enough bytes are read from the slice to construct the type. Any
+    /// residual bytes remain in the slice.
+    fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self>;
+
+    /// Convert the borrowed type into the owned type, copying any borrowed
+    /// data.
+    fn into_owned(self) -> Self::Owned;
+
+    /// Construct the borrowed type from the XDR bytes.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    fn from_xdr_borrowed(bytes: &'a [u8], limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(bytes, limits);
+        let t = Self::read_xdr_borrowed(&mut r)?;
+        if r.inner.is_empty() {
+            Ok(t)
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Limited<&'a [u8]> {
+    /// Split the given number of bytes off the front of the slice, returning
+    /// them borrowed from the slice.
+    ///
+    /// ### Errors
+    ///
+    /// If the slice contains fewer bytes than requested.
+    pub(crate) fn read_borrowed(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.inner.len() {
+            return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+        }
+        let (b, rest) = self.inner.split_at(len);
+        self.inner = rest;
+        Ok(b)
+    }
+
+    /// Read a variable length opaque or string, of which the length prefix
+    /// must not exceed the given max, and return the data borrowed from the
+    /// slice.
+    ///
+    /// ### Errors
+    ///
+    /// If the length exceeds the max, the length limit is exceeded, the slice
+    /// is too short, or the padding contains non-zero bytes.
+    pub(crate) fn read_borrowed_var(&mut self, max: u32) -> Result<&'a [u8]> {
+        let len: u32 = u32::read_xdr(self)?;
+        if len > max {
+            return Err(Error::LengthExceedsMax);
+        }
+
+        self.consume_len(len as usize)?;
+        let padding = pad_len(len as usize);
+        self.consume_len(padding)?;
+
+        let b = self.read_borrowed(len as usize)?;
+
+        let pad = self.read_borrowed(padding)?;
+        if pad.iter().any(|b| *b != 0) {
+            return Err(Error::NonZeroPadding);
+        }
+
+        Ok(b)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ReadXdrBorrowed<'a>> ReadXdrBorrowed<'a> for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let i = u32::read_xdr(r)?;
+            match i {
+                0 => Ok(None),
+                1 => {
+                    let t = T::read_xdr_borrowed(r)?;
+                    Ok(Some(t))
+                }
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ReadXdrBorrowed<'a>> ReadXdrBorrowed<'a> for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            r.consume_alloc(core::mem::size_of::<T>())?;
+            Ok(Box::new(T::read_xdr_borrowed(r)?))
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ReadXdrBorrowed<'a>, const N: usize> ReadXdrBorrowed<'a> for [T; N] {
+    type Owned = [T::Owned; N];
+
+    fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let mut vec = Vec::with_capacity(N);
+            for _ in 0..N {
+                let t = T::read_xdr_borrowed(r)?;
+                vec.push(t);
+            }
+            let arr: [T; N] = vec.try_into().unwrap_or_else(|_: Vec<T>| unreachable!());
+            Ok(arr)
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ReadXdrBorrowed<'a>, const MAX: u32> ReadXdrBorrowed<'a> for VecM<T, MAX> {
+    type Owned = VecM<T::Owned, MAX>;
+
+    fn read_xdr_borrowed(r: &mut Limited<&'a [u8]>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+            r.consume_elements::<T>(len as usize)?;
+
+            let mut vec = Vec::new();
+            for _ in 0..len {
+                let t = T::read_xdr_borrowed(r)?;
+                vec.push(t);
+            }
+
+            Ok(VecM(vec))
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        VecM(self.0.into_iter().map(T::into_owned).collect())
+    }
+}
+
+// Shared ------------------------------------------------------------------------
+
+/// `ReadXdrShared` defines types that can be decoded from a [`bytes::Buf`]
+/// while holding their variable length opaque and string data as
+/// [`bytes::Bytes`] taken from the buffer with [`bytes::Buf::copy_to_bytes`].
+///
+/// When the buffer is a `Bytes`, the data shares the storage of the buffer and
+/// is not copied.
+///
+/// The shared form of each type that contains variable length opaque or string
+/// data is in the [`shared`] module, and is named the same as the owned type.
+/// Types that contain no such data are their own shared form and are decoded
+/// with [`ReadXdr`].
+#[cfg(feature = "bytes")]
+pub trait ReadXdrShared
+where
+    Self: Sized,
+{
+    /// The owned type that the shared type converts into.
+    type Owned;
+
+    /// Read the XDR and construct the shared type.
+    ///
+    /// Read bytes from the buffer of the given reader, decoding the bytes as
+    /// XDR, and construct the type implementing this interface, taking opaque
+    /// and string data from the buffer.
+    ///
+    /// Just enough bytes are read from the buffer to construct the type. Any
+    /// residual bytes remain in the buffer.
+    fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self>;
+
+    /// Convert the shared type into the owned type, copying any shared data.
+    fn into_owned(self) -> Self::Owned;
+
+    /// Read the XDR from the buffer and construct the shared type, advancing
+    /// the buffer past the bytes read.
+    ///
+    /// Just enough bytes are read from the buffer to construct the type. Any
+    /// residual bytes remain in the buffer.
+    fn read_xdr_buf<B: bytes::Buf>(buf: &mut B, limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(bytes::Buf::reader(buf), limits);
+        Self::read_xdr_shared(&mut r)
+    }
+
+    /// Construct the shared type from the XDR in the buffer.
+    ///
+    /// An error is returned if the bytes are not completely consumed by the
+    /// deserialization.
+    fn from_xdr_buf(buf: impl bytes::Buf, limits: Limits) -> Result<Self> {
+        let mut r = Limited::new(buf.reader(), limits);
+        let t = Self::read_xdr_shared(&mut r)?;
+        if r.inner.get_ref().has_remaining() {
+            Err(Error::Invalid)
+        } else {
+            Ok(t)
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<B: bytes::Buf> Limited<bytes::buf::Reader<B>> {
+    /// Read a variable length opaque or string, of which the length prefix
+    /// must not exceed the given max, and return the data taken from the
+    /// buffer with [`bytes::Buf::copy_to_bytes`].
+    ///
+    /// ### Errors
+    ///
+    /// If the length exceeds the max, the length limit is exceeded, the buffer
+    /// is too short, or the padding contains non-zero bytes.
+    pub(crate) fn read_shared_var(&mut self, max: u32) -> Result<bytes::Bytes> {
+        let len: u32 = u32::read_xdr(self)?;
+        if len > max {
+            return Err(Error::LengthExceedsMax);
+        }
+
+        self.consume_len(len as usize)?;
+        let padding = pad_len(len as usize);
+        self.consume_len(padding)?;
+
+        let buf = self.inner.get_mut();
+        if buf.remaining() < len as usize + padding {
+            return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+        }
+        let b = buf.copy_to_bytes(len as usize);
+
+        let mut pad = [0u8; 3];
+        buf.copy_to_slice(&mut pad[..padding]);
+        if pad.iter().any(|b| *b != 0) {
+            return Err(Error::NonZeroPadding);
+        }
+
+        Ok(b)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: ReadXdrShared> ReadXdrShared for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let i = u32::read_xdr(r)?;
+            match i {
+                0 => Ok(None),
+                1 => {
+                    let t = T::read_xdr_shared(r)?;
+                    Ok(Some(t))
+                }
+                _ => Err(Error::Invalid),
+            }
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: ReadXdrShared> ReadXdrShared for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            r.consume_alloc(core::mem::size_of::<T>())?;
+            Ok(Box::new(T::read_xdr_shared(r)?))
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: ReadXdrShared, const N: usize> ReadXdrShared for [T; N] {
+    type Owned = [T::Owned; N];
+
+    fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let mut vec = Vec::with_capacity(N);
+            for _ in 0..N {
+                let t = T::read_xdr_shared(r)?;
+                vec.push(t);
+            }
+            let arr: [T; N] = vec.try_into().unwrap_or_else(|_: Vec<T>| unreachable!());
+            Ok(arr)
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T: ReadXdrShared, const MAX: u32> ReadXdrShared for VecM<T, MAX> {
+    type Owned = VecM<T::Owned, MAX>;
+
+    fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+        r.with_limited_depth(|r| {
+            let len = u32::read_xdr(r)?;
+            if len > MAX {
+                return Err(Error::LengthExceedsMax);
+            }
+            r.consume_elements::<T>(len as usize)?;
+
+            let mut vec = Vec::new();
+            for _ in 0..len {
+                let t = T::read_xdr_shared(r)?;
+                vec.push(t);
+            }
+
+            Ok(VecM(vec))
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        VecM(self.0.into_iter().map(T::into_owned).collect())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    pub fn vec_u8_read_without_padding() {
+        let buf = Cursor::new(vec![0, 0, 0, 4, 2, 2, 2, 2]);
+        let v = VecM::<u8, 8>::read_xdr(&mut Limited::new(buf, Limits::none())).unwrap();
+        assert_eq!(v.to_vec(), vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    pub fn vec_u8_read_with_padding() {
+        let buf = Cursor::new(vec![0, 0, 0, 1, 2, 0, 0, 0]);
+        let v = VecM::<u8, 8>::read_xdr(&mut Limited::new(buf, Limits::none())).unwrap();
+        assert_eq!(v.to_vec(), vec![2]);
+    }
+
+    #[test]
+    pub fn vec_u8_read_with_insufficient_padding() {
+        let buf = Cursor::new(vec![0, 0, 0, 1, 2, 0, 0]);
+        let res = VecM::<u8, 8>::read_xdr(&mut Limited::new(buf, Limits::none()));
+        match res {
+            Err(Error::Io(_)) => (),
+            _ => panic!("expected IO error got {res:?}"),
+        }
+    }
+
+    #[test]
+    pub fn vec_u8_read_with_non_zero_padding() {
+        let buf = Cursor::new(vec![0, 0, 0, 1, 2, 3, 0, 0]);
+        let res = VecM::<u8, 8>::read_xdr(&mut Limited::new(buf, Limits::none()));
+        match res {
+            Err(Error::NonZeroPadding) => (),
+            _ => panic!("expected NonZeroPadding got {res:?}"),
+        }
+    }
+
+    #[test]
+    pub fn vec_u8_write_without_padding() {
+        let mut buf = vec![];
+        let v: VecM<u8, 8> = vec![2, 2, 2, 2].try_into().unwrap();
+
+        v.write_xdr(&mut Limited::new(Cursor::new(&mut buf), Limits::none()))
+            .unwrap();
+        assert_eq!(buf, vec![0, 0, 0, 4, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    pub fn vec_u8_write_with_padding() {
+        let mut buf = vec![];
+        let v: VecM<u8, 8> = vec![2].try_into().unwrap();
+        v.write_xdr(&mut Limited::new(Cursor::new(&mut buf), Limits::none()))
+            .unwrap();
+        assert_eq!(buf, vec![0, 0, 0, 1, 2, 0, 0, 0]);
+    }
+
+    #[test]
+    pub fn arr_u8_read_without_padding() {
+        let buf = Cursor::new(vec![2, 2, 2, 2]);
+        let v = <[u8; 4]>::read_xdr(&mut Limited::new(buf, Limits::none())).unwrap();
+        assert_eq!(v, [2, 2, 2, 2]);
+    }
+
+    #[test]
+    pub fn arr_u8_read_with_padding() {
+        let buf = Cursor::new(vec![2, 0, 0, 0]);
+        let v = <[u8; 1]>::read_xdr(&mut Limited::new(buf, Limits::none())).unwrap();
+        assert_eq!(v, [2]);
+    }
+
+    #[test]
+    pub fn arr_u8_read_with_insufficient_padding() {
+        let buf = Cursor::new(vec![2, 0, 0]);
+        let res = <[u8; 1]>::read_xdr(&mut Limited::new(buf, Limits::none()));
+        match res {
+            Err(Error::Io(_)) => (),
+            _ => panic!("expected IO error got {res:?}"),
+        }
+    }
+
+    #[test]
+    pub fn arr_u8_read_with_non_zero_padding() {
+        let buf = Cursor::new(vec![2, 3, 0, 0]);
+        let res = <[u8; 1]>::read_xdr(&mut Limited::new(buf, Limits::none()));
+        match res {
+            Err(Error::NonZeroPadding) => (),
+            _ => panic!("expected NonZeroPadding got {res:?}"),
+        }
+    }
+
+    #[test]
+    pub fn arr_u8_write_without_padding() {
+        let mut buf = vec![];
+        [2u8, 2, 2, 2]
+            .write_xdr(&mut Limited::new(Cursor::new(&mut buf), Limits::none()))
+            .unwrap();
+        assert_eq!(buf, vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    pub fn arr_u8_write_with_padding() {
+        let mut buf = vec![];
+        [2u8]
+            .write_xdr(&mut Limited::new(Cursor::new(&mut buf), Limits::none()))
+            .unwrap();
+        assert_eq!(buf, vec![2, 0, 0, 0]);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn into_option_none() {
+        let v: VecM<u32, 1> = vec![].try_into().unwrap();
+        assert_eq!(v.into_option(), None);
+    }
+
+    #[test]
+    fn into_option_some() {
+        let v: VecM<_, 1> = vec![1].try_into().unwrap();
+        assert_eq!(v.into_option(), Some(1));
+    }
+
+    #[test]
+    fn to_option_none() {
+        let v: VecM<u32, 1> = vec![].try_into().unwrap();
+        assert_eq!(v.to_option(), None);
+    }
+
+    #[test]
+    fn to_option_some() {
+        let v: VecM<_, 1> = vec![1].try_into().unwrap();
+        assert_eq!(v.to_option(), Some(1));
+    }
+
+    #[test]
+    fn depth_limited_read_write_under_the_limit_success() {
+        let a: Option<Option<Option<u32>>> = Some(Some(Some(5)));
+        let mut buf = Limited::new(Vec::new(), Limits::depth(4));
+        a.write_xdr(&mut buf).unwrap();
+
+        let mut dlr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::depth(4));
+        let a_back: Option<Option<Option<u32>>> = ReadXdr::read_xdr(&mut dlr).unwrap();
+        assert_eq!(a, a_back);
+    }
+
+    #[test]
+    fn write_over_depth_limit_fail() {
+        let a: Option<Option<Option<u32>>> = Some(Some(Some(5)));
+        let mut buf = Limited::new(Vec::new(), Limits::depth(3));
+        let res = a.write_xdr(&mut buf);
+        match res {
+            Err(Error::DepthLimitExceeded) => (),
+            _ => panic!("expected DepthLimitExceeded got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn read_over_depth_limit_fail() {
+        let read_limits = Limits::depth(3);
+        let write_limits = Limits::depth(5);
+        let a: Option<Option<Option<u32>>> = Some(Some(Some(5)));
+        let mut buf = Limited::new(Vec::new(), write_limits);
+        a.write_xdr(&mut buf).unwrap();
+
+        let mut dlr = Limited::new(Cursor::new(buf.inner.as_slice()), read_limits);
+        let res: Result<Option<Option<Option<u32>>>> = ReadXdr::read_xdr(&mut dlr);
+        match res {
+            Err(Error::DepthLimitExceeded) => (),
+            _ => panic!("expected DepthLimitExceeded got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn length_limited_read_write_i32() {
+        // Exact limit, success
+        let v = 123i32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(4));
+        let v_back: i32 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = 123i32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(5));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(5));
+        let v_back: i32 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = 123i32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(3));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = 123i32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(3));
+        assert_eq!(
+            <i32 as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_u32() {
+        // Exact limit, success
+        let v = 123u32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(4));
+        let v_back: u32 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = 123u32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(5));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(5));
+        let v_back: u32 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = 123u32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(3));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = 123u32;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(3));
+        assert_eq!(
+            <u32 as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_i64() {
+        // Exact limit, success
+        let v = 123i64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(8));
+        let v_back: i64 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = 123i64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(9));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(9));
+        let v_back: i64 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = 123i64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(7));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = 123i64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(7));
+        assert_eq!(
+            <i64 as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_u64() {
+        // Exact limit, success
+        let v = 123u64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(8));
+        let v_back: u64 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = 123u64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(9));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(9));
+        let v_back: u64 = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = 123u64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(7));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = 123u64;
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(7));
+        assert_eq!(
+            <u64 as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_bool() {
+        // Exact limit, success
+        let v = true;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(4));
+        let v_back: bool = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = true;
+        let mut buf = Limited::new(Vec::new(), Limits::len(5));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(5));
+        let v_back: bool = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = true;
+        let mut buf = Limited::new(Vec::new(), Limits::len(3));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = true;
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(3));
+        assert_eq!(
+            <bool as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_option() {
+        // Exact limit, success
+        let v = Some(true);
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(8));
+        let v_back: Option<bool> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = Some(true);
+        let mut buf = Limited::new(Vec::new(), Limits::len(9));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(9));
+        let v_back: Option<bool> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = Some(true);
+        let mut buf = Limited::new(Vec::new(), Limits::len(7));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = Some(true);
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(7));
+        assert_eq!(
+            <Option<bool> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_array_u8() {
+        // Exact limit, success
+        let v = [1u8, 2, 3];
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(4));
+        let v_back: [u8; 3] = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = [1u8, 2, 3];
+        let mut buf = Limited::new(Vec::new(), Limits::len(5));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(5));
+        let v_back: [u8; 3] = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = [1u8, 2, 3];
+        let mut buf = Limited::new(Vec::new(), Limits::len(3));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = [1u8, 2, 3];
+        let mut buf = Limited::new(Vec::new(), Limits::len(4));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(3));
+        assert_eq!(
+            <[u8; 3] as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_array_type() {
+        // Exact limit, success
+        let v = [true, false, true];
+        let mut buf = Limited::new(Vec::new(), Limits::len(12));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(12));
+        let v_back: [bool; 3] = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = [true, false, true];
+        let mut buf = Limited::new(Vec::new(), Limits::len(13));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(13));
+        let v_back: [bool; 3] = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = [true, false, true];
+        let mut buf = Limited::new(Vec::new(), Limits::len(11));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = [true, false, true];
+        let mut buf = Limited::new(Vec::new(), Limits::len(12));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(11));
+        assert_eq!(
+            <[bool; 3] as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_vec() {
+        // Exact limit, success
+        let v = VecM::<i32, 3>::try_from([1i32, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(16));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(16));
+        let v_back: VecM<i32, 3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = VecM::<i32, 3>::try_from([1i32, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(17));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(17));
+        let v_back: VecM<i32, 3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = VecM::<i32, 3>::try_from([1i32, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(15));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = VecM::<i32, 3>::try_from([1i32, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(16));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(15));
+        assert_eq!(
+            <VecM<i32, 3> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_bytes() {
+        // Exact limit, success
+        let v = BytesM::<3>::try_from([1u8, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(8));
+        let v_back: BytesM<3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = BytesM::<3>::try_from([1u8, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(9));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(9));
+        let v_back: BytesM<3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = BytesM::<3>::try_from([1u8, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(7));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = BytesM::<3>::try_from([1u8, 2, 3]).unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(7));
+        assert_eq!(
+            <BytesM<3> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn length_limited_read_write_string() {
+        // Exact limit, success
+        let v = StringM::<3>::try_from("123").unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(8));
+        let v_back: StringM<3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        assert_eq!(v, v_back);
+
+        // Over limit, success
+        let v = StringM::<3>::try_from("123").unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(9));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(9));
+        let v_back: StringM<3> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(buf.limits.len, 1);
+        assert_eq!(v, v_back);
+
+        // Write under limit, failure
+        let v = StringM::<3>::try_from("123").unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(7));
+        assert_eq!(v.write_xdr(&mut buf), Err(Error::LengthLimitExceeded));
+
+        // Read under limit, failure
+        let v = StringM::<3>::try_from("123").unwrap();
+        let mut buf = Limited::new(Vec::new(), Limits::len(8));
+        v.write_xdr(&mut buf).unwrap();
+        assert_eq!(buf.limits.len, 0);
+        let mut lr = Limited::new(Cursor::new(buf.inner.as_slice()), Limits::len(7));
+        assert_eq!(
+            <StringM<3> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::LengthLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn alloc_limited_read_bytes() {
+        let v = BytesM::<8>::try_from([1u8, 2, 3]).unwrap();
+        let buf = v.to_xdr(Limits::none()).unwrap();
+
+        // Exact limit, success
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::alloc(3));
+        let v_back: BytesM<8> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(lr.limits.alloc, 0);
+        assert_eq!(v, v_back);
+
+        // Under limit, failure
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::alloc(2));
+        assert_eq!(
+            <BytesM<8> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::AllocLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn alloc_limited_read_length_prefix_larger_than_data() {
+        // A length prefix of 0x7fffffff followed by no data fails on the
+        // allocation limit before any allocation is made.
+        let buf = [0x7fu8, 0xff, 0xff, 0xff];
+        let mut lr = Limited::new(Cursor::new(&buf[..]), Limits::alloc(1024));
+        assert_eq!(
+            <BytesM as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::AllocLimitExceeded)
+        );
+        let mut lr = Limited::new(Cursor::new(&buf[..]), Limits::alloc(1024));
+        assert_eq!(
+            <VecM<u64> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::AllocLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn alloc_limited_read_vec_and_box() {
+        let v: VecM<Option<Box<u64>>> = vec![Some(Box::new(1)), None].try_into().unwrap();
+        let buf = v.to_xdr(Limits::none()).unwrap();
+        let len = 2 * core::mem::size_of::<Option<Box<u64>>>() + core::mem::size_of::<u64>();
+
+        // Exact limit, success
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::alloc(len));
+        let v_back: VecM<Option<Box<u64>>> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(lr.limits.alloc, 0);
+        assert_eq!(v, v_back);
+
+        // Under limit, failure
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::alloc(len - 1));
+        assert_eq!(
+            <VecM<Option<Box<u64>>> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::AllocLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn element_limited_read_vec() {
+        let v: VecM<u32> = vec![1, 2, 3].try_into().unwrap();
+        let buf = v.to_xdr(Limits::none()).unwrap();
+
+        // Exact limit, success
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::elements(3));
+        let v_back: VecM<u32> = ReadXdr::read_xdr(&mut lr).unwrap();
+        assert_eq!(v, v_back);
+
+        // Under limit, failure
+        let mut lr = Limited::new(Cursor::new(buf.as_slice()), Limits::elements(2));
+        assert_eq!(
+            <VecM<u32> as ReadXdr>::read_xdr(&mut lr),
+            Err(Error::ElementLimitExceeded)
+        );
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod test {
+    use super::VecM;
+
+    #[test]
+    fn to_option_none() {
+        let v: VecM<u32, 1> = (&[]).try_into().unwrap();
+        assert_eq!(v.to_option(), None);
+    }
+
+    #[test]
+    fn to_option_some() {
+        let v: VecM<_, 1> = (&[1]).try_into().unwrap();
+        assert_eq!(v.to_option(), Some(1));
+    }
+}
+
@@ -0,0 +1,96 @@
+// Schema ------------------------------------------------------------------------
+
+/// Descriptions of the XDR types, for walking the structure of the types at
+/// runtime.
+///
+/// The schema of each type is accessed with [`TypeVariant::schema`].
+pub mod schema {
+    use super::TypeVariant;
+
+    /// `Schema` describes an XDR type.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct Schema {
+        /// The name of the type, the same as [`TypeVariant::name`].
+        pub name: &'static str,
+        /// The XDR file the type is defined in.
+        pub file: &'static str,
+        pub kind: Kind,
+    }
+
+    /// `Kind` is the kind of definition of an XDR type.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum Kind {
+        Struct(&'static [Field]),
+        Enum(&'static [EnumCase]),
+        Union(Union),
+        Typedef(TypeRef),
+    }
+
+    /// `Field` is a field of a struct.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct Field {
+        pub name: &'static str,
+        pub ty: TypeRef,
+    }
+
+    /// `EnumCase` is a named value of an enum.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct EnumCase {
+        pub name: &'static str,
+        pub value: i32,
+    }
+
+    /// `Union` is the discriminant and arms of a union.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct Union {
+        /// The type of the discriminant, either an integer or an enum.
+        pub discriminant: TypeRef,
+        pub arms: &'static [UnionArm],
+    }
+
+    /// `UnionArm` is an arm of a union, selected by a value of the
+    /// discriminant.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub struct UnionArm {
+        /// The name of the arm, which for unions with an enum discriminant is
+        /// the name of the enum case.
+        pub name: &'static str,
+        /// The value of the discriminant that selects the arm.
+        pub value: i32,
+        /// The type of the value of the arm, or `None` if the arm is void.
+        pub ty: Option<TypeRef>,
+    }
+
+    /// `TypeRef` is a reference to a type, as used by a field, union arm or
+    /// typedef.
+    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    pub enum TypeRef {
+        Int32,
+        Uint32,
+        Int64,
+        Uint64,
+        Bool,
+        /// A type defined in the XDR.
+        Type(TypeVariant),
+        /// An optional value of the type.
+        Optional(&'static TypeRef),
+        /// Opaque data of the fixed length.
+        FixedOpaque(u32),
+        /// Opaque data of variable length, up to the maximum length.
+        VarOpaque(u32),
+        /// A string of variable length, up to the maximum length.
+        String(u32),
+        /// An array of the fixed length.
+        FixedArray(&'static TypeRef, u32),
+        /// An array of variable length, up to the maximum length.
+        VarArray(&'static TypeRef, u32),
+    }
+
+    impl TypeVariant {
+        /// Returns the schema of the type.
+        #[must_use]
+        pub fn schema(&self) -> &'static Schema {
+            &SCHEMAS[*self as usize]
+        }
+    }
+
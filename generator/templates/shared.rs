@@ -0,0 +1,168 @@
+/// Shared forms of the types that contain variable length opaque or string
+/// data, decoded with [`ReadXdrShared`].
+///
+/// Each type in this module holds its opaque and string data as a
+/// [`bytes::Bytes`] taken from the buffer it was decoded from with
+/// [`bytes::Buf::copy_to_bytes`], so that data decoded from a `Bytes` shares
+/// its storage without being copied. Each type converts into the owned type of
+/// the same name with [`ReadXdrShared::into_owned`].
+#[cfg(feature = "bytes")]
+pub mod shared {
+    #[allow(clippy::wildcard_imports)]
+    use super::*;
+
+    /// A variable length opaque held as a `Bytes`.
+    #[derive(Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct BytesM<const MAX: u32 = { u32::MAX }>(bytes::Bytes);
+
+    impl<const MAX: u32> core::fmt::Display for BytesM<MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for b in &self.0 {
+                write!(f, "{b:02x}")?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> core::fmt::Debug for BytesM<MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "BytesM(")?;
+            for b in &self.0 {
+                write!(f, "{b:02x}")?;
+            }
+            write!(f, ")")?;
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> Deref for BytesM<MAX> {
+        type Target = [u8];
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<const MAX: u32> AsRef<[u8]> for BytesM<MAX> {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl<const MAX: u32> BytesM<MAX> {
+        #[must_use]
+        pub fn as_bytes(&self) -> &bytes::Bytes {
+            &self.0
+        }
+    }
+
+    impl<const MAX: u32> TryFrom<bytes::Bytes> for BytesM<MAX> {
+        type Error = Error;
+
+        fn try_from(v: bytes::Bytes) -> Result<Self> {
+            let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            if len <= MAX {
+                Ok(BytesM(v))
+            } else {
+                Err(Error::LengthExceedsMax)
+            }
+        }
+    }
+
+    impl<const MAX: u32> From<BytesM<MAX>> for bytes::Bytes {
+        fn from(v: BytesM<MAX>) -> Self {
+            v.0
+        }
+    }
+
+    impl<const MAX: u32> ReadXdrShared for BytesM<MAX> {
+        type Owned = super::BytesM<MAX>;
+
+        fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+            r.with_limited_depth(|r| Ok(BytesM(r.read_shared_var(MAX)?)))
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            super::BytesM(self.0.to_vec())
+        }
+    }
+
+    /// A string held as a `Bytes`.
+    #[derive(Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct StringM<const MAX: u32 = { u32::MAX }>(bytes::Bytes);
+
+    impl<const MAX: u32> core::fmt::Display for StringM<MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            for b in escape_bytes::Escape::new(&self.0) {
+                write!(f, "{}", b as char)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> core::fmt::Debug for StringM<MAX> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "StringM(")?;
+            for b in escape_bytes::Escape::new(&self.0) {
+                write!(f, "{}", b as char)?;
+            }
+            write!(f, ")")?;
+            Ok(())
+        }
+    }
+
+    impl<const MAX: u32> Deref for StringM<MAX> {
+        type Target = [u8];
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<const MAX: u32> AsRef<[u8]> for StringM<MAX> {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl<const MAX: u32> StringM<MAX> {
+        #[must_use]
+        pub fn as_bytes(&self) -> &bytes::Bytes {
+            &self.0
+        }
+
+        pub fn to_utf8_str(&self) -> Result<&str> {
+            Ok(core::str::from_utf8(&self.0)?)
+        }
+    }
+
+    impl<const MAX: u32> TryFrom<bytes::Bytes> for StringM<MAX> {
+        type Error = Error;
+
+        fn try_from(v: bytes::Bytes) -> Result<Self> {
+            let len: u32 = v.len().try_into().map_err(|_| Error::LengthExceedsMax)?;
+            if len <= MAX {
+                Ok(StringM(v))
+            } else {
+                Err(Error::LengthExceedsMax)
+            }
+        }
+    }
+
+    impl<const MAX: u32> From<StringM<MAX>> for bytes::Bytes {
+        fn from(v: StringM<MAX>) -> Self {
+            v.0
+        }
+    }
+
+    impl<const MAX: u32> ReadXdrShared for StringM<MAX> {
+        type Owned = super::StringM<MAX>;
+
+        fn read_xdr_shared<B: bytes::Buf>(r: &mut Limited<bytes::buf::Reader<B>>) -> Result<Self> {
+            r.with_limited_depth(|r| Ok(StringM(r.read_shared_var(MAX)?)))
+        }
+
+        fn into_owned(self) -> Self::Owned {
+            super::StringM(self.0.to_vec())
+        }
+    }
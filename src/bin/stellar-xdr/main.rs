@@ -6,7 +6,12 @@ fn main() {
     if let Err(e) = cli::run(env::args_os()) {
         match e {
             cli::Error::Clap(e) => e.exit(),
-            cli::Error::Guess(_) | cli::Error::Decode(_) | cli::Error::Encode(_) => {
+            cli::Error::Guess(_)
+            | cli::Error::Decode(_)
+            | cli::Error::Encode(_)
+            | cli::Error::Query(_)
+            | cli::Error::Diff(_)
+            | cli::Error::Bucket(_) => {
                 Error::raw(clap::error::ErrorKind::ValueValidation, e).exit()
             }
         }
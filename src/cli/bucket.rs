@@ -0,0 +1,78 @@
+mod info;
+mod list;
+mod stats;
+
+use std::{fs::File, path::PathBuf};
+
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::cli::Channel;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown ledger entry type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("invalid ledger key {0}: {1}")]
+    InvalidKey(String, serde_json::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrCurr(#[from] crate::curr::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrNext(#[from] crate::next::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(#[from] std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+}
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    #[command(subcommand)]
+    sub: Sub,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum Sub {
+    /// View the metadata of bucket files
+    Info(info::Cmd),
+    /// List the entries of bucket files
+    List(list::Cmd),
+    /// Count the entries of bucket files by ledger entry type
+    Stats(stats::Cmd),
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match &self.sub {
+            Sub::Info(c) => c.run(channel),
+            Sub::List(c) => c.run(channel),
+            Sub::Stats(c) => c.run(channel),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    JsonFormatted,
+}
+
+impl OutputFormat {
+    fn out(self, v: &impl Serialize) -> Result<(), Error> {
+        match self {
+            OutputFormat::Json => println!("{}", serde_json::to_string(v)?),
+            OutputFormat::JsonFormatted => println!("{}", serde_json::to_string_pretty(v)?),
+        }
+        Ok(())
+    }
+}
+
+/// Open the bucket files, in the order given.
+fn files(paths: &[PathBuf]) -> Result<Vec<(&PathBuf, File)>, Error> {
+    paths
+        .iter()
+        .map(|path| Ok((path, File::open(path)?)))
+        .collect()
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::cli::{
+    bucket::{files, Error, OutputFormat},
+    Channel,
+};
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// Bucket files to read
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    // Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct Info<'a, M> {
+    file: &'a PathBuf,
+    protocol_version: u32,
+    metadata: &'a M,
+}
+
+macro_rules! run_x {
+    ($f:ident, $m:ident) => {
+        fn $f(&self) -> Result<(), Error> {
+            for (file, f) in files(&self.files)? {
+                let bucket =
+                    crate::$m::Bucket::new_with_error_context(f, crate::$m::Limits::none(), true)?;
+                self.output.out(&Info {
+                    file,
+                    protocol_version: bucket.protocol_version(),
+                    metadata: bucket.metadata(),
+                })?;
+            }
+            Ok(())
+        }
+    };
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match channel {
+            Channel::Curr => self.run_curr()?,
+            Channel::Next => self.run_next()?,
+        }
+        Ok(())
+    }
+
+    run_x!(run_curr, curr);
+    run_x!(run_next, next);
+}
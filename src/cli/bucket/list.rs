@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::cli::{
+    bucket::{files, Error, OutputFormat},
+    Channel,
+};
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// Bucket files to read
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Only list entries for ledger entries of the types, e.g. ContractData
+    #[arg(long)]
+    r#type: Vec<String>,
+
+    /// Only list entries for the ledger entry with the key, as JSON
+    #[arg(long)]
+    key: Option<String>,
+
+    // Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+macro_rules! run_x {
+    ($f:ident, $m:ident) => {
+        fn $f(&self) -> Result<(), Error> {
+            let types = self
+                .r#type
+                .iter()
+                .map(|t| {
+                    crate::$m::LedgerEntryType::VARIANTS
+                        .into_iter()
+                        .find(|v| v.name() == t)
+                        .ok_or_else(|| {
+                            Error::UnknownType(t.clone(), &crate::$m::LedgerEntryType::VARIANTS_STR)
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let key = self
+                .key
+                .as_ref()
+                .map(|k| {
+                    serde_json::from_str::<crate::$m::LedgerKey>(k)
+                        .map_err(|e| Error::InvalidKey(k.clone(), e))
+                })
+                .transpose()?;
+            for (_, f) in files(&self.files)? {
+                let bucket =
+                    crate::$m::Bucket::new_with_error_context(f, crate::$m::Limits::none(), true)?;
+                let entries: Box<dyn Iterator<Item = _>> = match &key {
+                    Some(key) => Box::new(bucket.entries_with_key(key.clone())),
+                    None => Box::new(bucket),
+                };
+                for entry in entries {
+                    let entry = entry?;
+                    if !types.is_empty()
+                        && !entry
+                            .ledger_entry_type()
+                            .is_some_and(|t| types.contains(&t))
+                    {
+                        continue;
+                    }
+                    self.output.out(&entry)?;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match channel {
+            Channel::Curr => self.run_curr()?,
+            Channel::Next => self.run_next()?,
+        }
+        Ok(())
+    }
+
+    run_x!(run_curr, curr);
+    run_x!(run_next, next);
+}
@@ -0,0 +1,82 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::cli::{
+    bucket::{files, Error, OutputFormat},
+    Channel,
+};
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// Bucket files to read
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    // Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+/// The number of entries in a bucket, by the type of the bucket entry and the
+/// type of the ledger entry.
+#[derive(Serialize)]
+struct Stats<'a> {
+    file: &'a PathBuf,
+    protocol_version: u32,
+    entries: usize,
+    live: BTreeMap<&'static str, usize>,
+    init: BTreeMap<&'static str, usize>,
+    dead: BTreeMap<&'static str, usize>,
+}
+
+macro_rules! run_x {
+    ($f:ident, $m:ident) => {
+        fn $f(&self) -> Result<(), Error> {
+            use crate::$m::BucketEntry;
+            for (file, f) in files(&self.files)? {
+                let bucket =
+                    crate::$m::Bucket::new_with_error_context(f, crate::$m::Limits::none(), true)?;
+                let mut stats = Stats {
+                    file,
+                    protocol_version: bucket.protocol_version(),
+                    entries: 0,
+                    live: BTreeMap::new(),
+                    init: BTreeMap::new(),
+                    dead: BTreeMap::new(),
+                };
+                for entry in bucket {
+                    let entry = entry?;
+                    let counts = match entry {
+                        BucketEntry::Liveentry(_) => &mut stats.live,
+                        BucketEntry::Initentry(_) => &mut stats.init,
+                        BucketEntry::Deadentry(_) => &mut stats.dead,
+                        // The bucket does not return metadata entries.
+                        BucketEntry::Metaentry(_) => continue,
+                    };
+                    if let Some(t) = entry.ledger_entry_type() {
+                        *counts.entry(t.name()).or_default() += 1;
+                    }
+                    stats.entries += 1;
+                }
+                self.output.out(&stats)?;
+            }
+            Ok(())
+        }
+    };
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match channel {
+            Channel::Curr => self.run_curr()?,
+            Channel::Next => self.run_next()?,
+        }
+        Ok(())
+    }
+
+    run_x!(run_curr, curr);
+    run_x!(run_next, next);
+}
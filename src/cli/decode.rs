@@ -79,7 +79,8 @@ macro_rules! run_x {
                 Error::UnknownType(self.r#type.clone(), &crate::$m::TypeVariant::VARIANTS_STR)
             })?;
             for f in &mut files {
-                let mut f = crate::$m::Limited::new(f, crate::$m::Limits::none());
+                let mut f =
+                    crate::$m::Limited::new(f, crate::$m::Limits::none()).with_error_context();
                 match self.input {
                     InputFormat::Single => {
                         let t = crate::$m::Type::read_xdr_to_end(r#type, &mut f)?;
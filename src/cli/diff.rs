@@ -0,0 +1,162 @@
+use std::{fs::File, path::PathBuf, str::FromStr};
+
+use clap::{Args, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::cli::Channel;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrCurr(#[from] crate::curr::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrNext(#[from] crate::next::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(#[from] std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+}
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// File containing the first value
+    #[arg()]
+    a: PathBuf,
+
+    /// File containing the second value
+    #[arg()]
+    b: PathBuf,
+
+    /// XDR type of the values
+    #[arg(long)]
+    r#type: String,
+
+    // Input format of the XDR
+    #[arg(long, value_enum, default_value_t)]
+    input: InputFormat,
+
+    // Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Single,
+    #[default]
+    SingleBase64,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    JsonFormatted,
+}
+
+macro_rules! run_x {
+    ($f:ident, $m:ident) => {
+        fn $f(&self) -> Result<(), Error> {
+            use crate::$m::Diff;
+            let r#type = crate::$m::TypeVariant::from_str(&self.r#type).map_err(|_| {
+                Error::UnknownType(self.r#type.clone(), &crate::$m::TypeVariant::VARIANTS_STR)
+            })?;
+            let read = |path: &PathBuf| -> Result<crate::$m::Type, Error> {
+                let f = File::open(path)?;
+                let mut f =
+                    crate::$m::Limited::new(f, crate::$m::Limits::none()).with_error_context();
+                Ok(match self.input {
+                    InputFormat::Single => crate::$m::Type::read_xdr_to_end(r#type, &mut f)?,
+                    InputFormat::SingleBase64 => {
+                        crate::$m::Type::read_xdr_base64_to_end(r#type, &mut f)?
+                    }
+                })
+            };
+            let (a, b) = (read(&self.a)?, read(&self.b)?);
+            let mut differences = Vec::new();
+            for d in a.diff(&b) {
+                let query = crate::$m::Query::from(d.path.as_slice());
+                let value = |t: &crate::$m::Type| -> Result<Option<Value>, Error> {
+                    query
+                        .select_types(t)
+                        .first()
+                        .map(serde_json::to_value)
+                        .transpose()
+                        .map_err(Error::from)
+                };
+                let (from, to) = match d.kind {
+                    crate::$m::DifferenceKind::Added => (None, value(&b)?),
+                    crate::$m::DifferenceKind::Removed => (value(&a)?, None),
+                    crate::$m::DifferenceKind::Changed
+                    | crate::$m::DifferenceKind::ArmChanged { .. } => (value(&a)?, value(&b)?),
+                };
+                let kind = match d.kind {
+                    crate::$m::DifferenceKind::Changed => "changed",
+                    crate::$m::DifferenceKind::ArmChanged { .. } => "arm_changed",
+                    crate::$m::DifferenceKind::Added => "added",
+                    crate::$m::DifferenceKind::Removed => "removed",
+                };
+                differences.push(Difference {
+                    text: d.to_string(),
+                    path: query.to_string(),
+                    kind,
+                    from,
+                    to,
+                });
+            }
+            self.out(&differences)
+        }
+    };
+}
+
+/// A difference, with the values that differ if they are XDR types.
+struct Difference {
+    text: String,
+    path: String,
+    kind: &'static str,
+    from: Option<Value>,
+    to: Option<Value>,
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match channel {
+            Channel::Curr => self.run_curr()?,
+            Channel::Next => self.run_next()?,
+        }
+        Ok(())
+    }
+
+    run_x!(run_curr, curr);
+    run_x!(run_next, next);
+
+    fn out(&self, differences: &[Difference]) -> Result<(), Error> {
+        match self.output {
+            OutputFormat::Text => {
+                for d in differences {
+                    match (&d.from, &d.to) {
+                        (Some(from), Some(to)) => println!("{}: {from} -> {to}", d.text),
+                        (Some(v), None) | (None, Some(v)) => println!("{}: {v}", d.text),
+                        (None, None) => println!("{}", d.text),
+                    }
+                }
+            }
+            OutputFormat::Json | OutputFormat::JsonFormatted => {
+                let v = differences
+                    .iter()
+                    .map(|d| json!({ "path": d.path, "kind": d.kind, "from": d.from, "to": d.to }))
+                    .collect::<Vec<_>>();
+                if self.output == OutputFormat::Json {
+                    println!("{}", serde_json::to_string(&v)?);
+                } else {
+                    println!("{}", serde_json::to_string_pretty(&v)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
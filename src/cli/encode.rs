@@ -37,7 +37,10 @@ impl From<crate::curr::Error> for Error {
             | crate::curr::Error::InvalidHex
             | crate::curr::Error::Io(_)
             | crate::curr::Error::DepthLimitExceeded
-            | crate::curr::Error::LengthLimitExceeded => Error::WriteXdrCurr(e),
+            | crate::curr::Error::LengthLimitExceeded
+            | crate::curr::Error::AllocLimitExceeded
+            | crate::curr::Error::ElementLimitExceeded
+            | crate::curr::Error::Context(_) => Error::WriteXdrCurr(e),
             crate::curr::Error::Json(_) => Error::ReadJsonCurr(e),
         }
     }
@@ -55,7 +58,10 @@ impl From<crate::next::Error> for Error {
             | crate::next::Error::InvalidHex
             | crate::next::Error::Io(_)
             | crate::next::Error::DepthLimitExceeded
-            | crate::next::Error::LengthLimitExceeded => Error::WriteXdrNext(e),
+            | crate::next::Error::LengthLimitExceeded
+            | crate::next::Error::AllocLimitExceeded
+            | crate::next::Error::ElementLimitExceeded
+            | crate::next::Error::Context(_) => Error::WriteXdrNext(e),
             crate::next::Error::Json(_) => Error::ReadJsonNext(e),
         }
     }
@@ -96,6 +102,7 @@ impl Default for InputFormat {
 pub enum OutputFormat {
     Single,
     SingleBase64,
+    StreamFramed,
 }
 
 impl Default for OutputFormat {
@@ -121,6 +128,9 @@ macro_rules! run_x {
                         match self.output {
                             OutputFormat::Single => stdout().write_all(&t.to_xdr(l)?)?,
                             OutputFormat::SingleBase64 => println!("{}", t.to_xdr_base64(l)?),
+                            OutputFormat::StreamFramed => {
+                                t.write_xdr_framed(&mut crate::$m::Limited::new(stdout(), l))?;
+                            }
                         }
                     }
                 };
@@ -74,16 +74,16 @@ macro_rules! run_x {
             'variants: for v in crate::$m::TypeVariant::VARIANTS {
                 f.inner.reset();
                 let count: usize = match self.input {
-                    InputFormat::Single => crate::$m::Type::read_xdr_to_end(v, &mut f)
+                    InputFormat::Single => crate::$m::Type::skip_xdr_to_end(v, &mut f)
                         .ok()
-                        .map(|_| 1)
+                        .map(|()| 1)
                         .unwrap_or_default(),
-                    InputFormat::SingleBase64 => crate::$m::Type::read_xdr_base64_to_end(v, &mut f)
+                    InputFormat::SingleBase64 => crate::$m::Type::skip_xdr_base64_to_end(v, &mut f)
                         .ok()
-                        .map(|_| 1)
+                        .map(|()| 1)
                         .unwrap_or_default(),
                     InputFormat::Stream => {
-                        let iter = crate::$m::Type::read_xdr_iter(v, &mut f).take(self.certainty);
+                        let iter = crate::$m::Type::skip_xdr_iter(v, &mut f).take(self.certainty);
                         let mut count = 0;
                         for v in iter {
                             match v {
@@ -95,7 +95,7 @@ macro_rules! run_x {
                     }
                     InputFormat::StreamBase64 => {
                         let iter =
-                            crate::$m::Type::read_xdr_base64_iter(v, &mut f).take(self.certainty);
+                            crate::$m::Type::skip_xdr_base64_iter(v, &mut f).take(self.certainty);
                         let mut count = 0;
                         for v in iter {
                             match v {
@@ -107,7 +107,7 @@ macro_rules! run_x {
                     }
                     InputFormat::StreamFramed => {
                         let iter =
-                            crate::$m::Type::read_xdr_framed_iter(v, &mut f).take(self.certainty);
+                            crate::$m::Type::skip_xdr_framed_iter(v, &mut f).take(self.certainty);
                         let mut count = 0;
                         for v in iter {
                             match v {
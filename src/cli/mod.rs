@@ -1,6 +1,9 @@
+mod bucket;
 mod decode;
+mod diff;
 mod encode;
 mod guess;
+mod query;
 mod types;
 mod version;
 
@@ -38,6 +41,9 @@ impl Root {
             Cmd::Guess(c) => c.run(&self.channel)?,
             Cmd::Decode(c) => c.run(&self.channel)?,
             Cmd::Encode(c) => c.run(&self.channel)?,
+            Cmd::Query(c) => c.run(&self.channel)?,
+            Cmd::Diff(c) => c.run(&self.channel)?,
+            Cmd::Bucket(c) => c.run(&self.channel)?,
             Cmd::Version => version::Cmd::run(),
         }
         Ok(())
@@ -68,6 +74,12 @@ pub enum Cmd {
     Decode(decode::Cmd),
     /// Encode XDR
     Encode(encode::Cmd),
+    /// Select values from decoded XDR
+    Query(query::Cmd),
+    /// Compare XDR values
+    Diff(diff::Cmd),
+    /// Read bucket files
+    Bucket(bucket::Cmd),
     /// Print version information
     Version,
 }
@@ -83,6 +95,12 @@ pub enum Error {
     Decode(#[from] decode::Error),
     #[error("error reading file: {0}")]
     Encode(#[from] encode::Error),
+    #[error("error querying file: {0}")]
+    Query(#[from] query::Error),
+    #[error("error diffing files: {0}")]
+    Diff(#[from] diff::Error),
+    #[error("error reading bucket: {0}")]
+    Bucket(#[from] bucket::Error),
 }
 
 /// Run the CLI with the given args.
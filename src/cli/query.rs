@@ -0,0 +1,214 @@
+use std::{
+    fs::File,
+    io::{stdin, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::cli::{
+    decode::{InputFormat, OutputFormat},
+    Channel,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("unknown type {0}, choose one of {1:?}")]
+    UnknownType(String, &'static [&'static str]),
+    #[error("invalid path {0}")]
+    InvalidPath(String),
+    #[error("path {0} does not match any value in type {1}")]
+    UnknownPath(String, &'static str),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrCurr(#[from] crate::curr::Error),
+    #[error("error decoding XDR: {0}")]
+    ReadXdrNext(#[from] crate::next::Error),
+    #[error("error reading file: {0}")]
+    ReadFile(#[from] std::io::Error),
+    #[error("error generating JSON: {0}")]
+    GenerateJson(#[from] serde_json::Error),
+}
+
+#[derive(Args, Debug, Clone)]
+#[command()]
+pub struct Cmd {
+    /// Files to decode, or stdin if omitted
+    #[arg()]
+    files: Vec<PathBuf>,
+
+    /// XDR type to decode
+    #[arg(long)]
+    r#type: String,
+
+    /// Path of the values to select, e.g. tx.operations[*].body.payment.amount
+    #[arg(long)]
+    path: String,
+
+    // Input format of the XDR
+    #[arg(long, value_enum, default_value_t)]
+    input: InputFormat,
+
+    // Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+macro_rules! run_x {
+    ($f:ident, $select_json:ident, $m:ident) => {
+        /// Selects the values at the path within the JSON form of a value.
+        fn $select_json(
+            value: serde_json::Value,
+            segments: &[crate::$m::QuerySegment],
+            values: &mut Vec<serde_json::Value>,
+        ) {
+            let Some((segment, segments)) = segments.split_first() else {
+                if !value.is_null() {
+                    values.push(value);
+                }
+                return;
+            };
+            match (segment, value) {
+                (crate::$m::QuerySegment::Name(name), serde_json::Value::Object(mut o)) => {
+                    if let Some(v) = o.remove(name) {
+                        Self::$select_json(v, segments, values);
+                    }
+                }
+                (crate::$m::QuerySegment::Index(i), serde_json::Value::Array(mut a)) => {
+                    if *i < a.len() {
+                        Self::$select_json(a.swap_remove(*i), segments, values);
+                    }
+                }
+                (crate::$m::QuerySegment::AnyIndex, serde_json::Value::Array(a)) => {
+                    for v in a {
+                        Self::$select_json(v, segments, values);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        fn $f(&self) -> Result<(), Error> {
+            let mut files = self.files()?;
+            let r#type = crate::$m::TypeVariant::from_str(&self.r#type).map_err(|_| {
+                Error::UnknownType(self.r#type.clone(), &crate::$m::TypeVariant::VARIANTS_STR)
+            })?;
+            let query = crate::$m::Query::from_str(&self.path)
+                .map_err(|_| Error::InvalidPath(self.path.clone()))?;
+            let mut types = query
+                .resolve(r#type)
+                .ok_or_else(|| Error::UnknownPath(self.path.clone(), r#type.name()))?;
+            let mut segments = query.segments().to_vec();
+            // A path that selects an array selects the elements of the array.
+            let elements = crate::$m::Query::new(
+                segments
+                    .iter()
+                    .cloned()
+                    .chain([crate::$m::QuerySegment::AnyIndex])
+                    .collect(),
+            );
+            if let Some(element_types) = elements.resolve(r#type) {
+                segments.push(crate::$m::QuerySegment::AnyIndex);
+                types = element_types;
+            }
+            // Values are selected as XDR types at the longest prefix of the path
+            // that selects XDR types. The rest of the path, which can only
+            // select numbers, strings and opaque data, is selected from the
+            // JSON form of those types.
+            let typed = types
+                .iter()
+                .rposition(|ty| {
+                    let mut ty = ty;
+                    while let crate::$m::schema::TypeRef::Optional(inner) = ty {
+                        ty = inner;
+                    }
+                    matches!(ty, crate::$m::schema::TypeRef::Type(_))
+                })
+                .unwrap_or_default();
+            let (typed, rest) = segments.split_at(typed);
+            let typed = crate::$m::Query::new(typed.to_vec());
+            let out = |t: &crate::$m::Type| -> Result<(), Error> {
+                let selected = typed.select_types(t);
+                if rest.is_empty() {
+                    return self.out(&selected);
+                }
+                for t in selected {
+                    let mut values = Vec::new();
+                    Self::$select_json(serde_json::to_value(&t)?, rest, &mut values);
+                    self.out(&values)?;
+                }
+                Ok(())
+            };
+            for f in &mut files {
+                let mut f =
+                    crate::$m::Limited::new(f, crate::$m::Limits::none()).with_error_context();
+                match self.input {
+                    InputFormat::Single => {
+                        let t = crate::$m::Type::read_xdr_to_end(r#type, &mut f)?;
+                        out(&t)?;
+                    }
+                    InputFormat::SingleBase64 => {
+                        let t = crate::$m::Type::read_xdr_base64_to_end(r#type, &mut f)?;
+                        out(&t)?;
+                    }
+                    InputFormat::Stream => {
+                        for t in crate::$m::Type::read_xdr_iter(r#type, &mut f) {
+                            out(&t?)?;
+                        }
+                    }
+                    InputFormat::StreamBase64 => {
+                        for t in crate::$m::Type::read_xdr_base64_iter(r#type, &mut f) {
+                            out(&t?)?;
+                        }
+                    }
+                    InputFormat::StreamFramed => {
+                        for t in crate::$m::Type::read_xdr_framed_iter(r#type, &mut f) {
+                            out(&t?)?;
+                        }
+                    }
+                };
+            }
+            Ok(())
+        }
+    };
+}
+
+impl Cmd {
+    pub fn run(&self, channel: &Channel) -> Result<(), Error> {
+        match channel {
+            Channel::Curr => self.run_curr()?,
+            Channel::Next => self.run_next()?,
+        }
+        Ok(())
+    }
+
+    run_x!(run_curr, select_json_curr, curr);
+    run_x!(run_next, select_json_next, next);
+
+    fn files(&self) -> Result<Vec<Box<dyn Read>>, Error> {
+        if self.files.is_empty() {
+            Ok(vec![Box::new(stdin())])
+        } else {
+            Ok(self
+                .files
+                .iter()
+                .map(File::open)
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .map(|f| -> Box<dyn Read> { Box::new(f) })
+                .collect())
+        }
+    }
+
+    /// Output each selected value, one per line.
+    fn out<T: Serialize>(&self, values: &[T]) -> Result<(), Error> {
+        for v in values {
+            match self.output {
+                OutputFormat::Json => println!("{}", serde_json::to_string(v)?),
+                OutputFormat::JsonFormatted => println!("{}", serde_json::to_string_pretty(v)?),
+            }
+        }
+        Ok(())
+    }
+}
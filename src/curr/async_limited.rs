@@ -0,0 +1,401 @@
+//# Async reading and writing of XDR values with tokio.
+
+use core::marker::PhantomData;
+use std::io;
+
+use super::{frame::write_framed, Error, Frame, Limited, Limits, ReadXdr, WriteXdr};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// `AsyncLimited` wraps an async reader or writer and provides async forms of
+/// reading and writing XDR, enforcing limits in the same way as [`Limited`].
+///
+/// Values are decoded from bytes buffered from the reader. Bytes are read from
+/// the reader until the buffered bytes contain the whole value, and any bytes
+/// following the value remain buffered for the next read.
+///
+/// A value that ends beyond the buffered bytes is decoded again from its start
+/// once more bytes are buffered. To bound the cost of that, the buffer is
+/// grown to hold the bytes the value was reading when it reached the end, and
+/// the value is not decoded again until those bytes are buffered, and the
+/// buffered bytes have doubled or the reader has no more bytes ready. A large
+/// opaque or string is therefore decoded at most a few times regardless of how
+/// few bytes each read of the reader returns. A value made of many small
+/// fields, that arrives in many small reads with the reader waiting between
+/// them, is decoded again each time the reader waits, and the cost of
+/// decoding it is quadratic in its length in the worst case.
+///
+/// Errors that occur decoding are returned with context when enabled with
+/// [`AsyncLimited::with_error_context`], the same as with
+/// [`Limited::with_error_context`], with byte offsets relative to the start of
+/// the value being read.
+pub struct AsyncLimited<L> {
+    inner: L,
+    limits: Limits,
+    error_context: bool,
+    // The buffer is zero filled beyond the bytes read, up to its length, so
+    // that the space is not filled again each time the reader is read.
+    buf: Vec<u8>,
+    filled: usize,
+    // The number of bytes buffered when a value was last decoded and ended
+    // beyond the buffered bytes, and the number of bytes the value was
+    // reading when it reached the end, or zero.
+    attempted: usize,
+    needed: usize,
+}
+
+/// `Buffered` is the reader of the bytes buffered by an [`AsyncLimited`] that
+/// values are decoded from. It records the furthest byte that a read
+/// requested, which when beyond the buffered bytes are bytes that must be
+/// buffered before the value can be decoded.
+struct Buffered<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    needed: usize,
+}
+
+// Returns true if the error is the end of the buffered bytes, with or without
+// context.
+fn is_eof(e: &Error) -> bool {
+    match e {
+        Error::Io(e) => e.kind() == io::ErrorKind::UnexpectedEof,
+        Error::Context(c) => is_eof(c.error()),
+        _ => false,
+    }
+}
+
+impl io::Read for Buffered<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.needed = core::cmp::max(self.needed, self.pos + buf.len());
+        let n = core::cmp::min(buf.len(), self.buf.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<L> AsyncLimited<L> {
+    /// Constructs a new `AsyncLimited`.
+    ///
+    /// - `inner`: The async reader or writer being limited.
+    /// - `limits`: The limits to enforce.
+    pub fn new(inner: L, limits: Limits) -> Self {
+        AsyncLimited {
+            inner,
+            limits,
+            error_context: false,
+            buf: Vec::new(),
+            filled: 0,
+            attempted: 0,
+            needed: 0,
+        }
+    }
+
+    /// Enables error context, so that errors that occur decoding are returned
+    /// as an [`Error::Context`], see [`Limited::with_error_context`].
+    #[must_use]
+    pub fn with_error_context(mut self) -> Self {
+        self.error_context = true;
+        self
+    }
+
+    /// Returns the limits remaining.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Returns the bytes that have been read from the reader but not yet
+    /// decoded.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    /// Returns the wrapped reader or writer. Any bytes that have been read
+    /// but not yet decoded are discarded.
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<R: tokio::io::AsyncRead + Unpin> AsyncLimited<R> {
+    /// The minimum number of bytes requested from the reader each time the
+    /// buffer is filled.
+    const MIN_READ_LEN: usize = 4096;
+
+    // Read more bytes from the reader onto the end of the buffered bytes,
+    // returning the number of bytes read. The buffer doubles in length when it
+    // is full, so that the cost of growing it is linear in the bytes read, and
+    // grows to hold the bytes needed by the value being decoded, so that they
+    // can be read at once.
+    fn poll_fill(
+        &mut self,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<io::Result<usize>> {
+        let len = self.buf.len();
+        if self.filled == len || self.needed > len {
+            let grown = len + core::cmp::max(len, Self::MIN_READ_LEN);
+            self.buf.resize(core::cmp::max(grown, self.needed), 0);
+        }
+        let mut rb = tokio::io::ReadBuf::new(&mut self.buf[self.filled..]);
+        let res = core::pin::Pin::new(&mut self.inner).poll_read(cx, &mut rb);
+        let n = rb.filled().len();
+        self.filled += n;
+        res.map_ok(|()| n)
+    }
+
+    // Discard the first `n` buffered bytes.
+    fn consume(&mut self, n: usize) {
+        self.buf.copy_within(n..self.filled, 0);
+        self.filled -= n;
+        self.attempted = 0;
+        self.needed = 0;
+    }
+
+    // Decode a value from the buffered bytes using `f`. If the value ends
+    // beyond the buffered bytes the bytes remain buffered, and the bytes the
+    // value needs are recorded.
+    //
+    // Limits are only consumed when a value is decoded, so that the attempts
+    // that stop at the end of the buffer are not counted against the limits.
+    fn decode_with<T, F>(&mut self, f: &F) -> Result<T, Error>
+    where
+        F: Fn(&mut Limited<Buffered<'_>>) -> Result<T, Error>,
+    {
+        let len = self.filled;
+        let buffered = Buffered {
+            buf: &self.buf[..len],
+            pos: 0,
+            needed: 0,
+        };
+        let mut r = Limited::new(buffered, self.limits.clone());
+        r.error_context = self.error_context;
+        match f(&mut r) {
+            Ok(t) => {
+                let consumed = r.inner.pos;
+                self.limits = r.limits;
+                self.consume(consumed);
+                Ok(t)
+            }
+            Err(e) if is_eof(&e) => {
+                self.attempted = len;
+                self.needed = r.inner.needed;
+                Err(e)
+            }
+            Err(e) => {
+                self.attempted = 0;
+                self.needed = 0;
+                Err(e)
+            }
+        }
+    }
+
+    // Decode a value from the buffer using `f`, reading more bytes into the
+    // buffer for as long as the buffer ends before the value does.
+    fn poll_read_with<T, F>(
+        &mut self,
+        cx: &mut core::task::Context<'_>,
+        f: F,
+    ) -> core::task::Poll<Result<T, Error>>
+    where
+        F: Fn(&mut Limited<Buffered<'_>>) -> Result<T, Error>,
+    {
+        loop {
+            let len = self.filled;
+            if len >= self.needed && len >= self.attempted.saturating_mul(2) {
+                match self.decode_with(&f) {
+                    Err(e) if is_eof(&e) => {}
+                    res => return core::task::Poll::Ready(res),
+                }
+            }
+            let ended = match self.poll_fill(cx) {
+                core::task::Poll::Ready(Ok(0)) => true,
+                core::task::Poll::Ready(Ok(_)) => continue,
+                core::task::Poll::Ready(Err(e)) => {
+                    return core::task::Poll::Ready(Err(Error::Io(e)))
+                }
+                core::task::Poll::Pending => false,
+            };
+            // The reader has no more bytes ready, or has ended, so the value
+            // is decoded from the bytes buffered if they may now contain it.
+            let len = self.filled;
+            if ended || (len >= self.needed && len > self.attempted) {
+                match self.decode_with(&f) {
+                    // The reader ended part way through the value, and the
+                    // bytes of the truncated value are consumed.
+                    Err(e) if is_eof(&e) && ended => {
+                        self.consume(len);
+                        return core::task::Poll::Ready(Err(e));
+                    }
+                    Err(e) if is_eof(&e) => {}
+                    res => return core::task::Poll::Ready(res),
+                }
+            }
+            return core::task::Poll::Pending;
+        }
+    }
+
+    /// Read the XDR and construct the type.
+    ///
+    /// Just enough bytes are decoded to construct the type. Any residual bytes
+    /// remain buffered for subsequent reads.
+    ///
+    /// ### Errors
+    ///
+    /// If the reader ends before the value, or the value is invalid or exceeds
+    /// the remaining limits.
+    pub async fn read_xdr<T: ReadXdr>(&mut self) -> Result<T, Error> {
+        core::future::poll_fn(|cx| self.poll_read_with(cx, |r| T::read_xdr(r))).await
+    }
+
+    /// Read the XDR and construct the type, and consider it an error if the
+    /// value does not completely consume the reader.
+    ///
+    /// No more bytes than the remaining length limit, plus one, are read from
+    /// the reader.
+    ///
+    /// ### Errors
+    ///
+    /// If the reader ends before the value, any bytes remain after the value,
+    /// or the value is invalid or exceeds the remaining limits.
+    pub async fn read_xdr_to_end<T: ReadXdr>(&mut self) -> Result<T, Error> {
+        core::future::poll_fn(|cx| loop {
+            if self.filled > self.limits.len {
+                return core::task::Poll::Ready(Ok(()));
+            }
+            match core::task::ready!(self.poll_fill(cx)) {
+                Ok(0) => return core::task::Poll::Ready(Ok(())),
+                Ok(_) => {}
+                Err(e) => return core::task::Poll::Ready(Err(Error::Io(e))),
+            }
+        })
+        .await?;
+        let t = self.read_xdr().await?;
+        if self.filled == 0 {
+            Ok(t)
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+
+    /// Read a record containing the XDR, that may be split across multiple
+    /// fragments, and construct the type.
+    ///
+    /// ### Errors
+    ///
+    /// If the reader ends before the record, the value does not consume
+    /// exactly the record, or the value is invalid or exceeds the remaining
+    /// limits.
+    pub async fn read_xdr_framed<T: ReadXdr>(&mut self) -> Result<T, Error> {
+        let Frame(t) = self.read_xdr::<Frame<T>>().await?;
+        Ok(t)
+    }
+
+    /// Create a stream that reads the reader as a sequence of values.
+    ///
+    /// The stream ends when the reader ends between values. If the reader
+    /// ends part way through a value, or a value is invalid, the stream yields
+    /// the error and then ends.
+    pub fn read_xdr_stream<S: ReadXdr>(&mut self) -> ReadXdrStream<'_, R, S> {
+        ReadXdrStream {
+            reader: self,
+            done: false,
+            _s: PhantomData,
+        }
+    }
+
+    /// Create a stream that reads the reader as a sequence of records, each
+    /// containing a value, as written by [`FramedWriter`].
+    pub fn read_xdr_framed_stream<T: ReadXdr>(&mut self) -> ReadXdrStream<'_, R, Frame<T>> {
+        self.read_xdr_stream()
+    }
+}
+
+impl<W: tokio::io::AsyncWrite + Unpin> AsyncLimited<W> {
+    /// Write the XDR of the value.
+    ///
+    /// The XDR is encoded in full before any of it is written to the writer,
+    /// so that a value that errors, or exceeds the limits, writes nothing.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be encoded, exceeds the remaining limits, or the
+    /// writer fails.
+    pub async fn write_xdr<T: WriteXdr + ?Sized>(&mut self, t: &T) -> Result<(), Error> {
+        let mut w = Limited::new(Vec::new(), self.limits.clone());
+        t.write_xdr(&mut w)?;
+        tokio::io::AsyncWriteExt::write_all(&mut self.inner, &w.inner).await?;
+        self.limits = w.limits;
+        Ok(())
+    }
+
+    /// Write the value as a single record with fragment headers.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be encoded, exceeds the remaining limits, or the
+    /// writer fails.
+    pub async fn write_xdr_framed<T: WriteXdr + ?Sized>(&mut self, t: &T) -> Result<(), Error> {
+        let mut w = Limited::new(Vec::new(), self.limits.clone());
+        write_framed(&mut w, |w| t.write_xdr(w))?;
+        tokio::io::AsyncWriteExt::write_all(&mut self.inner, &w.inner).await?;
+        self.limits = w.limits;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    ///
+    /// ### Errors
+    ///
+    /// If the underlying writer fails to flush.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        tokio::io::AsyncWriteExt::flush(&mut self.inner).await?;
+        Ok(())
+    }
+}
+
+/// `ReadXdrStream` is a [`Stream`](futures_core::Stream) of values read from
+/// an [`AsyncLimited`] reader. It is the async form of [`ReadXdrIter`].
+pub struct ReadXdrStream<'r, R, S: ReadXdr> {
+    reader: &'r mut AsyncLimited<R>,
+    done: bool,
+    // The stream only produces values of type S and does not contain them, so
+    // it is Unpin regardless of S.
+    _s: PhantomData<fn() -> S>,
+}
+
+impl<R: tokio::io::AsyncRead + Unpin, S: ReadXdr> futures_core::Stream for ReadXdrStream<'_, R, S> {
+    type Item = Result<S, Error>;
+
+    // Poll next reads the internal reader and XDR decodes it into the S type.
+    // As with `ReadXdrIter`, if the EOF is reached without any bytes buffered
+    // the stream ends, and if the EOF is reached after some bytes an error is
+    // returned. Unlike `ReadXdrIter`, the stream ends after any error, because
+    // a value that fails to decode remains buffered and would fail again.
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return core::task::Poll::Ready(None);
+        }
+        if this.reader.filled == 0 {
+            match core::task::ready!(this.reader.poll_fill(cx)) {
+                Ok(0) => {
+                    this.done = true;
+                    return core::task::Poll::Ready(None);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    this.done = true;
+                    return core::task::Poll::Ready(Some(Err(Error::Io(e))));
+                }
+            }
+        }
+        let res = core::task::ready!(this
+            .reader
+            .poll_read_with(cx, |r| r.with_limited_depth(|dlr| S::read_xdr(dlr))));
+        this.done = res.is_err();
+        core::task::Poll::Ready(Some(res))
+    }
+}
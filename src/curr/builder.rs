@@ -0,0 +1,348 @@
+//# A builder of transactions, for constructing a transaction from strkeys and
+//# native values, checking the limits of the XDR as the transaction is built.
+//#
+//# Fee bumps of transactions, checking the fee of the fee bump against the fee
+//# of the transaction it bumps.
+
+use core::str::FromStr;
+
+use super::{
+    Duration, Error, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, Hash, LedgerBounds, Memo, MuxedAccount, Operation, Preconditions,
+    PreconditionsV2, SequenceNumber, SorobanTransactionData, TimeBounds, TimePoint, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionV1Envelope, VecM,
+};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The maximum number of operations in a transaction.
+pub const MAX_OPERATIONS: usize = 100;
+
+/// The maximum length in bytes of a text memo.
+pub const MAX_MEMO_TEXT_LEN: usize = 28;
+
+/// The minimum base fee in stroops, the minimum fee per operation of a
+/// transaction.
+pub const MIN_BASE_FEE: u32 = 100;
+
+/// `TransactionBuilder` builds a [`Transaction`], or an unsigned
+/// [`TransactionEnvelope`] containing one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionBuilder {
+    source_account: MuxedAccount,
+    fee: u32,
+    seq_num: SequenceNumber,
+    time_bounds: Option<TimeBounds>,
+    ledger_bounds: Option<LedgerBounds>,
+    memo: Memo,
+    operations: Vec<Operation>,
+    soroban_data: Option<SorobanTransactionData>,
+}
+
+impl TransactionBuilder {
+    /// Returns a builder of a transaction from the source account, a `G...`
+    /// or `M...` strkey, with the sequence number.
+    ///
+    /// The sequence number is the sequence number of the transaction, one more
+    /// than the current sequence number of the source account.
+    ///
+    /// ### Errors
+    ///
+    /// If the source account is not a valid account or muxed account strkey.
+    pub fn new(source_account: &str, seq_num: i64) -> Result<Self, Error> {
+        Ok(Self::from_source_account(
+            MuxedAccount::from_str(source_account)?,
+            seq_num,
+        ))
+    }
+
+    /// Returns a builder of a transaction from the source account with the
+    /// sequence number.
+    #[must_use]
+    pub fn from_source_account(source_account: MuxedAccount, seq_num: i64) -> Self {
+        Self {
+            source_account,
+            fee: 0,
+            seq_num: SequenceNumber(seq_num),
+            time_bounds: None,
+            ledger_bounds: None,
+            memo: Memo::None,
+            operations: Vec::new(),
+            soroban_data: None,
+        }
+    }
+
+    /// Sets the fee of the transaction, the maximum total fee in stroops the
+    /// source account will pay for all operations of the transaction.
+    #[must_use]
+    pub fn fee(mut self, fee: u32) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Sets the memo of the transaction.
+    #[must_use]
+    pub fn memo(mut self, memo: Memo) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    /// Sets the memo of the transaction to the text.
+    ///
+    /// ### Errors
+    ///
+    /// If the text is longer than [`MAX_MEMO_TEXT_LEN`] bytes.
+    pub fn memo_text(self, text: &str) -> Result<Self, Error> {
+        Ok(self.memo(Memo::Text(text.try_into()?)))
+    }
+
+    /// Sets the memo of the transaction to the id.
+    #[must_use]
+    pub fn memo_id(self, id: u64) -> Self {
+        self.memo(Memo::Id(id))
+    }
+
+    /// Sets the memo of the transaction to the hash.
+    #[must_use]
+    pub fn memo_hash(self, hash: [u8; 32]) -> Self {
+        self.memo(Memo::Hash(Hash(hash)))
+    }
+
+    /// Sets the memo of the transaction to the hash of the transaction being
+    /// returned.
+    #[must_use]
+    pub fn memo_return(self, hash: [u8; 32]) -> Self {
+        self.memo(Memo::Return(Hash(hash)))
+    }
+
+    /// Sets the time bounds of the transaction, the unix times in seconds that
+    /// the transaction is valid from and until. A `max_time` of zero is no
+    /// upper bound.
+    #[must_use]
+    pub fn time_bounds(mut self, min_time: u64, max_time: u64) -> Self {
+        self.time_bounds = Some(TimeBounds {
+            min_time: TimePoint(min_time),
+            max_time: TimePoint(max_time),
+        });
+        self
+    }
+
+    /// Sets the ledger bounds of the transaction, the ledger sequence numbers
+    /// that the transaction is valid from and until. A `max_ledger` of zero is
+    /// no upper bound.
+    #[must_use]
+    pub fn ledger_bounds(mut self, min_ledger: u32, max_ledger: u32) -> Self {
+        self.ledger_bounds = Some(LedgerBounds {
+            min_ledger,
+            max_ledger,
+        });
+        self
+    }
+
+    /// Adds the operation to the operations of the transaction.
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction already has [`MAX_OPERATIONS`] operations.
+    pub fn operation(mut self, operation: Operation) -> Result<Self, Error> {
+        if self.operations.len() >= MAX_OPERATIONS {
+            return Err(Error::LengthExceedsMax);
+        }
+        self.operations.push(operation);
+        Ok(self)
+    }
+
+    /// Sets the Soroban data of the transaction, the resources and footprint
+    /// of a transaction that invokes a contract.
+    #[must_use]
+    pub fn soroban_data(mut self, soroban_data: SorobanTransactionData) -> Self {
+        self.soroban_data = Some(soroban_data);
+        self
+    }
+
+    /// Builds the transaction.
+    ///
+    /// Time bounds alone are built as [`Preconditions::Time`], and ledger
+    /// bounds as [`Preconditions::V2`].
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction has no operations, if the minimum of the time or
+    /// ledger bounds is greater than a non-zero maximum, or if the fee
+    /// excluding the resource fee of the Soroban data is less than
+    /// [`MIN_BASE_FEE`] for each operation, transactions the network always
+    /// rejects.
+    pub fn build(self) -> Result<Transaction, Error> {
+        if self.operations.is_empty() {
+            return Err(Error::Invalid);
+        }
+        if let Some(TimeBounds { min_time, max_time }) = &self.time_bounds {
+            if max_time.0 != 0 && min_time.0 > max_time.0 {
+                return Err(Error::Invalid);
+            }
+        }
+        if let Some(LedgerBounds {
+            min_ledger,
+            max_ledger,
+        }) = &self.ledger_bounds
+        {
+            if *max_ledger != 0 && min_ledger > max_ledger {
+                return Err(Error::Invalid);
+            }
+        }
+        let cond = match (self.time_bounds, self.ledger_bounds) {
+            (None, None) => Preconditions::None,
+            (Some(time_bounds), None) => Preconditions::Time(time_bounds),
+            (time_bounds, ledger_bounds) => Preconditions::V2(PreconditionsV2 {
+                time_bounds,
+                ledger_bounds,
+                min_seq_num: None,
+                min_seq_age: Duration(0),
+                min_seq_ledger_gap: 0,
+                extra_signers: VecM::default(),
+            }),
+        };
+        // The operations are at most MAX_OPERATIONS and the count fits.
+        #[allow(clippy::cast_possible_wrap)]
+        let min_fee = i64::from(MIN_BASE_FEE) * self.operations.len() as i64;
+        let tx = Transaction {
+            source_account: self.source_account,
+            fee: self.fee,
+            seq_num: self.seq_num,
+            cond,
+            memo: self.memo,
+            operations: self.operations.try_into()?,
+            ext: match self.soroban_data {
+                Some(soroban_data) => TransactionExt::V1(soroban_data),
+                None => TransactionExt::V0,
+            },
+        };
+        match tx.inclusion_fee() {
+            Some(inclusion_fee) if inclusion_fee >= min_fee => Ok(tx),
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// Builds the transaction into an envelope without signatures.
+    ///
+    /// ### Errors
+    ///
+    /// See [`TransactionBuilder::build`].
+    pub fn build_envelope(self) -> Result<TransactionEnvelope, Error> {
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: self.build()?,
+            signatures: VecM::default(),
+        }))
+    }
+}
+
+impl Transaction {
+    /// Returns the resource fee of a Soroban transaction, or zero.
+    fn resource_fee(&self) -> i64 {
+        match &self.ext {
+            TransactionExt::V0 => 0,
+            TransactionExt::V1(soroban_data) => soroban_data.resource_fee,
+        }
+    }
+
+    /// Returns the inclusion fee of the transaction, the fee excluding the
+    /// resource fee of a Soroban transaction, or `None` if the resource fee is
+    /// so far below zero that the inclusion fee overflows.
+    #[must_use]
+    pub fn inclusion_fee(&self) -> Option<i64> {
+        i64::from(self.fee).checked_sub(self.resource_fee())
+    }
+}
+
+impl FeeBumpTransaction {
+    /// Returns the minimum fee of a fee bump of the transaction, on a network
+    /// with the base fee.
+    ///
+    /// A fee bump counts as an operation, and its inclusion fee must be at
+    /// least the base fee for each operation of the transaction plus one, and
+    /// the fee per operation must be at least that of the transaction. The
+    /// resource fee of a Soroban transaction is paid in addition.
+    ///
+    /// Returns `None` if the minimum fee overflows, which happens only for a
+    /// transaction whose resource fee is out of range, and that the network
+    /// rejects.
+    #[must_use]
+    pub fn min_fee(inner: &Transaction, base_fee: u32) -> Option<i64> {
+        // The operations are at most MAX_OPERATIONS and the count fits.
+        #[allow(clippy::cast_possible_wrap)]
+        let ops = (inner.operations.len() as i64).max(1);
+        let inner_inclusion_fee = inner.inclusion_fee()?.max(0);
+        // The minimum fee at which the fee per operation of the fee bump is at
+        // least that of the transaction, rounded up.
+        let min_rate_fee = inner_inclusion_fee
+            .checked_mul(ops + 1)?
+            .checked_add(ops - 1)?
+            / ops;
+        let min_inclusion_fee = (i64::from(base_fee) * (ops + 1)).max(min_rate_fee);
+        min_inclusion_fee.checked_add(inner.resource_fee())
+    }
+
+    /// Returns the transaction the fee bump bumps.
+    #[must_use]
+    pub fn inner_tx(&self) -> &Transaction {
+        let FeeBumpTransactionInnerTx::Tx(e) = &self.inner_tx;
+        &e.tx
+    }
+
+    /// Checks that the fee of the fee bump is at least the minimum fee of a
+    /// fee bump of its transaction, see [`FeeBumpTransaction::min_fee`].
+    ///
+    /// ### Errors
+    ///
+    /// If the fee is less than the minimum fee, or the minimum fee overflows.
+    pub fn check_fee(&self, base_fee: u32) -> Result<(), Error> {
+        match Self::min_fee(self.inner_tx(), base_fee) {
+            Some(min_fee) if self.fee >= min_fee => Ok(()),
+            _ => Err(Error::Invalid),
+        }
+    }
+}
+
+impl TransactionEnvelope {
+    /// Returns a fee bump of the transaction in the envelope, with the fee paid
+    /// by the fee source, on a network with the base fee. The fee bump has no
+    /// signatures, and must be signed by the fee source.
+    ///
+    /// A [`TransactionV0`](super::TransactionV0) is upgraded to the
+    /// [`Transaction`] it is equivalent to, which its signatures remain valid
+    /// for. A fee bump is bumped again by bumping the transaction it bumps.
+    ///
+    /// ### Errors
+    ///
+    /// If the fee is less than the minimum fee of a fee bump of the
+    /// transaction, see [`FeeBumpTransaction::min_fee`].
+    pub fn fee_bump(
+        self,
+        fee_source: MuxedAccount,
+        fee: i64,
+        base_fee: u32,
+    ) -> Result<FeeBumpTransactionEnvelope, Error> {
+        let inner = match self {
+            TransactionEnvelope::TxV0(e) => TransactionV1Envelope {
+                tx: e.tx.into(),
+                signatures: e.signatures,
+            },
+            TransactionEnvelope::Tx(e) => e,
+            TransactionEnvelope::TxFeeBump(e) => {
+                let FeeBumpTransactionInnerTx::Tx(inner) = e.tx.inner_tx;
+                inner
+            }
+        };
+        let tx = FeeBumpTransaction {
+            fee_source,
+            fee,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        };
+        tx.check_fee(base_fee)?;
+        Ok(FeeBumpTransactionEnvelope {
+            tx,
+            signatures: VecM::default(),
+        })
+    }
+}
@@ -0,0 +1,493 @@
+//# Resource and rent fees of Soroban transactions, computed from the fee
+//# configuration of the network in the config settings, the same as the fees
+//# are computed by stellar-core.
+
+use super::{
+    ConfigSettingContractBandwidthV0, ConfigSettingContractComputeV0,
+    ConfigSettingContractEventsV0, ConfigSettingContractHistoricalDataV0,
+    ConfigSettingContractLedgerCostV0, ContractDataDurability, Error, ExtendFootprintTtlOp,
+    LedgerEntry, LedgerEntryData, Limits, SorobanResources, SorobanTransactionData,
+    StateArchivalSettings, TtlEntry, WriteXdr,
+};
+
+/// The number of instructions that each fee per instruction increment is for.
+pub const INSTRUCTIONS_INCREMENT: i64 = 10_000;
+
+/// The number of bytes that each fee per 1KB is for.
+pub const DATA_SIZE_1KB_INCREMENT: i64 = 1024;
+
+/// The size in bytes of the result of a transaction that is charged for as
+/// historical data, in addition to the size of the transaction.
+pub const TX_BASE_RESULT_SIZE: u32 = 300;
+
+/// The size in bytes of a TTL entry that is charged for when an entry is
+/// extended.
+pub const TTL_ENTRY_SIZE: u32 = 48;
+
+/// The minimum fee for writing 1KB to the ledger.
+pub const MINIMUM_WRITE_FEE_PER_1KB: i64 = 1000;
+
+/// `FeeConfiguration` is the fee configuration of the network, the fees for
+/// each resource a Soroban transaction uses.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FeeConfiguration {
+    pub fee_per_instruction_increment: i64,
+    pub fee_per_read_entry: i64,
+    pub fee_per_write_entry: i64,
+    pub fee_per_read_1kb: i64,
+    pub fee_per_write_1kb: i64,
+    pub fee_per_historical_1kb: i64,
+    pub fee_per_contract_event_1kb: i64,
+    pub fee_per_transaction_size_1kb: i64,
+}
+
+/// `TransactionResources` are the resources that a Soroban transaction uses
+/// that are charged for.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TransactionResources {
+    pub instructions: u32,
+    pub read_entries: u32,
+    pub write_entries: u32,
+    pub read_bytes: u32,
+    pub write_bytes: u32,
+    pub contract_events_size_bytes: u32,
+    pub transaction_size_bytes: u32,
+}
+
+impl FeeConfiguration {
+    /// Returns the fee configuration in the config settings of the network,
+    /// with the bucket list size that the fee for writing to the ledger grows
+    /// with.
+    ///
+    /// The bucket list size is the average of the sizes in the
+    /// [`ConfigSettingEntry::BucketlistSizeWindow`](super::ConfigSettingEntry::BucketlistSizeWindow).
+    #[must_use]
+    pub fn from_config_settings(
+        compute: &ConfigSettingContractComputeV0,
+        ledger_cost: &ConfigSettingContractLedgerCostV0,
+        historical_data: &ConfigSettingContractHistoricalDataV0,
+        events: &ConfigSettingContractEventsV0,
+        bandwidth: &ConfigSettingContractBandwidthV0,
+        bucket_list_size_bytes: i64,
+    ) -> Self {
+        Self {
+            fee_per_instruction_increment: compute.fee_rate_per_instructions_increment,
+            fee_per_read_entry: ledger_cost.fee_read_ledger_entry,
+            fee_per_write_entry: ledger_cost.fee_write_ledger_entry,
+            fee_per_read_1kb: ledger_cost.fee_read1_kb,
+            fee_per_write_1kb: ledger_cost.write_fee_per_1kb(bucket_list_size_bytes),
+            fee_per_historical_1kb: historical_data.fee_historical1_kb,
+            fee_per_contract_event_1kb: events.fee_contract_events1_kb,
+            fee_per_transaction_size_1kb: bandwidth.fee_tx_size1_kb,
+        }
+    }
+
+    /// Returns the non-refundable and the refundable fee for the resources.
+    ///
+    /// The refundable fee is the fee for the contract events, and does not
+    /// include the rent fee, which is also refundable.
+    #[must_use]
+    pub fn resource_fee(&self, resources: &TransactionResources) -> (i64, i64) {
+        let compute_fee = fee_per_increment(
+            resources.instructions,
+            self.fee_per_instruction_increment,
+            INSTRUCTIONS_INCREMENT,
+        );
+        // Entries that are written are also read.
+        let read_entries_fee = self.fee_per_read_entry.saturating_mul(
+            resources
+                .read_entries
+                .saturating_add(resources.write_entries)
+                .into(),
+        );
+        let write_entries_fee = self
+            .fee_per_write_entry
+            .saturating_mul(resources.write_entries.into());
+        let read_bytes_fee = fee_per_increment(
+            resources.read_bytes,
+            self.fee_per_read_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        let write_bytes_fee = fee_per_increment(
+            resources.write_bytes,
+            self.fee_per_write_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        let historical_fee = fee_per_increment(
+            resources
+                .transaction_size_bytes
+                .saturating_add(TX_BASE_RESULT_SIZE),
+            self.fee_per_historical_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        let bandwidth_fee = fee_per_increment(
+            resources.transaction_size_bytes,
+            self.fee_per_transaction_size_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        let events_fee = fee_per_increment(
+            resources.contract_events_size_bytes,
+            self.fee_per_contract_event_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        let non_refundable_fee = compute_fee
+            .saturating_add(read_entries_fee)
+            .saturating_add(write_entries_fee)
+            .saturating_add(read_bytes_fee)
+            .saturating_add(write_bytes_fee)
+            .saturating_add(historical_fee)
+            .saturating_add(bandwidth_fee);
+        (non_refundable_fee, events_fee)
+    }
+}
+
+impl TransactionResources {
+    /// Returns the resources declared in the Soroban resources of a
+    /// transaction, with the size of the contract events it emits and the
+    /// size of the transaction envelope.
+    #[must_use]
+    pub fn from_soroban_resources(
+        resources: &SorobanResources,
+        contract_events_size_bytes: u32,
+        transaction_size_bytes: u32,
+    ) -> Self {
+        let len = |keys: &[_]| u32::try_from(keys.len()).unwrap_or(u32::MAX);
+        Self {
+            instructions: resources.instructions,
+            read_entries: len(&resources.footprint.read_only),
+            write_entries: len(&resources.footprint.read_write),
+            read_bytes: resources.read_bytes,
+            write_bytes: resources.write_bytes,
+            contract_events_size_bytes,
+            transaction_size_bytes,
+        }
+    }
+}
+
+impl ConfigSettingContractLedgerCostV0 {
+    /// Returns the fee for writing 1KB to the ledger when the bucket list has
+    /// the size.
+    ///
+    /// The fee grows linearly from the low to the high fee as the bucket list
+    /// grows to the target size, and beyond the target size grows faster by
+    /// the growth factor.
+    #[must_use]
+    pub fn write_fee_per_1kb(&self, bucket_list_size_bytes: i64) -> i64 {
+        // The target size of a valid configuration is positive, and the high
+        // fee is used for any other.
+        if self.bucket_list_target_size_bytes <= 0 {
+            return self
+                .write_fee1_kb_bucket_list_high
+                .max(MINIMUM_WRITE_FEE_PER_1KB);
+        }
+        let fee_rate_multiplier = self
+            .write_fee1_kb_bucket_list_high
+            .saturating_sub(self.write_fee1_kb_bucket_list_low);
+        let write_fee_per_1kb = if bucket_list_size_bytes < self.bucket_list_target_size_bytes {
+            div_ceil(
+                fee_rate_multiplier.saturating_mul(bucket_list_size_bytes),
+                self.bucket_list_target_size_bytes,
+            )
+            .saturating_add(self.write_fee1_kb_bucket_list_low)
+        } else {
+            let size_after_target =
+                bucket_list_size_bytes.saturating_sub(self.bucket_list_target_size_bytes);
+            let post_target_fee = div_ceil(
+                fee_rate_multiplier
+                    .saturating_mul(size_after_target)
+                    .saturating_mul(self.bucket_list_write_fee_growth_factor.into()),
+                self.bucket_list_target_size_bytes,
+            );
+            self.write_fee1_kb_bucket_list_high
+                .saturating_add(post_target_fee)
+        };
+        write_fee_per_1kb.max(MINIMUM_WRITE_FEE_PER_1KB)
+    }
+}
+
+impl SorobanTransactionData {
+    /// Checks that the resource fee declared is enough to pay for the
+    /// resources declared, with the size of the contract events the
+    /// transaction emits and the size of the transaction envelope, see
+    /// [`FeeConfiguration::resource_fee`].
+    ///
+    /// ### Errors
+    ///
+    /// If the resource fee is less than the non-refundable fee plus the
+    /// refundable fee of the resources.
+    pub fn check_resource_fee(
+        &self,
+        fee_configuration: &FeeConfiguration,
+        contract_events_size_bytes: u32,
+        transaction_size_bytes: u32,
+    ) -> Result<(), Error> {
+        let resources = TransactionResources::from_soroban_resources(
+            &self.resources,
+            contract_events_size_bytes,
+            transaction_size_bytes,
+        );
+        let (non_refundable_fee, refundable_fee) = fee_configuration.resource_fee(&resources);
+        if self.resource_fee < non_refundable_fee.saturating_add(refundable_fee) {
+            return Err(Error::Invalid);
+        }
+        Ok(())
+    }
+}
+
+/// `RentFeeConfiguration` is the fee configuration of the network for the rent
+/// of contract data and contract code entries, that are charged for keeping
+/// the entries live in the ledger.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RentFeeConfiguration {
+    pub fee_per_write_1kb: i64,
+    pub fee_per_write_entry: i64,
+    pub persistent_rent_rate_denominator: i64,
+    pub temporary_rent_rate_denominator: i64,
+}
+
+/// `LedgerEntryRentChange` is a change to the size or the TTL of an entry that
+/// is charged rent for.
+///
+/// An entry that is new, or that is restored, has an old size and an old live
+/// until ledger of zero.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LedgerEntryRentChange {
+    pub is_persistent: bool,
+    pub old_size_bytes: u32,
+    pub new_size_bytes: u32,
+    pub old_live_until_ledger: u32,
+    pub new_live_until_ledger: u32,
+}
+
+impl RentFeeConfiguration {
+    /// Returns the rent fee configuration in the config settings of the
+    /// network, with the bucket list size that the fee for writing to the
+    /// ledger grows with, see
+    /// [`ConfigSettingContractLedgerCostV0::write_fee_per_1kb`].
+    #[must_use]
+    pub fn from_config_settings(
+        ledger_cost: &ConfigSettingContractLedgerCostV0,
+        state_archival: &StateArchivalSettings,
+        bucket_list_size_bytes: i64,
+    ) -> Self {
+        Self {
+            fee_per_write_1kb: ledger_cost.write_fee_per_1kb(bucket_list_size_bytes),
+            fee_per_write_entry: ledger_cost.fee_write_ledger_entry,
+            persistent_rent_rate_denominator: state_archival.persistent_rent_rate_denominator,
+            temporary_rent_rate_denominator: state_archival.temp_rent_rate_denominator,
+        }
+    }
+
+    /// Returns the rent fee for an entry of the size and durability to be
+    /// live for the number of ledgers.
+    ///
+    /// The durability of a contract code entry, which is always persistent,
+    /// must be given as [`ContractDataDurability::Persistent`].
+    #[must_use]
+    pub fn rent_fee_for_ledgers(
+        &self,
+        durability: ContractDataDurability,
+        size_bytes: u32,
+        ledgers: u32,
+    ) -> i64 {
+        let rent_rate_denominator = match durability {
+            ContractDataDurability::Persistent => self.persistent_rent_rate_denominator,
+            ContractDataDurability::Temporary => self.temporary_rent_rate_denominator,
+        };
+        let denominator = DATA_SIZE_1KB_INCREMENT.saturating_mul(rent_rate_denominator);
+        // The rent rate denominators of a valid configuration are positive,
+        // and no rent is charged for any other.
+        if denominator <= 0 {
+            return 0;
+        }
+        div_ceil(
+            i64::from(size_bytes)
+                .saturating_mul(self.fee_per_write_1kb)
+                .saturating_mul(ledgers.into()),
+            denominator,
+        )
+    }
+
+    /// Returns the rent fee for the changes to entries at the current ledger.
+    ///
+    /// Rent is charged for the ledgers an entry is extended by at its new
+    /// size, and for any increase in size over the ledgers that were already
+    /// paid for. Each entry that is extended is also charged for writing its
+    /// TTL entry.
+    #[must_use]
+    pub fn rent_fee(&self, changes: &[LedgerEntryRentChange], current_ledger: u32) -> i64 {
+        let mut fee: i64 = 0;
+        let mut extended_entries: u32 = 0;
+        for change in changes {
+            fee = fee.saturating_add(self.rent_fee_for_change(change, current_ledger));
+            if change.old_live_until_ledger < change.new_live_until_ledger {
+                extended_entries = extended_entries.saturating_add(1);
+            }
+        }
+        fee.saturating_add(self.ttl_write_fee(extended_entries))
+    }
+
+    /// Returns the rent fee for extending the TTL of the entries with an
+    /// [`ExtendFootprintTtlOp`] at the current ledger.
+    ///
+    /// The entries are given with their TTL entries. Entries that are not
+    /// live at the current ledger, or that already live long enough, are not
+    /// extended.
+    ///
+    /// ### Errors
+    ///
+    /// If any of the entries is not a contract data or contract code entry,
+    /// or if the size of an entry cannot be computed.
+    pub fn extend_footprint_ttl_rent_fee(
+        &self,
+        op: &ExtendFootprintTtlOp,
+        entries: &[(LedgerEntry, TtlEntry)],
+        current_ledger: u32,
+    ) -> Result<i64, Error> {
+        let new_live_until_ledger = current_ledger.saturating_add(op.extend_to);
+        let mut fee: i64 = 0;
+        let mut extended_entries: u32 = 0;
+        for (entry, ttl) in entries {
+            let is_persistent = is_persistent(entry)?;
+            let size_bytes = entry_size(entry)?;
+            if ttl.live_until_ledger_seq < current_ledger
+                || ttl.live_until_ledger_seq >= new_live_until_ledger
+            {
+                continue;
+            }
+            let change = LedgerEntryRentChange {
+                is_persistent,
+                old_size_bytes: size_bytes,
+                new_size_bytes: size_bytes,
+                old_live_until_ledger: ttl.live_until_ledger_seq,
+                new_live_until_ledger,
+            };
+            fee = fee.saturating_add(self.rent_fee_for_change(&change, current_ledger));
+            extended_entries = extended_entries.saturating_add(1);
+        }
+        Ok(fee.saturating_add(self.ttl_write_fee(extended_entries)))
+    }
+
+    /// Returns the rent fee for restoring the entries with a
+    /// [`RestoreFootprintOp`](super::RestoreFootprintOp) at the current ledger, with the minimum TTL of
+    /// persistent entries in the [`StateArchivalSettings`].
+    ///
+    /// The entries are given with their TTL entries. Entries that are still
+    /// live at the current ledger are not restored. Restored entries are
+    /// charged rent as if they are new.
+    ///
+    /// ### Errors
+    ///
+    /// If any of the entries is not a persistent contract data or contract
+    /// code entry, or if the size of an entry cannot be computed.
+    pub fn restore_footprint_rent_fee(
+        &self,
+        entries: &[(LedgerEntry, TtlEntry)],
+        min_persistent_ttl: u32,
+        current_ledger: u32,
+    ) -> Result<i64, Error> {
+        let new_live_until_ledger = current_ledger
+            .saturating_add(min_persistent_ttl)
+            .saturating_sub(1);
+        let mut fee: i64 = 0;
+        let mut restored_entries: u32 = 0;
+        for (entry, ttl) in entries {
+            if !is_persistent(entry)? {
+                return Err(Error::Invalid);
+            }
+            let size_bytes = entry_size(entry)?;
+            if ttl.live_until_ledger_seq >= current_ledger {
+                continue;
+            }
+            let change = LedgerEntryRentChange {
+                is_persistent: true,
+                old_size_bytes: 0,
+                new_size_bytes: size_bytes,
+                old_live_until_ledger: 0,
+                new_live_until_ledger,
+            };
+            fee = fee.saturating_add(self.rent_fee_for_change(&change, current_ledger));
+            restored_entries = restored_entries.saturating_add(1);
+        }
+        Ok(fee.saturating_add(self.ttl_write_fee(restored_entries)))
+    }
+
+    /// Returns the fee for writing the TTL entries of the extended entries.
+    fn ttl_write_fee(&self, extended_entries: u32) -> i64 {
+        let ttl_entries_fee = self
+            .fee_per_write_entry
+            .saturating_mul(extended_entries.into());
+        let ttl_bytes_fee = fee_per_increment(
+            extended_entries.saturating_mul(TTL_ENTRY_SIZE),
+            self.fee_per_write_1kb,
+            DATA_SIZE_1KB_INCREMENT,
+        );
+        ttl_entries_fee.saturating_add(ttl_bytes_fee)
+    }
+
+    fn rent_fee_for_change(&self, change: &LedgerEntryRentChange, current_ledger: u32) -> i64 {
+        let durability = if change.is_persistent {
+            ContractDataDurability::Persistent
+        } else {
+            ContractDataDurability::Temporary
+        };
+        let is_new = change.old_size_bytes == 0 && change.old_live_until_ledger == 0;
+        let mut fee: i64 = 0;
+        // The ledgers the entry is extended by, that are charged for at the
+        // new size.
+        let live_until_before_extension = if is_new {
+            current_ledger.saturating_sub(1)
+        } else {
+            change.old_live_until_ledger
+        };
+        if change.new_live_until_ledger > live_until_before_extension {
+            fee = self.rent_fee_for_ledgers(
+                durability,
+                change.new_size_bytes,
+                change.new_live_until_ledger - live_until_before_extension,
+            );
+        }
+        // The ledgers that are already paid for are charged for any increase
+        // in size.
+        if !is_new
+            && change.old_live_until_ledger >= current_ledger
+            && change.new_size_bytes > change.old_size_bytes
+        {
+            fee = fee.saturating_add(self.rent_fee_for_ledgers(
+                durability,
+                change.new_size_bytes - change.old_size_bytes,
+                change.old_live_until_ledger - current_ledger + 1,
+            ));
+        }
+        fee
+    }
+}
+
+fn is_persistent(entry: &LedgerEntry) -> Result<bool, Error> {
+    match &entry.data {
+        LedgerEntryData::ContractData(data) => {
+            Ok(data.durability == ContractDataDurability::Persistent)
+        }
+        LedgerEntryData::ContractCode(_) => Ok(true),
+        _ => Err(Error::Invalid),
+    }
+}
+
+fn entry_size(entry: &LedgerEntry) -> Result<u32, Error> {
+    let len = entry.xdr_len(Limits::none())?;
+    u32::try_from(len).map_err(|_| Error::LengthExceedsMax)
+}
+
+fn fee_per_increment(resource: u32, fee_rate: i64, increment: i64) -> i64 {
+    div_ceil(i64::from(resource).saturating_mul(fee_rate), increment)
+}
+
+/// Divides rounding towards positive infinity. The divisor must be positive.
+fn div_ceil(n: i64, d: i64) -> i64 {
+    let q = n / d;
+    if n % d > 0 {
+        q + 1
+    } else {
+        q
+    }
+}
@@ -0,0 +1,275 @@
+//# Record marking of XDR values, as defined by the record marking standard in
+//# RFC 5531, where each value is written as a record split across one or more
+//# fragments.
+
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use super::io::{self, Read, Write};
+
+use super::ReadXdr;
+#[cfg(feature = "alloc")]
+use super::{Error, Limited, Limits, WriteXdr};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// `Frame` is a record-marked XDR value, as defined by the record marking
+/// standard in RFC 5531. A record may be split across multiple fragments, each
+/// with its own header containing a last-fragment flag and the length of the
+/// fragment.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    all(feature = "serde", feature = "alloc"),
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Frame<T>(pub T)
+where
+    T: ReadXdr;
+
+/// `FrameReader` reads the bytes of a single record that may be split across
+/// multiple fragments, reading the header of each subsequent fragment as the
+/// previous fragment is exhausted. The reader reaches EOF at the end of the
+/// last fragment.
+#[cfg(feature = "alloc")]
+pub(crate) struct FrameReader<'r, R: Read> {
+    inner: &'r mut R,
+    // The number of bytes remaining in the current fragment.
+    remaining: usize,
+    // True if the current fragment is the last fragment of the record.
+    last: bool,
+    // The number of fragment headers read after the first.
+    headers: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'r, R: Read> FrameReader<'r, R> {
+    fn new(inner: &'r mut R, header: u32) -> Self {
+        let mut fr = FrameReader {
+            inner,
+            remaining: 0,
+            last: false,
+            headers: 0,
+        };
+        fr.set_header(header);
+        fr
+    }
+
+    // Set the state of the reader from a frame header value that contains 1
+    // flag-bit and a 31-bit length.
+    //  - The 1 flag bit is 0 when there are more frames for the same record.
+    //  - The 31-bit length is the length of the bytes within the frame that
+    //  follow the frame header.
+    fn set_header(&mut self, header: u32) {
+        self.last = header >> 31 == 1;
+        self.remaining = (header & 0x7fff_ffff) as usize;
+    }
+
+    // Read the headers of any further fragments while the current fragment is
+    // exhausted and is not the last fragment of the record.
+    fn next_fragment(&mut self) -> io::Result<()> {
+        while self.remaining == 0 && !self.last {
+            let mut b = [0u8; 4];
+            self.inner.read_exact(&mut b)?;
+            self.headers += 1;
+            self.set_header(u32::from_be_bytes(b));
+        }
+        Ok(())
+    }
+
+    // Returns true if all the bytes of the record have been read.
+    fn is_end(&self) -> bool {
+        self.remaining == 0 && self.last
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read> Read for FrameReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.next_fragment()?;
+        let len = core::cmp::min(buf.len(), self.remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining -= n;
+        Ok(n)
+    }
+}
+
+impl<T> ReadXdr for Frame<T>
+where
+    T: ReadXdr,
+{
+    /// Read a record, reassembling it from as many fragments as it is split
+    /// across.
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::LengthMismatch`] if the value does not consume exactly
+    /// the length of the record declared by the fragment headers.
+    #[cfg(feature = "alloc")]
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self, Error> {
+        read_frame(r, T::read_xdr).map(Self)
+    }
+
+    /// Skip a record, and the value it contains.
+    #[cfg(feature = "alloc")]
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<(), Error> {
+        read_frame(r, T::skip_xdr)
+    }
+}
+
+/// Read a record, reading the value it contains with `f` from a reader that
+/// reassembles the record from its fragments.
+#[cfg(feature = "alloc")]
+pub(crate) fn read_frame<'r, R, T, F>(r: &'r mut Limited<R>, f: F) -> Result<T, Error>
+where
+    R: Read,
+    F: FnOnce(&mut Limited<FrameReader<'r, R>>) -> Result<T, Error>,
+{
+    let header = u32::read_xdr(r)?;
+    let mut fr = Limited::new(FrameReader::new(&mut r.inner, header), r.limits.clone());
+    fr.offset = r.offset;
+    fr.error_context = r.error_context;
+    let t = match f(&mut fr) {
+        Ok(t) => t,
+        Err(e) => {
+            // If the value attempted to read beyond the end of the record,
+            // the length of the record does not match the value.
+            if let Error::Io(io_err) = e.without_context() {
+                if io_err.kind() == io::ErrorKind::UnexpectedEof && fr.inner.is_end() {
+                    return Err(Error::LengthMismatch);
+                }
+            }
+            return Err(e);
+        }
+    };
+    // Read past any trailing empty fragments, and check that the value
+    // consumed all the bytes of the record.
+    fr.inner.next_fragment()?;
+    if !fr.inner.is_end() {
+        return Err(Error::LengthMismatch);
+    }
+    let headers = fr.inner.headers;
+    fr.consume_len(headers * 4)?;
+    r.limits = fr.limits;
+    r.offset = fr.offset;
+    Ok(t)
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WriteXdr for Frame<T>
+where
+    T: ReadXdr + WriteXdr,
+{
+    /// Write a record containing the value, split across as many fragments as
+    /// the length of the value requires.
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<(), Error> {
+        write_framed(w, |w| self.0.write_xdr(w))
+    }
+
+    /// Returns the length of the value, and of the fragment headers written
+    /// before each fragment.
+    fn xdr_len_at_depth(&self, depth: u32) -> Result<usize, Error> {
+        let len = self.0.xdr_len_at_depth(depth)?;
+        let headers = len.div_ceil(FRAGMENT_MAX_LEN).max(1);
+        Ok(len + headers * 0u32.xdr_len_at_depth(depth)?)
+    }
+}
+
+/// The maximum length of a fragment, that fits in the 31-bit length of the
+/// fragment header.
+#[cfg(feature = "alloc")]
+const FRAGMENT_MAX_LEN: usize = 0x7fff_ffff;
+
+/// Write a record containing the XDR written by `f`, with fragment headers.
+///
+/// The XDR is buffered so that the length of the record is known before the
+/// fragment headers are written.
+#[cfg(feature = "alloc")]
+pub(crate) fn write_framed<W, F>(w: &mut Limited<W>, f: F) -> Result<(), Error>
+where
+    W: Write,
+    F: FnOnce(&mut Limited<Vec<u8>>) -> Result<(), Error>,
+{
+    let mut buf = Limited::new(Vec::new(), w.limits.clone());
+    f(&mut buf)?;
+    w.limits = buf.limits;
+    let mut chunks = buf.inner.chunks(FRAGMENT_MAX_LEN).peekable();
+    if chunks.peek().is_none() {
+        return 0x8000_0000u32.write_xdr(w);
+    }
+    while let Some(chunk) = chunks.next() {
+        // The chunk length is at most FRAGMENT_MAX_LEN and fits in the header.
+        #[allow(clippy::cast_possible_truncation)]
+        let mut header = chunk.len() as u32;
+        if chunks.peek().is_none() {
+            header |= 0x8000_0000;
+        }
+        header.write_xdr(w)?;
+        w.write_all(chunk)?;
+    }
+    Ok(())
+}
+
+/// `FramedWriter` writes a sequence of values as a stream of records, each
+/// preceded by fragment headers, as read by
+/// [`Type::read_xdr_framed_iter`](Type::read_xdr_framed_iter).
+///
+/// The limits apply to the stream as a whole, and not to each record.
+#[cfg(feature = "alloc")]
+pub struct FramedWriter<W: Write> {
+    writer: Limited<W>,
+}
+
+#[cfg(feature = "alloc")]
+impl<W: Write> FramedWriter<W> {
+    pub fn new(w: W, limits: Limits) -> Self {
+        Self {
+            writer: Limited::new(w, limits),
+        }
+    }
+
+    /// Write the value as a single record.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be written, or exceeds the remaining limits.
+    pub fn write<T: WriteXdr + ?Sized>(&mut self, t: &T) -> Result<(), Error> {
+        self.writer
+            .with_limited_depth(|w| write_framed(w, |w| t.write_xdr(w)))
+    }
+
+    /// Write each value as a record.
+    ///
+    /// ### Errors
+    ///
+    /// If any value cannot be written, or exceeds the remaining limits. Values
+    /// preceding the value that errored will have been written.
+    pub fn write_iter<T, I>(&mut self, iter: I) -> Result<(), Error>
+    where
+        T: WriteXdr,
+        I: IntoIterator<Item = T>,
+    {
+        for t in iter {
+            self.write(&t)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    ///
+    /// ### Errors
+    ///
+    /// If the underlying writer fails to flush.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer.inner
+    }
+}
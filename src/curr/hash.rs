@@ -0,0 +1,171 @@
+//# Hashes of transactions, that are signed to authorize the transactions, and
+//# the network ids of the networks they are signed for.
+
+#[cfg(all(feature = "sha2", feature = "std"))]
+use std::io::{self, Write};
+
+#[cfg(all(feature = "sha2", not(feature = "std")))]
+use super::io::{self, Write};
+
+#[cfg(feature = "sha2")]
+use super::{
+    EnvelopeType, Error, FeeBumpTransaction, Hash, Limited, Limits, TransactionEnvelope,
+    TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+};
+use super::{MuxedAccount, Preconditions, Transaction, TransactionExt, TransactionV0};
+
+/// The network passphrase of the public network.
+pub const NETWORK_PASSPHRASE_PUBLIC: &str = "Public Global Stellar Network ; September 2015";
+
+/// The network passphrase of the test network.
+pub const NETWORK_PASSPHRASE_TESTNET: &str = "Test SDF Network ; September 2015";
+
+/// The network passphrase of the future network.
+pub const NETWORK_PASSPHRASE_FUTURENET: &str = "Test SDF Future Network ; October 2022";
+
+/// `Sha256Writer` is a writer that hashes the bytes written to it.
+#[cfg(feature = "sha2")]
+pub(crate) struct Sha256Writer(pub(crate) sha2::Sha256);
+
+#[cfg(feature = "sha2")]
+impl Sha256Writer {
+    pub(crate) fn new() -> Self {
+        Self(sha2::Digest::new())
+    }
+
+    pub(crate) fn finalize(self) -> Hash {
+        Hash(sha2::Digest::finalize(self.0).into())
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl Write for Sha256Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        sha2::Digest::update(&mut self.0, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl Hash {
+    /// Returns the network id of the network with the passphrase, the sha256
+    /// hash of the passphrase.
+    #[must_use]
+    pub fn network_id(network_passphrase: &str) -> Self {
+        let mut w = Sha256Writer::new();
+        sha2::Digest::update(&mut w.0, network_passphrase.as_bytes());
+        w.finalize()
+    }
+}
+
+/// Upgrades a [`TransactionV0`] to the [`Transaction`] it is equivalent to,
+/// the form that it is hashed and signed as.
+impl From<TransactionV0> for Transaction {
+    fn from(tx: TransactionV0) -> Self {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(tx.source_account_ed25519),
+            fee: tx.fee,
+            seq_num: tx.seq_num,
+            cond: match tx.time_bounds {
+                Some(time_bounds) => Preconditions::Time(time_bounds),
+                None => Preconditions::None,
+            },
+            memo: tx.memo,
+            operations: tx.operations,
+            ext: TransactionExt::V0,
+        }
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl Transaction {
+    /// Returns the payload that is hashed and signed for the transaction on
+    /// the network with the passphrase.
+    #[must_use]
+    pub fn signature_payload(&self, network_passphrase: &str) -> TransactionSignaturePayload {
+        TransactionSignaturePayload {
+            network_id: Hash::network_id(network_passphrase),
+            tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(self.clone()),
+        }
+    }
+
+    /// Returns the hash of the transaction on the network with the
+    /// passphrase, the sha256 hash of its [`TransactionSignaturePayload`].
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction cannot be encoded.
+    pub fn hash(&self, network_passphrase: &str) -> Result<Hash, Error> {
+        // Write the fields of the payload directly, to avoid cloning the
+        // transaction into one.
+        let mut w = Limited::new(Sha256Writer::new(), Limits::none());
+        Hash::network_id(network_passphrase).write_xdr(&mut w)?;
+        EnvelopeType::Tx.write_xdr(&mut w)?;
+        self.write_xdr(&mut w)?;
+        Ok(w.inner.finalize())
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl FeeBumpTransaction {
+    /// Returns the payload that is hashed and signed for the transaction on
+    /// the network with the passphrase.
+    #[must_use]
+    pub fn signature_payload(&self, network_passphrase: &str) -> TransactionSignaturePayload {
+        TransactionSignaturePayload {
+            network_id: Hash::network_id(network_passphrase),
+            tagged_transaction: TransactionSignaturePayloadTaggedTransaction::TxFeeBump(
+                self.clone(),
+            ),
+        }
+    }
+
+    /// Returns the hash of the transaction on the network with the
+    /// passphrase, the sha256 hash of its [`TransactionSignaturePayload`].
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction cannot be encoded.
+    pub fn hash(&self, network_passphrase: &str) -> Result<Hash, Error> {
+        let mut w = Limited::new(Sha256Writer::new(), Limits::none());
+        Hash::network_id(network_passphrase).write_xdr(&mut w)?;
+        EnvelopeType::TxFeeBump.write_xdr(&mut w)?;
+        self.write_xdr(&mut w)?;
+        Ok(w.inner.finalize())
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl TransactionEnvelope {
+    /// Returns the payload that is hashed and signed for the transaction in
+    /// the envelope on the network with the passphrase.
+    ///
+    /// A [`TransactionV0`] is signed as the [`Transaction`] it is equivalent
+    /// to.
+    #[must_use]
+    pub fn signature_payload(&self, network_passphrase: &str) -> TransactionSignaturePayload {
+        match self {
+            Self::TxV0(e) => Transaction::from(e.tx.clone()).signature_payload(network_passphrase),
+            Self::Tx(e) => e.tx.signature_payload(network_passphrase),
+            Self::TxFeeBump(e) => e.tx.signature_payload(network_passphrase),
+        }
+    }
+
+    /// Returns the hash of the transaction in the envelope on the network
+    /// with the passphrase.
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction cannot be encoded.
+    pub fn hash(&self, network_passphrase: &str) -> Result<Hash, Error> {
+        match self {
+            Self::TxV0(e) => Transaction::from(e.tx.clone()).hash(network_passphrase),
+            Self::Tx(e) => e.tx.hash(network_passphrase),
+            Self::TxFeeBump(e) => e.tx.hash(network_passphrase),
+        }
+    }
+}
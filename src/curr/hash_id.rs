@@ -0,0 +1,154 @@
+//# Ids derived from hashes of the preimages in [`HashIdPreimage`], of
+//# contracts, claimable balances and liquidity pools, and the payloads signed
+//# to authorize Soroban invocations.
+
+use super::{
+    hash::Sha256Writer, AccountId, Asset, ClaimableBalanceId, ContractIdPreimage, Error, Hash,
+    HashIdPreimage, HashIdPreimageContractId, HashIdPreimageOperationId, HashIdPreimageRevokeId,
+    HashIdPreimageSorobanAuthorization, Limited, Limits, LiquidityPoolParameters, PoolId,
+    ScAddress, SequenceNumber, SorobanAuthorizationEntry, SorobanCredentials, WriteXdr,
+};
+
+impl HashIdPreimage {
+    /// Returns the id derived from the preimage, the sha256 hash of the
+    /// preimage.
+    ///
+    /// ### Errors
+    ///
+    /// If the preimage cannot be encoded.
+    pub fn hash(&self) -> Result<Hash, Error> {
+        let mut w = Limited::new(Sha256Writer::new(), Limits::none());
+        self.write_xdr(&mut w)?;
+        Ok(w.inner.finalize())
+    }
+}
+
+impl ContractIdPreimage {
+    /// Returns the id of the contract created from the preimage on the
+    /// network with the passphrase.
+    ///
+    /// ### Errors
+    ///
+    /// If the preimage cannot be encoded.
+    pub fn contract_id(&self, network_passphrase: &str) -> Result<Hash, Error> {
+        HashIdPreimage::ContractId(HashIdPreimageContractId {
+            network_id: Hash::network_id(network_passphrase),
+            contract_id_preimage: self.clone(),
+        })
+        .hash()
+    }
+
+    /// Returns the address of the contract created from the preimage on the
+    /// network with the passphrase.
+    ///
+    /// ### Errors
+    ///
+    /// If the preimage cannot be encoded.
+    pub fn contract_address(&self, network_passphrase: &str) -> Result<ScAddress, Error> {
+        Ok(ScAddress::Contract(self.contract_id(network_passphrase)?))
+    }
+}
+
+impl ClaimableBalanceId {
+    /// Returns the id of the claimable balance created by the operation at
+    /// index `op_num` in the transaction with the source account and sequence
+    /// number.
+    ///
+    /// The source account is the source account of the transaction, not of the
+    /// operation.
+    ///
+    /// ### Errors
+    ///
+    /// If the preimage cannot be encoded.
+    pub fn from_operation(
+        source_account: AccountId,
+        seq_num: SequenceNumber,
+        op_num: u32,
+    ) -> Result<Self, Error> {
+        let hash = HashIdPreimage::OpId(HashIdPreimageOperationId {
+            source_account,
+            seq_num,
+            op_num,
+        })
+        .hash()?;
+        Ok(Self::ClaimableBalanceIdTypeV0(hash))
+    }
+
+    /// Returns the id of the claimable balance created for the asset when the
+    /// operation at index `op_num` revokes the authorization of a trustline
+    /// that holds shares of the liquidity pool, in the transaction with the
+    /// source account and sequence number.
+    ///
+    /// ### Errors
+    ///
+    /// If the preimage cannot be encoded.
+    pub fn from_pool_revoke(
+        source_account: AccountId,
+        seq_num: SequenceNumber,
+        op_num: u32,
+        liquidity_pool_id: PoolId,
+        asset: Asset,
+    ) -> Result<Self, Error> {
+        let hash = HashIdPreimage::PoolRevokeOpId(HashIdPreimageRevokeId {
+            source_account,
+            seq_num,
+            op_num,
+            liquidity_pool_id,
+            asset,
+        })
+        .hash()?;
+        Ok(Self::ClaimableBalanceIdTypeV0(hash))
+    }
+}
+
+impl PoolId {
+    /// Returns the id of the liquidity pool with the parameters, the sha256
+    /// hash of the parameters.
+    ///
+    /// The assets of a pool are ordered, `asset_a` less than `asset_b`, and
+    /// parameters with the assets in the other order do not identify a pool.
+    ///
+    /// ### Errors
+    ///
+    /// If the parameters cannot be encoded.
+    pub fn from_parameters(parameters: &LiquidityPoolParameters) -> Result<Self, Error> {
+        let mut w = Limited::new(Sha256Writer::new(), Limits::none());
+        parameters.write_xdr(&mut w)?;
+        Ok(Self(w.inner.finalize()))
+    }
+}
+
+impl SorobanAuthorizationEntry {
+    /// Returns the payload that is hashed and signed to authorize the root
+    /// invocation of the entry on the network with the passphrase.
+    ///
+    /// Returns `None` if the entry has source account credentials, which are
+    /// authorized by the signatures of the transaction instead.
+    #[must_use]
+    pub fn signature_payload(&self, network_passphrase: &str) -> Option<HashIdPreimage> {
+        let SorobanCredentials::Address(credentials) = &self.credentials else {
+            return None;
+        };
+        Some(HashIdPreimage::SorobanAuthorization(
+            HashIdPreimageSorobanAuthorization {
+                network_id: Hash::network_id(network_passphrase),
+                nonce: credentials.nonce,
+                signature_expiration_ledger: credentials.signature_expiration_ledger,
+                invocation: self.root_invocation.clone(),
+            },
+        ))
+    }
+
+    /// Returns the hash of the payload that is signed to authorize the root
+    /// invocation of the entry on the network with the passphrase, see
+    /// [`SorobanAuthorizationEntry::signature_payload`].
+    ///
+    /// ### Errors
+    ///
+    /// If the payload cannot be encoded.
+    pub fn signature_payload_hash(&self, network_passphrase: &str) -> Result<Option<Hash>, Error> {
+        self.signature_payload(network_passphrase)
+            .map(|payload| payload.hash())
+            .transpose()
+    }
+}
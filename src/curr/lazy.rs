@@ -0,0 +1,164 @@
+//# Values held as XDR, that are only decoded when needed.
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+#[cfg(not(feature = "std"))]
+use super::io::{self, Read, Write};
+
+use super::{Error, Limited, Limits, ReadXdr, WriteXdr};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// `Lazy` holds the XDR of a value of type `T` without decoding it.
+///
+/// Reading a `Lazy` skips over the value with [`ReadXdr::skip_xdr`], validating
+/// the value without constructing it, and keeps its bytes. The value is decoded
+/// on demand with [`Lazy::decode`].
+///
+/// Writing a `Lazy` writes the bytes it holds verbatim, so a value that is read
+/// and written again without being decoded and modified has the same bytes,
+/// and the same hash, as it had when read.
+///
+/// The borrowed form, [`borrowed::Lazy`](super::borrowed::Lazy), holds the XDR
+/// borrowed from the slice it is decoded from instead of a copy.
+pub struct Lazy<T> {
+    xdr: Vec<u8>,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T> Lazy<T> {
+    /// Constructs a `Lazy` holding the XDR of the value.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be written within the limits.
+    pub fn new(value: &T, limits: Limits) -> Result<Self, Error>
+    where
+        T: WriteXdr,
+    {
+        Ok(Self {
+            xdr: value.to_xdr(limits)?,
+            _t: PhantomData,
+        })
+    }
+
+    /// Decodes the value from the XDR held.
+    ///
+    /// ### Errors
+    ///
+    /// If the value cannot be read within the limits.
+    pub fn decode(&self, limits: Limits) -> Result<T, Error>
+    where
+        T: ReadXdr,
+    {
+        T::from_xdr(&self.xdr, limits)
+    }
+
+    /// Returns the XDR of the value.
+    #[must_use]
+    pub fn as_xdr(&self) -> &[u8] {
+        &self.xdr
+    }
+
+    /// Returns the XDR of the value.
+    #[must_use]
+    pub fn into_xdr(self) -> Vec<u8> {
+        self.xdr
+    }
+
+    /// Constructs a `Lazy` holding XDR that has already been validated by
+    /// skipping over a value of type `T`.
+    pub(crate) fn from_skipped_xdr(xdr: Vec<u8>) -> Self {
+        Self {
+            xdr,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            xdr: self.xdr.clone(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for Lazy<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.xdr == other.xdr
+    }
+}
+
+impl<T> Eq for Lazy<T> {}
+
+impl<T> core::hash::Hash for Lazy<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.xdr.hash(state);
+    }
+}
+
+impl<T> core::fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Lazy(")?;
+        for b in &self.xdr {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+/// `Recorder` reads from the wrapped reader, keeping a copy of the bytes read.
+struct Recorder<'r, R: Read> {
+    inner: &'r mut R,
+    xdr: Vec<u8>,
+}
+
+impl<R: Read> Read for Recorder<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.xdr.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<T: ReadXdr> ReadXdr for Lazy<T> {
+    /// Skip over the value, keeping its XDR.
+    fn read_xdr<R: Read>(r: &mut Limited<R>) -> Result<Self, Error> {
+        let rec = Recorder {
+            inner: &mut r.inner,
+            xdr: Vec::new(),
+        };
+        let mut rr = Limited::new(rec, r.limits.clone());
+        rr.offset = r.offset;
+        rr.error_context = r.error_context;
+        T::skip_xdr(&mut rr)?;
+        let xdr = rr.inner.xdr;
+        r.limits = rr.limits;
+        r.offset = rr.offset;
+        r.consume_alloc(xdr.len())?;
+        Ok(Self::from_skipped_xdr(xdr))
+    }
+
+    fn skip_xdr<R: Read>(r: &mut Limited<R>) -> Result<(), Error> {
+        T::skip_xdr(r)
+    }
+}
+
+impl<T> WriteXdr for Lazy<T> {
+    /// Write the XDR held verbatim.
+    fn write_xdr<W: Write>(&self, w: &mut Limited<W>) -> Result<(), Error> {
+        w.consume_len(self.xdr.len())?;
+        w.write_all(&self.xdr)?;
+        Ok(())
+    }
+
+    fn xdr_len_at_depth(&self, _depth: u32) -> Result<usize, Error> {
+        Ok(self.xdr.len())
+    }
+}
@@ -0,0 +1,107 @@
+//# Keys of ledger entries.
+
+#[cfg(feature = "sha2")]
+use super::{hash::Sha256Writer, Error, Limited, Limits, WriteXdr};
+use super::{
+    LedgerEntry, LedgerEntryData, LedgerKey, LedgerKeyAccount, LedgerKeyClaimableBalance,
+    LedgerKeyConfigSetting, LedgerKeyContractCode, LedgerKeyContractData, LedgerKeyData,
+    LedgerKeyLiquidityPool, LedgerKeyOffer, LedgerKeyTrustLine, LedgerKeyTtl,
+};
+
+impl LedgerEntryData {
+    /// Returns the key of the ledger entry with the data.
+    #[must_use]
+    pub fn to_key(&self) -> LedgerKey {
+        match self {
+            Self::Account(entry) => LedgerKey::Account(LedgerKeyAccount {
+                account_id: entry.account_id.clone(),
+            }),
+            Self::Trustline(entry) => LedgerKey::Trustline(LedgerKeyTrustLine {
+                account_id: entry.account_id.clone(),
+                asset: entry.asset.clone(),
+            }),
+            Self::Offer(entry) => LedgerKey::Offer(LedgerKeyOffer {
+                seller_id: entry.seller_id.clone(),
+                offer_id: entry.offer_id,
+            }),
+            Self::Data(entry) => LedgerKey::Data(LedgerKeyData {
+                account_id: entry.account_id.clone(),
+                data_name: entry.data_name.clone(),
+            }),
+            Self::ClaimableBalance(entry) => {
+                LedgerKey::ClaimableBalance(LedgerKeyClaimableBalance {
+                    balance_id: entry.balance_id.clone(),
+                })
+            }
+            Self::LiquidityPool(entry) => LedgerKey::LiquidityPool(LedgerKeyLiquidityPool {
+                liquidity_pool_id: entry.liquidity_pool_id.clone(),
+            }),
+            Self::ContractData(entry) => LedgerKey::ContractData(LedgerKeyContractData {
+                contract: entry.contract.clone(),
+                key: entry.key.clone(),
+                durability: entry.durability,
+            }),
+            Self::ContractCode(entry) => LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: entry.hash.clone(),
+            }),
+            Self::ConfigSetting(entry) => LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+                config_setting_id: entry.discriminant(),
+            }),
+            Self::Ttl(entry) => LedgerKey::Ttl(LedgerKeyTtl {
+                key_hash: entry.key_hash.clone(),
+            }),
+        }
+    }
+}
+
+impl LedgerEntry {
+    /// Returns the key of the ledger entry, see [`LedgerEntryData::to_key`].
+    #[must_use]
+    pub fn to_key(&self) -> LedgerKey {
+        self.data.to_key()
+    }
+}
+
+impl From<&LedgerEntryData> for LedgerKey {
+    fn from(data: &LedgerEntryData) -> Self {
+        data.to_key()
+    }
+}
+
+impl From<&LedgerEntry> for LedgerKey {
+    fn from(entry: &LedgerEntry) -> Self {
+        entry.to_key()
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl LedgerKey {
+    /// Returns the key of the TTL entry of the ledger entry with the key, the
+    /// sha256 hash of the key.
+    ///
+    /// Returns `None` if the key is not of a contract data or contract code
+    /// entry, the only entries that have a TTL.
+    ///
+    /// ### Errors
+    ///
+    /// If the key cannot be encoded.
+    pub fn ttl_key(&self) -> Result<Option<LedgerKeyTtl>, Error> {
+        match self {
+            Self::ContractData(_) | Self::ContractCode(_) => {
+                let mut w = Limited::new(Sha256Writer::new(), Limits::none());
+                self.write_xdr(&mut w)?;
+                Ok(Some(LedgerKeyTtl {
+                    key_hash: w.inner.finalize(),
+                }))
+            }
+            Self::Account(_)
+            | Self::Trustline(_)
+            | Self::Offer(_)
+            | Self::Data(_)
+            | Self::ClaimableBalance(_)
+            | Self::LiquidityPool(_)
+            | Self::ConfigSetting(_)
+            | Self::Ttl(_) => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//# Signing of transactions and Soroban authorization entries with ed25519
+//# keys, and verification of the signatures of the signers of transactions.
+
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::{
+    DecoratedSignature, Error, Hash, ScBytes, ScMap, ScMapEntry, ScSymbol, ScVal, ScVec, Signature,
+    SignatureHint, SignerKey, SignerKeyEd25519SignedPayload, SorobanAuthorizationEntry,
+    SorobanCredentials, TransactionEnvelope, Uint256, VecM,
+};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Returns the signing key for the secret seed strkey, `S...`.
+///
+/// ### Errors
+///
+/// If the secret is not a valid secret seed strkey.
+pub fn signing_key_from_secret(secret: &str) -> Result<SigningKey, Error> {
+    let stellar_strkey::ed25519::PrivateKey(seed) =
+        stellar_strkey::ed25519::PrivateKey::from_string(secret)?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+fn last_4(bytes: &[u8; 32]) -> [u8; 4] {
+    [bytes[28], bytes[29], bytes[30], bytes[31]]
+}
+
+fn sign_ed25519(signing_key: &SigningKey, message: &[u8]) -> Signature {
+    // An ed25519 signature is 64 bytes, the maximum length of a signature.
+    Signature(
+        signing_key
+            .sign(message)
+            .to_bytes()
+            .try_into()
+            .unwrap_or_else(|_| unreachable!()),
+    )
+}
+
+impl SignerKey {
+    /// Returns the hint of the signatures of the signer.
+    ///
+    /// The hint is the last 4 bytes of the key, and for signed payload signers
+    /// is that combined with the last 4 bytes of the payload using xor.
+    #[must_use]
+    pub fn hint(&self) -> SignatureHint {
+        match self {
+            SignerKey::Ed25519(Uint256(k))
+            | SignerKey::PreAuthTx(Uint256(k))
+            | SignerKey::HashX(Uint256(k)) => SignatureHint(last_4(k)),
+            SignerKey::Ed25519SignedPayload(SignerKeyEd25519SignedPayload {
+                ed25519: Uint256(k),
+                payload,
+            }) => {
+                // Payloads shorter than 4 bytes are padded with zeros at the
+                // end.
+                let mut payload_hint = [0u8; 4];
+                let payload = payload.as_slice();
+                let start = payload.len().saturating_sub(4);
+                payload_hint[..payload.len() - start].copy_from_slice(&payload[start..]);
+                let mut hint = last_4(k);
+                for (h, p) in hint.iter_mut().zip(payload_hint) {
+                    *h ^= p;
+                }
+                SignatureHint(hint)
+            }
+        }
+    }
+}
+
+impl DecoratedSignature {
+    /// Signs the message with the signing key. For a signature of a
+    /// transaction the message is the transaction hash.
+    #[must_use]
+    pub fn sign(signing_key: &SigningKey, message: &[u8]) -> Self {
+        DecoratedSignature {
+            hint: SignatureHint(last_4(signing_key.verifying_key().as_bytes())),
+            signature: sign_ed25519(signing_key, message),
+        }
+    }
+
+    /// Returns true if the signature is a signature of the signer, for a
+    /// transaction with the hash.
+    ///
+    /// - `Ed25519` signers sign the transaction hash.
+    /// - `HashX` signers are satisfied by a signature that is the preimage of
+    ///   the hash.
+    /// - `Ed25519SignedPayload` signers sign the payload.
+    /// - `PreAuthTx` signers have no signatures, see
+    ///   [`TransactionEnvelope::is_signed_by`].
+    #[must_use]
+    pub fn verify(&self, signer: &SignerKey, tx_hash: &Hash) -> bool {
+        if self.hint != signer.hint() {
+            return false;
+        }
+        match signer {
+            SignerKey::Ed25519(k) => verify_ed25519(k, &tx_hash.0, &self.signature),
+            SignerKey::PreAuthTx(_) => false,
+            SignerKey::HashX(Uint256(x)) => {
+                Sha256::digest(self.signature.0.as_slice())[..] == x[..]
+            }
+            SignerKey::Ed25519SignedPayload(SignerKeyEd25519SignedPayload { ed25519, payload }) => {
+                verify_ed25519(ed25519, payload, &self.signature)
+            }
+        }
+    }
+}
+
+fn verify_ed25519(key: &Uint256, message: &[u8], signature: &Signature) -> bool {
+    let Ok(key) = VerifyingKey::from_bytes(&key.0) else {
+        return false;
+    };
+    let Ok(signature) = ed25519_dalek::Signature::from_slice(&signature.0) else {
+        return false;
+    };
+    key.verify_strict(message, &signature).is_ok()
+}
+
+impl TransactionEnvelope {
+    /// Returns the signatures of the envelope.
+    #[must_use]
+    pub fn signatures(&self) -> &VecM<DecoratedSignature, 20> {
+        match self {
+            TransactionEnvelope::TxV0(e) => &e.signatures,
+            TransactionEnvelope::Tx(e) => &e.signatures,
+            TransactionEnvelope::TxFeeBump(e) => &e.signatures,
+        }
+    }
+
+    fn signatures_mut(&mut self) -> &mut VecM<DecoratedSignature, 20> {
+        match self {
+            TransactionEnvelope::TxV0(e) => &mut e.signatures,
+            TransactionEnvelope::Tx(e) => &mut e.signatures,
+            TransactionEnvelope::TxFeeBump(e) => &mut e.signatures,
+        }
+    }
+
+    /// Signs the transaction in the envelope for the network with the
+    /// passphrase, adding the signature to the signatures of the envelope.
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction cannot be encoded to be hashed, or the envelope
+    /// already has the maximum number of signatures.
+    pub fn sign(
+        &mut self,
+        network_passphrase: &str,
+        signing_key: &SigningKey,
+    ) -> Result<(), Error> {
+        let hash = self.hash(network_passphrase)?;
+        let signature = DecoratedSignature::sign(signing_key, &hash.0);
+        let signatures = self.signatures_mut();
+        let mut v = signatures.to_vec();
+        v.push(signature);
+        *signatures = v.try_into()?;
+        Ok(())
+    }
+
+    /// Returns true if the signer has signed the transaction in the envelope
+    /// for the network with the passphrase.
+    ///
+    /// `PreAuthTx` signers have signed a transaction if the key is the hash of
+    /// the transaction. Other signers have signed a transaction if any of the
+    /// signatures of the envelope verify, see [`DecoratedSignature::verify`].
+    ///
+    /// ### Errors
+    ///
+    /// If the transaction cannot be encoded to be hashed.
+    pub fn is_signed_by(
+        &self,
+        network_passphrase: &str,
+        signer: &SignerKey,
+    ) -> Result<bool, Error> {
+        let hash = self.hash(network_passphrase)?;
+        if let SignerKey::PreAuthTx(Uint256(k)) = signer {
+            return Ok(*k == hash.0);
+        }
+        Ok(self.signatures().iter().any(|s| s.verify(signer, &hash)))
+    }
+}
+
+const PUBLIC_KEY: &str = "public_key";
+const SIGNATURE: &str = "signature";
+
+impl SorobanAuthorizationEntry {
+    /// Returns the public keys and signatures of the signers of the entry.
+    ///
+    /// The signatures are decoded from the signature of the credentials, in
+    /// the format used by Stellar accounts, a vec of maps each with a
+    /// `public_key` and a `signature`. A void signature has no signatures.
+    ///
+    /// ### Errors
+    ///
+    /// If the entry has source account credentials, or the signature of the
+    /// credentials is not in the format.
+    pub fn signatures(&self) -> Result<Vec<(Uint256, Signature)>, Error> {
+        let SorobanCredentials::Address(credentials) = &self.credentials else {
+            return Err(Error::Unsupported);
+        };
+        match &credentials.signature {
+            ScVal::Void => Ok(Vec::new()),
+            ScVal::Vec(Some(ScVec(signatures))) => {
+                signatures.iter().map(decode_account_signature).collect()
+            }
+            _ => Err(Error::Invalid),
+        }
+    }
+
+    /// Signs the root invocation of the entry for the network with the
+    /// passphrase, adding the signature to the signatures of the credentials.
+    ///
+    /// The signatures are kept ordered by public key, as accounts require, and
+    /// a signature replaces any existing signature of the same key. The nonce
+    /// and signature expiration ledger of the credentials are signed, and
+    /// must be set before signing.
+    ///
+    /// ### Errors
+    ///
+    /// If the entry has source account credentials, the existing signatures
+    /// are not in the format of [`SorobanAuthorizationEntry::signatures`], or
+    /// the payload cannot be encoded.
+    pub fn sign(
+        &mut self,
+        network_passphrase: &str,
+        signing_key: &SigningKey,
+    ) -> Result<(), Error> {
+        let hash = self
+            .signature_payload_hash(network_passphrase)?
+            .ok_or(Error::Unsupported)?;
+        let public_key = Uint256(signing_key.verifying_key().to_bytes());
+        let signature = sign_ed25519(signing_key, &hash.0);
+        let mut signatures = self.signatures()?;
+        match signatures.binary_search_by(|(k, _)| k.cmp(&public_key)) {
+            Ok(i) => signatures[i].1 = signature,
+            Err(i) => signatures.insert(i, (public_key, signature)),
+        }
+        let signatures = signatures
+            .into_iter()
+            .map(encode_account_signature)
+            .collect::<Result<Vec<_>, _>>()?;
+        if let SorobanCredentials::Address(credentials) = &mut self.credentials {
+            credentials.signature = ScVal::Vec(Some(ScVec(signatures.try_into()?)));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the entry has a signature of the public key that
+    /// verifies for the root invocation on the network with the passphrase.
+    ///
+    /// ### Errors
+    ///
+    /// If the entry has source account credentials, the signatures are not in
+    /// the format of [`SorobanAuthorizationEntry::signatures`], or the payload
+    /// cannot be encoded.
+    pub fn is_signed_by(
+        &self,
+        network_passphrase: &str,
+        public_key: &Uint256,
+    ) -> Result<bool, Error> {
+        let hash = self
+            .signature_payload_hash(network_passphrase)?
+            .ok_or(Error::Unsupported)?;
+        Ok(self
+            .signatures()?
+            .iter()
+            .any(|(k, s)| k == public_key && verify_ed25519(k, &hash.0, s)))
+    }
+}
+
+fn decode_account_signature(v: &ScVal) -> Result<(Uint256, Signature), Error> {
+    let ScVal::Map(Some(ScMap(entries))) = v else {
+        return Err(Error::Invalid);
+    };
+    match entries.as_slice() {
+        [ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(public_key_key)),
+            val: ScVal::Bytes(ScBytes(public_key)),
+        }, ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(signature_key)),
+            val: ScVal::Bytes(ScBytes(signature)),
+        }] if public_key_key.as_slice() == PUBLIC_KEY.as_bytes()
+            && signature_key.as_slice() == SIGNATURE.as_bytes() =>
+        {
+            Ok((
+                public_key.as_slice().try_into()?,
+                Signature(signature.as_slice().try_into()?),
+            ))
+        }
+        _ => Err(Error::Invalid),
+    }
+}
+
+fn encode_account_signature((public_key, signature): (Uint256, Signature)) -> Result<ScVal, Error> {
+    let entries = [
+        ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(PUBLIC_KEY.try_into()?)),
+            val: ScVal::Bytes(ScBytes(public_key.0.try_into()?)),
+        },
+        ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(SIGNATURE.try_into()?)),
+            val: ScVal::Bytes(ScBytes(signature.0.into_vec().try_into()?)),
+        },
+    ];
+    Ok(ScVal::Map(Some(ScMap(entries.to_vec().try_into()?))))
+}
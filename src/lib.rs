@@ -34,26 +34,39 @@
 //! Teirs of functionality:
 //!
 //! 1. `std` – The std feature provides all functionality (types, encode,
-//! decode), and is the default feature set.
+//!    decode), and is the default feature set.
 //! 2. `alloc` – The alloc feature uses `Box` and `Vec` types for recursive
-//! references and arrays, and is automatically enabled if the std feature is
-//! enabled. The default global allocator is used. Support for a custom
-//! allocator will be added in [#39]. No encode or decode capability exists,
-//! only types. Encode and decode capability will be added in [#46].
+//!    references and arrays, and is automatically enabled if the std feature is
+//!    enabled. The default global allocator is used. Support for a custom
+//!    allocator will be added in [#39]. Encode and decode are supported using
+//!    the crate's own minimal `io::Read` and `io::Write` traits, implemented
+//!    for `&[u8]`, `&mut [u8]`, and `Vec<u8>`. Iterators over streams of values
+//!    require std.
 //! 3. If std or alloc are not enabled recursive and array types requires static
-//! lifetime values. No encode or decode capability exists. Encode and decode
-//! capability will be added in [#47].
+//!    lifetime values. Encode is supported, including into a fixed size buffer
+//!    with `WriteXdr::write_xdr_to_slice`. Decode is not supported because
+//!    decoded variable length values require allocation.
 //!
 //! [#39]: https://github.com/stellar/rs-stellar-xdr/issues/39
-//! [#46]: https://github.com/stellar/rs-stellar-xdr/issues/46
-//! [#47]: https://github.com/stellar/rs-stellar-xdr/issues/47
 //!
 //! Ancillary functionality:
 //!
 //! 1. `base64` – Enables support for base64 encoding and decoding.
 //! 2. `serde` – Enables support for serializing and deserializing types with
-//! the serde crate.
+//!    the serde crate.
 //! 3. `arbitrary` – Enables support for interop with the arbitrary crate.
+//! 4. `tokio` – Enables support for async encoding and decoding with the tokio
+//!    crate's `AsyncRead` and `AsyncWrite` traits, using `AsyncLimited`.
+//! 5. `bytes` – Enables support for encoding into and decoding from the bytes
+//!    crate's `Buf` and `BufMut` traits, and for decoding into the types of the
+//!    `shared` module, that hold opaque and string data as `Bytes` sharing the
+//!    buffer they are decoded from.
+//! 6. `sha2` – Enables computing transaction hashes, signature payloads, the
+//!    ids of contracts, claimable balances and liquidity pools, and the keys of
+//!    TTL entries with the sha2 crate.
+//! 7. `ed25519` – Enables signing transaction envelopes and Soroban
+//!    authorization entries, and verifying their signatures, with the
+//!    ed25519-dalek crate.
 //!
 //! Channels of XDR:
 //!
@@ -92,6 +105,28 @@
 //! ```console
 //! stellar-xdr decode --type BucketEntry --input stream-framed --output json-formatted bucket.xdr
 //! ```
+//!
+//! Count the entries of a bucket file by ledger entry type:
+//! ```console
+//! stellar-xdr bucket stats bucket.xdr
+//! ```
+//!
+//! List the contract data entries of a bucket file:
+//! ```console
+//! stellar-xdr bucket list --type ContractData --output json-formatted bucket.xdr
+//! ```
+//!
+//! Select the amounts of the payments in a `TransactionEnvelope`:
+//! ```console
+//! stellar-xdr query --type TransactionEnvelope --path 'tx.tx.operations[*].body.payment.amount' << -
+//! AAAAA...
+//! -
+//! ```
+//!
+//! Compare two `TransactionEnvelope`s:
+//! ```console
+//! stellar-xdr diff --type TransactionEnvelope a.txt b.txt
+//! ```
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
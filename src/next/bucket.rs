@@ -0,0 +1,146 @@
+//# Bucket files, the streams of ledger entries that make up the state of the
+//# ledger in history archives.
+
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use super::{BucketEntry, LedgerEntryType, LedgerKey};
+#[cfg(feature = "std")]
+use super::{BucketMetadata, Error, Frame, Limits, ReadXdrIter};
+
+impl BucketEntry {
+    /// Returns the key of the ledger entry that the bucket entry is for.
+    ///
+    /// Returns `None` for the metadata entry, that is not for a ledger entry.
+    #[must_use]
+    pub fn to_key(&self) -> Option<LedgerKey> {
+        match self {
+            Self::Liveentry(entry) | Self::Initentry(entry) => Some(entry.to_key()),
+            Self::Deadentry(key) => Some(key.clone()),
+            Self::Metaentry(_) => None,
+        }
+    }
+
+    /// Returns the type of the ledger entry that the bucket entry is for.
+    ///
+    /// Returns `None` for the metadata entry, that is not for a ledger entry.
+    #[must_use]
+    pub fn ledger_entry_type(&self) -> Option<LedgerEntryType> {
+        match self {
+            Self::Liveentry(entry) | Self::Initentry(entry) => Some(entry.data.discriminant()),
+            Self::Deadentry(key) => Some(key.discriminant()),
+            Self::Metaentry(_) => None,
+        }
+    }
+}
+
+/// `Bucket` reads the entries of a bucket file, a stream of records each
+/// containing a [`BucketEntry`].
+///
+/// The first entry of a bucket is the [`BucketEntry::Metaentry`] with the
+/// [`BucketMetadata`] of the bucket, that is read and validated when the
+/// bucket is opened. The bucket is an iterator over the remaining entries,
+/// the [`BucketEntry::Liveentry`], [`BucketEntry::Initentry`] and
+/// [`BucketEntry::Deadentry`] entries.
+///
+/// Bucket files in history archives are gzip compressed, and must be
+/// decompressed before they are read.
+#[cfg(feature = "std")]
+pub struct Bucket<R: Read> {
+    metadata: BucketMetadata,
+    entries: ReadXdrIter<R, Frame<BucketEntry>>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Bucket<R> {
+    /// Opens the bucket in the reader, reading its metadata entry.
+    ///
+    /// The limits apply to the bucket as a whole, and not to each entry.
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`Error::Invalid`] if the bucket is empty or the first entry is
+    /// not a metadata entry, or an error if the first entry cannot be read.
+    pub fn new(r: R, limits: Limits) -> Result<Self, Error> {
+        Self::new_with_error_context(r, limits, false)
+    }
+
+    /// Opens the bucket in the reader, as [`Bucket::new`], with errors
+    /// reading entries returned with the context describing where in the
+    /// bucket they occurred, see [`Limited::with_error_context`].
+    ///
+    /// ### Errors
+    ///
+    /// As [`Bucket::new`].
+    pub fn new_with_error_context(
+        r: R,
+        limits: Limits,
+        error_context: bool,
+    ) -> Result<Self, Error> {
+        let mut entries = ReadXdrIter::new(r, limits, error_context);
+        match entries.next() {
+            Some(Ok(Frame(BucketEntry::Metaentry(metadata)))) => Ok(Self { metadata, entries }),
+            Some(Ok(_)) | None => Err(Error::Invalid),
+            Some(Err(e)) => Err(e),
+        }
+    }
+
+    /// Returns the metadata of the bucket.
+    #[must_use]
+    pub fn metadata(&self) -> &BucketMetadata {
+        &self.metadata
+    }
+
+    /// Returns the protocol version of the ledger the bucket was written at.
+    #[must_use]
+    pub fn protocol_version(&self) -> u32 {
+        self.metadata.ledger_version
+    }
+
+    /// Returns the entries of the bucket for ledger entries of the type.
+    pub fn entries_of_type(
+        self,
+        t: LedgerEntryType,
+    ) -> impl Iterator<Item = Result<BucketEntry, Error>> {
+        self.filter(move |entry| match entry {
+            Ok(entry) => entry.ledger_entry_type() == Some(t),
+            Err(_) => true,
+        })
+    }
+
+    /// Returns the entries of the bucket for the ledger entry with the key.
+    ///
+    /// A bucket contains at most one entry for each key, so no more of the
+    /// bucket is read after the entry with the key.
+    pub fn entries_with_key(
+        self,
+        key: LedgerKey,
+    ) -> impl Iterator<Item = Result<BucketEntry, Error>> {
+        self.filter(move |entry| match entry {
+            Ok(entry) => entry.to_key().as_ref() == Some(&key),
+            Err(_) => true,
+        })
+        .scan(false, |found, entry| {
+            if *found {
+                return None;
+            }
+            *found = entry.is_ok();
+            Some(entry)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for Bucket<R> {
+    type Item = Result<BucketEntry, Error>;
+
+    // Next reads the next entry of the bucket. A metadata entry is only valid
+    // as the first entry of the bucket, and is an error anywhere else.
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.entries.next()? {
+            Ok(Frame(BucketEntry::Metaentry(_))) => Some(Err(Error::Invalid)),
+            Ok(Frame(entry)) => Some(Ok(entry)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -1,6 +1,36 @@
 mod generated;
 pub use generated::*;
 
+mod frame;
+pub use frame::*;
+
+#[cfg(feature = "alloc")]
+mod lazy;
+#[cfg(feature = "alloc")]
+pub use lazy::*;
+
+#[cfg(feature = "tokio")]
+mod async_limited;
+#[cfg(feature = "tokio")]
+pub use async_limited::*;
+
+#[cfg(feature = "alloc")]
+mod query;
+#[cfg(feature = "alloc")]
+pub use query::*;
+
+mod hash;
+pub use hash::*;
+
+#[cfg(feature = "sha2")]
+mod hash_id;
+
+mod ledger_key;
+
+mod bucket;
+#[cfg(feature = "std")]
+pub use bucket::*;
+
 mod str;
 
 mod scval_conversions;
@@ -9,5 +39,18 @@ pub use scval_conversions::*;
 mod scval_validations;
 pub use scval_validations::*;
 
+mod fee;
+pub use fee::*;
+
 #[cfg(feature = "alloc")]
 mod scmap;
+
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+
+#[cfg(feature = "ed25519")]
+mod sign;
+#[cfg(feature = "ed25519")]
+pub use sign::*;
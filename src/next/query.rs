@@ -0,0 +1,257 @@
+//# Path expressions that select values nested within a value, such as
+//# `tx.operations[*].body.payment.amount`.
+
+use core::{any::Any, fmt};
+
+use super::{schema, PathSegment, Type, TypeVariant, Visit};
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// `Query` is a path expression that selects values nested within a value,
+/// such as `tx.operations[*].body.payment.amount`.
+///
+/// Names in the path are the names of struct fields and union arms as they
+/// appear in the JSON form of the value. An index in brackets selects an
+/// element of an array, and `[*]` selects every element. An empty query
+/// selects the value itself.
+///
+/// Values are found using [`Visit`], and selected values are cloned.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Query(Vec<QuerySegment>);
+
+/// `QuerySegment` is a single step in a [`Query`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum QuerySegment {
+    /// A field of a struct, or an arm of a union in snake case.
+    Name(String),
+    /// An element of an array.
+    Index(usize),
+    /// Every element of an array.
+    AnyIndex,
+}
+
+/// `QueryParseError` is returned when parsing a [`Query`] from a string that
+/// is not a valid query.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct QueryParseError;
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "query invalid")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QueryParseError {}
+
+impl Query {
+    #[must_use]
+    pub fn new(segments: Vec<QuerySegment>) -> Self {
+        Self(segments)
+    }
+
+    #[must_use]
+    pub fn segments(&self) -> &[QuerySegment] {
+        &self.0
+    }
+
+    /// Returns true if the path of a visited value is selected by the query.
+    ///
+    /// The [`PathSegment::Type`] segments of the path are ignored, so that a
+    /// query selects the same values in a [`Type`] as in the value it
+    /// contains.
+    #[must_use]
+    pub fn matches(&self, path: &[PathSegment]) -> bool {
+        let mut path = path.iter().filter(|s| !matches!(s, PathSegment::Type(_)));
+        let mut segments = self.0.iter();
+        loop {
+            match (segments.next(), path.next()) {
+                (None, None) => return true,
+                (Some(q), Some(p)) if q.matches(p) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    /// Returns the values of type `T` selected by the query, in the order
+    /// they are visited.
+    ///
+    /// Values of other types at the selected paths are ignored, which allows
+    /// selecting a value nested within an `Option` or typedef at the same path.
+    #[must_use]
+    pub fn select<T: Any + Clone>(&self, value: &impl Visit) -> Vec<T> {
+        let mut selected = Vec::new();
+        value.visit(&mut |path: &[PathSegment], v: &dyn Any| {
+            if let Some(v) = v.downcast_ref::<T>() {
+                if self.matches(path) {
+                    selected.push(v.clone());
+                }
+            }
+        });
+        selected
+    }
+
+    /// Returns the values selected by the query as [`Type`]s, in the order
+    /// they are visited.
+    ///
+    /// At each selected path the outermost value that is one of the XDR types
+    /// is returned, see [`Type::from_any`]. Paths where no value is one of the
+    /// XDR types, such as a field containing an array, are not returned.
+    #[must_use]
+    pub fn select_types(&self, value: &impl Visit) -> Vec<Type> {
+        let mut selected = Vec::new();
+        let mut last: Option<Vec<PathSegment>> = None;
+        value.visit(&mut |path: &[PathSegment], v: &dyn Any| {
+            if last.as_deref() == Some(path) || !self.matches(path) {
+                return;
+            }
+            if let Some(t) = Type::from_any(v) {
+                last = Some(path.to_vec());
+                selected.push(t);
+            }
+        });
+        selected
+    }
+
+    /// Resolves the query against the schema of the type, returning the type
+    /// of the values selected by each prefix of the query, starting with the
+    /// type itself for the empty prefix.
+    ///
+    /// Returns `None` if the query does not match any path in the type. The
+    /// returned types may be typedefs or optionals, which do not add segments
+    /// to the path of the values they contain.
+    #[must_use]
+    pub fn resolve(&self, variant: TypeVariant) -> Option<Vec<schema::TypeRef>> {
+        let mut ty = schema::TypeRef::Type(variant);
+        let mut types = vec![ty];
+        for segment in &self.0 {
+            ty = segment.resolve(ty)?;
+            types.push(ty);
+        }
+        Some(types)
+    }
+}
+
+impl QuerySegment {
+    /// Returns true if the segment of a visited value's path is selected by
+    /// the query segment.
+    #[must_use]
+    pub fn matches(&self, segment: &PathSegment) -> bool {
+        match (self, segment) {
+            (Self::Name(name), PathSegment::Field(field)) => name == field,
+            (Self::Name(name), PathSegment::Arm(arm)) => snake_case(arm).eq(name.chars()),
+            (Self::Index(index), PathSegment::Index(i)) => index == i,
+            (Self::AnyIndex, PathSegment::Index(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the type of the values selected by the segment within values
+    /// of the type, or `None` if the segment does not match the type.
+    fn resolve(&self, ty: schema::TypeRef) -> Option<schema::TypeRef> {
+        use schema::{Kind, TypeRef};
+        match ty {
+            TypeRef::Optional(ty) => self.resolve(*ty),
+            TypeRef::FixedArray(ty, _) | TypeRef::VarArray(ty, _) => {
+                matches!(self, Self::Index(_) | Self::AnyIndex).then_some(*ty)
+            }
+            TypeRef::Type(variant) => match (self, variant.schema().kind) {
+                (_, Kind::Typedef(ty)) => self.resolve(ty),
+                (Self::Name(name), Kind::Struct(fields)) => {
+                    fields.iter().find(|f| f.name == name).map(|f| f.ty)
+                }
+                (Self::Name(name), Kind::Union(union)) => {
+                    union
+                        .arms
+                        .iter()
+                        .find(|a| snake_case(a.name).eq(name.chars()))?
+                        .ty
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Returns the name in snake case, the same as the names of union arms in the
+/// JSON form.
+pub(crate) fn snake_case(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.char_indices().flat_map(|(i, c)| {
+        (i > 0 && c.is_uppercase())
+            .then_some('_')
+            .into_iter()
+            .chain(c.to_lowercase())
+    })
+}
+
+/// Converts the path of a visited value into the query that selects it.
+impl From<&[PathSegment]> for Query {
+    fn from(path: &[PathSegment]) -> Self {
+        Self(
+            path.iter()
+                .filter_map(|segment| match segment {
+                    PathSegment::Type(_) => None,
+                    PathSegment::Field(name) => Some(QuerySegment::Name((*name).to_string())),
+                    PathSegment::Arm(name) => Some(QuerySegment::Name(snake_case(name).collect())),
+                    PathSegment::Index(index) => Some(QuerySegment::Index(*index)),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl core::str::FromStr for Query {
+    type Err = QueryParseError;
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        if s.is_empty() {
+            return Ok(Self(segments));
+        }
+        for (i, part) in s.split('.').enumerate() {
+            let (name, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+            if !name.is_empty() {
+                if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(QueryParseError);
+                }
+                segments.push(QuerySegment::Name(name.to_string()));
+            } else if i > 0 || rest.is_empty() {
+                return Err(QueryParseError);
+            }
+            while !rest.is_empty() {
+                let end = rest.find(']').ok_or(QueryParseError)?;
+                let index = rest.get(1..end).filter(|_| rest.starts_with('['));
+                segments.push(match index.ok_or(QueryParseError)? {
+                    "*" => QuerySegment::AnyIndex,
+                    index => QuerySegment::Index(index.parse().map_err(|_| QueryParseError)?),
+                });
+                rest = &rest[end + 1..];
+            }
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                QuerySegment::Name(name) => {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{name}")?;
+                }
+                QuerySegment::Index(index) => write!(f, "[{index}]")?,
+                QuerySegment::AnyIndex => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
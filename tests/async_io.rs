@@ -0,0 +1,217 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "tokio")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use core::{
+    future::poll_fn,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use futures_core::Stream;
+use stellar_xdr::{
+    AsyncLimited, Error, FramedWriter, Limited, Limits, ReadXdr, ReadXdrStream, ScVal, Uint256,
+    WriteXdr,
+};
+use tokio::io::AsyncWriteExt;
+
+async fn collect<R, S>(mut s: ReadXdrStream<'_, R, S>) -> Vec<Result<S, Error>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    S: stellar_xdr::ReadXdr,
+{
+    let mut values = Vec::new();
+    while let Some(v) = poll_fn(|cx| Pin::new(&mut s).poll_next(cx)).await {
+        values.push(v);
+    }
+    values
+}
+
+#[tokio::test]
+async fn read_xdr() -> Result<(), Error> {
+    let data = [0u8, 0, 0, 1, 0, 0, 0, 2];
+    let mut r = AsyncLimited::new(&data[..], Limits::none());
+    assert_eq!(r.read_xdr::<u32>().await?, 1);
+    assert_eq!(r.read_xdr::<u32>().await?, 2);
+    assert_eq!(
+        r.read_xdr::<u32>().await,
+        Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_xdr_across_partial_reads() -> Result<(), Error> {
+    let v = ScVal::Bytes(vec![7u8; 1000].try_into()?);
+    let xdr = v.to_xdr(Limits::none())?;
+
+    // The duplex only transfers a few bytes at a time, so the value is read
+    // across many reads.
+    let (mut writer, reader) = tokio::io::duplex(3);
+    let mut r = AsyncLimited::new(reader, Limits::none());
+    let (written, read) = tokio::join!(
+        async {
+            writer.write_all(&xdr).await?;
+            writer.write_all(&xdr).await
+        },
+        async {
+            let first = r.read_xdr::<ScVal>().await?;
+            let second = r.read_xdr::<ScVal>().await?;
+            Ok::<_, Error>((first, second))
+        },
+    );
+    written?;
+    assert_eq!(read?, (v.clone(), v));
+    Ok(())
+}
+
+// Counts the number of times a value is decoded.
+static DECODES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, PartialEq)]
+struct CountedScVal(ScVal);
+
+impl ReadXdr for CountedScVal {
+    fn read_xdr<R: std::io::Read>(r: &mut Limited<R>) -> Result<Self, Error> {
+        DECODES.fetch_add(1, Ordering::Relaxed);
+        Ok(CountedScVal(ScVal::read_xdr(r)?))
+    }
+}
+
+#[tokio::test]
+async fn read_xdr_large_value_across_small_reads() -> Result<(), Error> {
+    let v = ScVal::Bytes(vec![7u8; 1 << 16].try_into()?);
+    let xdr = v.to_xdr(Limits::none())?;
+
+    // The duplex transfers the value across about a thousand reads, but the
+    // value is not decoded again until the whole opaque is buffered.
+    let (mut writer, reader) = tokio::io::duplex(64);
+    let mut r = AsyncLimited::new(reader, Limits::none());
+    let (written, read) = tokio::join!(
+        async {
+            writer.write_all(&xdr).await?;
+            writer.shutdown().await
+        },
+        r.read_xdr::<CountedScVal>(),
+    );
+    written?;
+    assert_eq!(read?, CountedScVal(v));
+    assert!(DECODES.load(Ordering::Relaxed) <= 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_xdr_with_error_context() -> Result<(), Error> {
+    let v = ScVal::Vec(Some([ScVal::U32(1)].to_vec().try_into()?));
+    let mut xdr = v.to_xdr(Limits::none())?;
+    // The discriminant of the element of the vec.
+    xdr[15] = 0xff;
+    let mut r = AsyncLimited::new(&xdr[..], Limits::none()).with_error_context();
+    let Err(Error::Context(c)) = r.read_xdr::<ScVal>().await else {
+        panic!("error without context");
+    };
+    assert_eq!(c.error(), &Error::Invalid);
+    assert_eq!(c.value_offset(), 12);
+
+    let xdr = v.to_xdr(Limits::none())?;
+    let mut r = AsyncLimited::new(&xdr[..14], Limits::none()).with_error_context();
+    let Err(Error::Context(c)) = r.read_xdr::<ScVal>().await else {
+        panic!("error without context");
+    };
+    assert_eq!(
+        c.error(),
+        &Error::Io(std::io::ErrorKind::UnexpectedEof.into())
+    );
+    assert_eq!(r.buffer(), &[] as &[u8]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_xdr_to_end() -> Result<(), Error> {
+    let data = [0u8, 0, 0, 1];
+    let mut r = AsyncLimited::new(&data[..], Limits::none());
+    assert_eq!(r.read_xdr_to_end::<u32>().await?, 1);
+
+    let data = [0u8, 0, 0, 1, 0];
+    let mut r = AsyncLimited::new(&data[..], Limits::none());
+    assert_eq!(r.read_xdr_to_end::<u32>().await, Err(Error::Invalid));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_limits() {
+    let data = [0u8, 0, 0, 1, 0, 0, 0, 2];
+    let mut r = AsyncLimited::new(&data[..], Limits::len(6));
+    assert_eq!(r.read_xdr::<u32>().await, Ok(1));
+    assert_eq!(r.limits().len, 2);
+    assert_eq!(r.read_xdr::<u32>().await, Err(Error::LengthLimitExceeded));
+
+    let mut r = AsyncLimited::new(&data[..], Limits::depth(0));
+    assert_eq!(r.read_xdr::<u32>().await, Err(Error::DepthLimitExceeded));
+}
+
+#[tokio::test]
+async fn read_framed_stream() -> Result<(), Error> {
+    let v1 = Uint256([1; 32]);
+    let v2 = Uint256([2; 32]);
+    let mut w = FramedWriter::new(Vec::new(), Limits::none());
+    w.write_iter([v1.clone(), v2.clone()])?;
+    let data = w.into_inner();
+
+    let mut r = AsyncLimited::new(&data[..], Limits::none());
+    let values = collect(r.read_xdr_framed_stream::<Uint256>())
+        .await
+        .into_iter()
+        .map(|f| f.map(|f| f.0))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, [v1, v2]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_stream_truncated() {
+    let data = [0u8, 0, 0, 1, 0, 0];
+    let mut r = AsyncLimited::new(&data[..], Limits::none());
+    let values = collect(r.read_xdr_stream::<u32>()).await;
+    assert_eq!(
+        values,
+        [
+            Ok(1),
+            Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn write_xdr() -> Result<(), Error> {
+    let mut w = AsyncLimited::new(Vec::new(), Limits::none());
+    w.write_xdr(&1u32).await?;
+    w.write_xdr_framed(&2u32).await?;
+    w.flush().await?;
+    assert_eq!(
+        w.into_inner(),
+        [
+            0x00, 0x00, 0x00, 0x01, //
+            0x80, 0x00, 0x00, 0x04, // last fragment, length 4
+            0x00, 0x00, 0x00, 0x02, //
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_limits() -> Result<(), Error> {
+    let mut w = AsyncLimited::new(Vec::new(), Limits::len(6));
+    w.write_xdr(&1u32).await?;
+    assert_eq!(w.write_xdr(&2u32).await, Err(Error::LengthLimitExceeded));
+    // The value that exceeded the limits was not written.
+    assert_eq!(w.into_inner(), [0, 0, 0, 1]);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    borrowed, Error, Limits, Memo, MuxedAccount, Preconditions, ReadXdr, ReadXdrBorrowed, ScVal,
+    SequenceNumber, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope,
+    Uint256, WriteXdr,
+};
+
+#[test]
+fn test_borrowed_bytes_borrow_from_input() -> Result<(), Error> {
+    let v = ScVal::Bytes(vec![1u8, 2, 3, 4, 5].try_into()?);
+    let xdr = v.to_xdr(Limits::none())?;
+
+    let b = borrowed::ScVal::from_xdr_borrowed(&xdr, Limits::none())?;
+    let borrowed::ScVal::Bytes(borrowed::ScBytes(bytes)) = &b else {
+        panic!("expected bytes, got {b:?}");
+    };
+    assert_eq!(bytes.as_slice(), &[1, 2, 3, 4, 5]);
+    // The bytes point into the input rather than into a new allocation.
+    assert_eq!(bytes.as_slice().as_ptr(), xdr[8..].as_ptr());
+
+    assert_eq!(b.into_owned(), v);
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_tx_into_owned_matches_owned_decode() -> Result<(), Error> {
+    let te = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("Stellar".as_bytes().try_into()?),
+            operations: [].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    });
+    let xdr = te.to_xdr(Limits::none())?;
+
+    let b = borrowed::TransactionEnvelope::from_xdr_borrowed(&xdr, Limits::none())?;
+    let borrowed::TransactionEnvelope::Tx(v1) = &b else {
+        panic!("expected tx, got {b:?}");
+    };
+    let borrowed::Memo::Text(text) = &v1.tx.memo else {
+        panic!("expected text memo, got {:?}", v1.tx.memo);
+    };
+    assert_eq!(text.to_utf8_str()?, "Stellar");
+
+    assert_eq!(
+        b.into_owned(),
+        TransactionEnvelope::from_xdr(&xdr, Limits::none())?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_errors() -> Result<(), Error> {
+    let xdr = ScVal::Bytes(vec![1u8, 2, 3].try_into()?).to_xdr(Limits::none())?;
+
+    // Residual bytes.
+    let mut extra = xdr.clone();
+    extra.extend_from_slice(&[0, 0, 0, 0]);
+    assert_eq!(
+        borrowed::ScVal::from_xdr_borrowed(&extra, Limits::none()),
+        Err(Error::Invalid)
+    );
+
+    // Non-zero padding.
+    let mut padding = xdr.clone();
+    *padding.last_mut().unwrap() = 1;
+    assert_eq!(
+        borrowed::ScVal::from_xdr_borrowed(&padding, Limits::none()),
+        Err(Error::NonZeroPadding)
+    );
+
+    // Truncated.
+    assert!(matches!(
+        borrowed::ScVal::from_xdr_borrowed(&xdr[..xdr.len() - 1], Limits::none()),
+        Err(Error::Io(_))
+    ));
+
+    // Length limit.
+    assert_eq!(
+        borrowed::ScVal::from_xdr_borrowed(&xdr, Limits::len(xdr.len() - 1)),
+        Err(Error::LengthLimitExceeded)
+    );
+    Ok(())
+}
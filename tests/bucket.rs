@@ -0,0 +1,137 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Bucket, BucketEntry, BucketMetadata, BucketMetadataExt, ContractCodeEntry, Error,
+    ExtensionPoint, FramedWriter, Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt,
+    LedgerEntryType, LedgerKey, LedgerKeyContractCode, LedgerKeyTtl, Limits, TtlEntry,
+};
+
+fn metaentry(ledger_version: u32) -> BucketEntry {
+    BucketEntry::Metaentry(BucketMetadata {
+        ledger_version,
+        ext: BucketMetadataExt::V0,
+    })
+}
+
+fn code(hash: u8) -> BucketEntry {
+    BucketEntry::Liveentry(LedgerEntry {
+        last_modified_ledger_seq: 1,
+        data: LedgerEntryData::ContractCode(ContractCodeEntry {
+            ext: ExtensionPoint::V0,
+            hash: Hash([hash; 32]),
+            code: [0, 97, 115, 109].try_into().unwrap(),
+        }),
+        ext: LedgerEntryExt::V0,
+    })
+}
+
+fn ttl(hash: u8) -> BucketEntry {
+    BucketEntry::Initentry(LedgerEntry {
+        last_modified_ledger_seq: 1,
+        data: LedgerEntryData::Ttl(TtlEntry {
+            key_hash: Hash([hash; 32]),
+            live_until_ledger_seq: 100,
+        }),
+        ext: LedgerEntryExt::V0,
+    })
+}
+
+fn dead(hash: u8) -> BucketEntry {
+    BucketEntry::Deadentry(LedgerKey::Ttl(LedgerKeyTtl {
+        key_hash: Hash([hash; 32]),
+    }))
+}
+
+fn bucket(entries: &[BucketEntry]) -> Result<Vec<u8>, Error> {
+    let mut w = FramedWriter::new(Vec::new(), Limits::none());
+    w.write_iter(entries.iter().cloned())?;
+    Ok(w.into_inner())
+}
+
+#[test]
+fn read_bucket() -> Result<(), Error> {
+    let data = bucket(&[metaentry(20), code(1), ttl(2), dead(3)])?;
+    let bucket = Bucket::new(data.as_slice(), Limits::none())?;
+    assert_eq!(bucket.protocol_version(), 20);
+    assert_eq!(bucket.metadata().ext, BucketMetadataExt::V0);
+    let entries = bucket.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(entries, [code(1), ttl(2), dead(3)]);
+    assert_eq!(
+        entries.iter().map(BucketEntry::to_key).collect::<Vec<_>>(),
+        [
+            Some(LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: Hash([1; 32])
+            })),
+            Some(LedgerKey::Ttl(LedgerKeyTtl {
+                key_hash: Hash([2; 32])
+            })),
+            Some(LedgerKey::Ttl(LedgerKeyTtl {
+                key_hash: Hash([3; 32])
+            })),
+        ]
+    );
+    assert_eq!(metaentry(20).to_key(), None);
+    Ok(())
+}
+
+#[test]
+fn read_bucket_without_metadata() -> Result<(), Error> {
+    let data = bucket(&[code(1)])?;
+    assert!(matches!(
+        Bucket::new(data.as_slice(), Limits::none()),
+        Err(Error::Invalid)
+    ));
+    assert!(matches!(
+        Bucket::new([].as_slice(), Limits::none()),
+        Err(Error::Invalid)
+    ));
+    Ok(())
+}
+
+#[test]
+fn read_bucket_with_second_metadata() -> Result<(), Error> {
+    let data = bucket(&[metaentry(20), code(1), metaentry(20)])?;
+    let mut bucket = Bucket::new(data.as_slice(), Limits::none())?;
+    assert_eq!(bucket.next(), Some(Ok(code(1))));
+    assert_eq!(bucket.next(), Some(Err(Error::Invalid)));
+    Ok(())
+}
+
+#[test]
+fn filter_bucket() -> Result<(), Error> {
+    let data = bucket(&[metaentry(20), code(1), ttl(2), code(3), dead(4)])?;
+    let invalid_data = bucket(&[metaentry(20), code(3), metaentry(20)])?;
+
+    let bucket = Bucket::new(data.as_slice(), Limits::none())?;
+    let entries = bucket
+        .entries_of_type(LedgerEntryType::Ttl)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(entries, [ttl(2), dead(4)]);
+
+    let bucket = Bucket::new(data.as_slice(), Limits::none())?;
+    let key = LedgerKey::ContractCode(LedgerKeyContractCode {
+        hash: Hash([3; 32]),
+    });
+    let entries = bucket
+        .entries_with_key(key.clone())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(entries, [code(3)]);
+
+    // The bucket is not read after the entry with the key, so the invalid
+    // entry that follows it is not reached.
+    let bucket = Bucket::new(invalid_data.as_slice(), Limits::none())?;
+    let entries = bucket
+        .entries_with_key(key)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(entries, [code(3)]);
+    Ok(())
+}
@@ -0,0 +1,193 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "bytes")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use bytes::{Buf, Bytes, BytesMut};
+use stellar_xdr::{
+    shared, BytesM, Error, Limits, ReadXdr, ReadXdrShared, ScBytes, ScString, ScVal, Type,
+    TypeVariant, WriteXdr,
+};
+
+#[test]
+fn read_xdr_buf() -> Result<(), Error> {
+    let v = ScVal::Bytes(ScBytes(vec![1u8; 5].try_into()?));
+    let mut buf = BytesMut::new();
+    v.write_xdr_buf(&mut buf, Limits::none())?;
+    1u32.write_xdr_buf(&mut buf, Limits::none())?;
+    assert_eq!(buf.len(), 20);
+
+    let mut buf = buf.freeze();
+    assert_eq!(ScVal::read_xdr_buf(&mut buf, Limits::none())?, v);
+    // The buffer is advanced past the value, and the residual bytes remain.
+    assert_eq!(buf.remaining(), 4);
+    assert_eq!(u32::from_xdr_buf(buf, Limits::none())?, 1);
+    Ok(())
+}
+
+#[test]
+fn from_xdr_buf() -> Result<(), Error> {
+    let v = ScVal::U32(1);
+    let buf = v.to_xdr_bytes(Limits::none())?;
+    assert_eq!(ScVal::from_xdr_buf(buf.clone(), Limits::none())?, v);
+    assert_eq!(
+        Type::from_xdr_buf(TypeVariant::ScVal, buf.clone(), Limits::none())?,
+        Type::ScVal(Box::new(v))
+    );
+
+    // The buffer may be split across multiple chunks.
+    let (a, b) = (buf.slice(..3), buf.slice(3..));
+    assert_eq!(
+        ScVal::from_xdr_buf(a.chain(b), Limits::none())?,
+        ScVal::U32(1)
+    );
+
+    let mut residual = BytesMut::from(&buf[..]);
+    residual.extend_from_slice(&[0]);
+    assert_eq!(
+        ScVal::from_xdr_buf(residual, Limits::none()),
+        Err(Error::Invalid)
+    );
+    Ok(())
+}
+
+#[test]
+fn buf_limits() -> Result<(), Error> {
+    let v = ScVal::U32(1);
+    let mut buf = BytesMut::new();
+    assert_eq!(
+        v.write_xdr_buf(&mut buf, Limits::len(7)),
+        Err(Error::LengthLimitExceeded)
+    );
+
+    let buf = v.to_xdr_bytes(Limits::none())?;
+    assert_eq!(
+        ScVal::from_xdr_buf(buf.clone(), Limits::len(7)),
+        Err(Error::LengthLimitExceeded)
+    );
+    assert_eq!(
+        ScVal::from_xdr_buf(buf, Limits::depth(1)),
+        Err(Error::DepthLimitExceeded)
+    );
+    Ok(())
+}
+
+#[test]
+fn write_xdr_buf_full() {
+    let mut storage = [0u8; 6];
+    let mut buf = &mut storage[..];
+    assert!(matches!(
+        ScVal::U32(1).write_xdr_buf(&mut buf, Limits::none()),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn bytes_conversions() -> Result<(), Error> {
+    let b = Bytes::from(vec![1u8, 2, 3]);
+    let ptr = b.as_ptr();
+
+    let bm: BytesM<3> = b.try_into()?;
+    let sc: ScBytes = Bytes::from(bm.to_vec()).try_into()?;
+    assert_eq!(sc.as_slice(), [1, 2, 3]);
+
+    // Converting back to bytes does not copy.
+    let b = Bytes::from(bm);
+    assert_eq!(b.as_ptr(), ptr);
+    assert_eq!(Bytes::from(sc), b);
+
+    let b = Bytes::from(vec![1u8, 2, 3, 4]);
+    assert_eq!(BytesM::<3>::try_from(b), Err(Error::LengthExceedsMax));
+    Ok(())
+}
+
+#[test]
+fn read_xdr_buf_shared() -> Result<(), Error> {
+    let v = ScVal::Vec(Some(
+        [
+            ScVal::Bytes(ScBytes(vec![1u8; 5].try_into()?)),
+            ScVal::String(ScString("Stellar".try_into()?)),
+            ScVal::U32(1),
+        ]
+        .to_vec()
+        .try_into()?,
+    ));
+    let mut buf = BytesMut::new();
+    v.write_xdr_buf(&mut buf, Limits::none())?;
+    7u32.write_xdr_buf(&mut buf, Limits::none())?;
+    let mut buf = buf.freeze();
+    let range = buf.as_ptr_range();
+
+    let shared = shared::ScVal::read_xdr_buf(&mut buf, Limits::none())?;
+    assert_eq!(buf.remaining(), 4);
+    let shared::ScVal::Vec(Some(vec)) = &shared else {
+        panic!("expected vec");
+    };
+    let [shared::ScVal::Bytes(b), shared::ScVal::String(st), shared::ScVal::U32(1)] = &vec.0[..]
+    else {
+        panic!("expected bytes, string and u32");
+    };
+    // The opaque and string data share the buffer decoded from.
+    assert_eq!(&b.0[..], [1u8; 5]);
+    assert!(range.contains(&b.0.as_ptr()));
+    assert_eq!(st.0.to_utf8_str()?, "Stellar");
+    assert!(range.contains(&st.0.as_ptr()));
+
+    assert_eq!(shared.into_owned(), v);
+    assert_eq!(u32::from_xdr_buf(buf, Limits::none())?, 7);
+    Ok(())
+}
+
+#[test]
+fn from_xdr_buf_shared() -> Result<(), Error> {
+    let v = ScVal::Bytes(ScBytes(vec![1u8; 5].try_into()?));
+    let buf = v.to_xdr_bytes(Limits::none())?;
+
+    // A buffer split across multiple chunks has its data copied.
+    let (a, b) = (buf.slice(..10), buf.slice(10..));
+    let shared = shared::ScVal::from_xdr_buf(a.chain(b), Limits::none())?;
+    assert_eq!(shared.into_owned(), v);
+
+    let mut residual = BytesMut::from(&buf[..]);
+    residual.extend_from_slice(&[0]);
+    assert_eq!(
+        shared::ScVal::from_xdr_buf(residual, Limits::none()),
+        Err(Error::Invalid)
+    );
+    assert_eq!(
+        shared::ScVal::from_xdr_buf(buf.slice(..14), Limits::none()),
+        Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    );
+    assert_eq!(
+        shared::ScVal::from_xdr_buf(buf.clone(), Limits::len(15)),
+        Err(Error::LengthLimitExceeded)
+    );
+
+    let mut padded = BytesMut::from(&buf[..]);
+    padded[15] = 1;
+    assert_eq!(
+        shared::ScVal::from_xdr_buf(padded, Limits::none()),
+        Err(Error::NonZeroPadding)
+    );
+    Ok(())
+}
+
+#[test]
+fn shared_bytes_conversions() -> Result<(), Error> {
+    let b = Bytes::from(vec![1u8, 2, 3]);
+    let slice = b.slice(1..);
+    let bm: shared::BytesM<2> = slice.clone().try_into()?;
+    assert_eq!(bm.as_bytes().as_ptr(), slice.as_ptr());
+    assert_eq!(Bytes::from(bm), slice);
+    assert_eq!(
+        shared::BytesM::<1>::try_from(slice),
+        Err(Error::LengthExceedsMax)
+    );
+    Ok(())
+}
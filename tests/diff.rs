@@ -0,0 +1,151 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Asset, Diff, Difference, DifferenceKind, Error, Memo, MuxedAccount, Operation, OperationBody,
+    PathSegment, PaymentOp, Preconditions, ScVal, SequenceNumber, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionV1Envelope, Type, Uint256,
+};
+
+fn payment(amount: i64) -> Operation {
+    Operation {
+        source_account: None,
+        body: OperationBody::Payment(PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+            asset: Asset::Native,
+            amount,
+        }),
+    }
+}
+
+fn tx_envelope(
+    fee: u32,
+    memo: Memo,
+    operations: &[Operation],
+) -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo,
+            operations: operations.to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+#[test]
+fn diff_equal() -> Result<(), Error> {
+    let te = tx_envelope(100, Memo::None, &[payment(1)])?;
+    assert_eq!(te.diff(&te.clone()), []);
+    Ok(())
+}
+
+#[test]
+fn diff_changes() -> Result<(), Error> {
+    let a = tx_envelope(100, Memo::None, &[payment(1), payment(2)])?;
+    let b = tx_envelope(200, Memo::Id(1), &[payment(1), payment(3)])?;
+    let tx = [PathSegment::Arm("Tx"), PathSegment::Field("tx")];
+    assert_eq!(
+        a.diff(&b),
+        [
+            Difference {
+                path: [&tx[..], &[PathSegment::Field("fee")]].concat(),
+                kind: DifferenceKind::Changed,
+            },
+            Difference {
+                path: [&tx[..], &[PathSegment::Field("memo")]].concat(),
+                kind: DifferenceKind::ArmChanged {
+                    from: "None",
+                    to: "Id"
+                },
+            },
+            Difference {
+                path: [
+                    &tx[..],
+                    &[
+                        PathSegment::Field("operations"),
+                        PathSegment::Index(1),
+                        PathSegment::Field("body"),
+                        PathSegment::Arm("Payment"),
+                        PathSegment::Field("amount"),
+                    ],
+                ]
+                .concat(),
+                kind: DifferenceKind::Changed,
+            },
+        ]
+    );
+    let display = a
+        .diff(&b)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        display,
+        [
+            "~ tx.tx.fee",
+            "~ tx.tx.memo (none -> id)",
+            "~ tx.tx.operations[1].body.payment.amount",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn diff_added_removed() -> Result<(), Error> {
+    let a = ScVal::Vec(Some(
+        [ScVal::U32(1), ScVal::U32(2), ScVal::U32(3)]
+            .to_vec()
+            .try_into()?,
+    ));
+    let b = ScVal::Vec(Some(
+        [ScVal::U32(1), ScVal::U32(4), ScVal::U32(5), ScVal::U32(3)]
+            .to_vec()
+            .try_into()?,
+    ));
+    let display = |a: &ScVal, b: &ScVal| {
+        a.diff(b)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    // Unchanged elements at the start and end are skipped.
+    assert_eq!(display(&a, &b), ["~ vec[1].u32", "+ vec[2]"]);
+    assert_eq!(display(&b, &a), ["~ vec[1].u32", "- vec[2]"]);
+    assert_eq!(display(&a, &ScVal::Vec(None)), ["~ vec"]);
+    Ok(())
+}
+
+#[test]
+fn diff_type() {
+    let a = Type::Memo(Box::new(Memo::Id(1)));
+    let b = Type::Memo(Box::new(Memo::Id(2)));
+    assert_eq!(
+        a.diff(&b),
+        [Difference {
+            path: vec![PathSegment::Type("Memo"), PathSegment::Arm("Id")],
+            kind: DifferenceKind::Changed,
+        }]
+    );
+    let c = Type::Uint256(Box::new(Uint256([0; 32])));
+    assert_eq!(
+        a.diff(&c),
+        [Difference {
+            path: vec![],
+            kind: DifferenceKind::Changed,
+        }]
+    );
+    assert_eq!(a.diff(&c)[0].to_string(), "~ .");
+}
@@ -0,0 +1,123 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Error, Frame, Limited, Limits, Memo, MuxedAccount, Operation, OperationBody, PathSegment,
+    Preconditions, ReadXdr, SequenceNumber, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Type, TypeVariant, Uint256, WriteXdr,
+};
+
+fn tx_envelope() -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: [
+                Operation {
+                    source_account: None,
+                    body: OperationBody::Inflation,
+                },
+                Operation {
+                    source_account: None,
+                    body: OperationBody::Inflation,
+                },
+            ]
+            .to_vec()
+            .try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+// The offset of the body of the second operation in the envelope.
+const SECOND_OP_BODY_OFFSET: usize = 4 + 4 + 32 + 4 + 8 + 4 + 4 + 4 + (4 + 4) + 4;
+
+#[test]
+fn error_without_context() -> Result<(), Error> {
+    let mut xdr = tx_envelope()?.to_xdr(Limits::none())?;
+    xdr[SECOND_OP_BODY_OFFSET + 3] = 0xff;
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    let res = TransactionEnvelope::read_xdr_to_end(&mut r);
+    assert_eq!(res, Err(Error::Invalid));
+    Ok(())
+}
+
+#[test]
+fn error_with_context() -> Result<(), Error> {
+    let mut xdr = tx_envelope()?.to_xdr(Limits::none())?;
+    xdr[SECOND_OP_BODY_OFFSET + 3] = 0xff;
+    let mut r = Limited::new(&xdr[..], Limits::none()).with_error_context();
+    let Err(Error::Context(c)) = TransactionEnvelope::read_xdr_to_end(&mut r) else {
+        panic!("expected error with context");
+    };
+    assert_eq!(c.error(), &Error::Invalid);
+    assert_eq!(c.value_offset(), SECOND_OP_BODY_OFFSET);
+    assert_eq!(
+        c.path(),
+        [
+            PathSegment::Arm("Tx"),
+            PathSegment::Field("tx"),
+            PathSegment::Field("operations"),
+            PathSegment::Index(1),
+            PathSegment::Field("body"),
+        ]
+    );
+    assert_eq!(
+        c.to_string(),
+        format!(
+            "xdr value invalid at Tx.tx.operations[1].body (value at byte offset {SECOND_OP_BODY_OFFSET})"
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn error_with_context_from_type() -> Result<(), Error> {
+    let xdr = tx_envelope()?.to_xdr(Limits::none())?;
+    let truncated = &xdr[..SECOND_OP_BODY_OFFSET + 2];
+    let mut r = Limited::new(truncated, Limits::none()).with_error_context();
+    let err = Type::read_xdr_to_end(TypeVariant::TransactionEnvelope, &mut r).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "failed to fill whole buffer at TransactionEnvelope.Tx.tx.operations[1].body (value at byte offset {SECOND_OP_BODY_OFFSET})"
+        ),
+    );
+    assert_eq!(
+        err.without_context(),
+        &Error::Io(std::io::ErrorKind::UnexpectedEof.into())
+    );
+    Ok(())
+}
+
+#[test]
+fn error_with_context_from_framed_iter() -> Result<(), Error> {
+    let mut xdr = Frame(tx_envelope()?).to_xdr(Limits::none())?;
+    xdr[4 + SECOND_OP_BODY_OFFSET + 3] = 0xff;
+    let mut r = Limited::new(&xdr[..], Limits::none()).with_error_context();
+    let err = Type::read_xdr_framed_iter(TypeVariant::TransactionEnvelope, &mut r)
+        .next()
+        .unwrap()
+        .unwrap_err();
+    // The offset includes the record header.
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "xdr value invalid at TransactionEnvelope.Tx.tx.operations[1].body (value at byte offset {})",
+            4 + SECOND_OP_BODY_OFFSET
+        ),
+    );
+    Ok(())
+}
@@ -0,0 +1,461 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    ConfigSettingContractBandwidthV0, ConfigSettingContractComputeV0,
+    ConfigSettingContractEventsV0, ConfigSettingContractHistoricalDataV0,
+    ConfigSettingContractLedgerCostV0, ContractDataDurability, ContractDataEntry, Error,
+    ExtendFootprintTtlOp, ExtensionPoint, FeeConfiguration, Hash, LedgerEntry, LedgerEntryData,
+    LedgerEntryExt, LedgerEntryRentChange, LedgerFootprint, LedgerKey, LedgerKeyContractCode,
+    Limits, RentFeeConfiguration, ScAddress, ScVal, SorobanResources, SorobanTransactionData,
+    StateArchivalSettings, TransactionResources, TtlEntry, WriteXdr,
+};
+
+fn fee_configuration(fee: i64) -> FeeConfiguration {
+    FeeConfiguration {
+        fee_per_instruction_increment: fee,
+        fee_per_read_entry: fee,
+        fee_per_write_entry: fee,
+        fee_per_read_1kb: fee,
+        fee_per_write_1kb: fee,
+        fee_per_historical_1kb: fee,
+        fee_per_contract_event_1kb: fee,
+        fee_per_transaction_size_1kb: fee,
+    }
+}
+
+fn ledger_cost(low: i64, high: i64) -> ConfigSettingContractLedgerCostV0 {
+    ConfigSettingContractLedgerCostV0 {
+        ledger_max_read_ledger_entries: 0,
+        ledger_max_read_bytes: 0,
+        ledger_max_write_ledger_entries: 0,
+        ledger_max_write_bytes: 0,
+        tx_max_read_ledger_entries: 0,
+        tx_max_read_bytes: 0,
+        tx_max_write_ledger_entries: 0,
+        tx_max_write_bytes: 0,
+        fee_read_ledger_entry: 2000,
+        fee_write_ledger_entry: 4000,
+        fee_read1_kb: 1500,
+        bucket_list_target_size_bytes: 100_000,
+        write_fee1_kb_bucket_list_low: low,
+        write_fee1_kb_bucket_list_high: high,
+        bucket_list_write_fee_growth_factor: 50,
+    }
+}
+
+#[test]
+fn resource_fee_no_resources() {
+    let resources = TransactionResources {
+        instructions: 0,
+        read_entries: 0,
+        write_entries: 0,
+        read_bytes: 0,
+        write_bytes: 0,
+        contract_events_size_bytes: 0,
+        transaction_size_bytes: 0,
+    };
+    // The historical fee of the base result size, 300 * 100 / 1024 rounded up.
+    assert_eq!(fee_configuration(100).resource_fee(&resources), (30, 0));
+}
+
+#[test]
+fn resource_fee_minimal_resources() {
+    let resources = TransactionResources {
+        instructions: 1,
+        read_entries: 0,
+        write_entries: 1,
+        read_bytes: 1,
+        write_bytes: 1,
+        contract_events_size_bytes: 1,
+        transaction_size_bytes: 1,
+    };
+    assert_eq!(
+        fee_configuration(100).resource_fee(&resources),
+        (
+            1 // instructions
+            + 100 // read entries, that include the write entries
+            + 100 // write entries
+            + 1 // read bytes
+            + 1 // write bytes
+            + 30 // historical
+            + 1, // bandwidth
+            1 // events
+        )
+    );
+}
+
+#[test]
+fn resource_fee() {
+    let resources = TransactionResources {
+        instructions: 10_123_456,
+        read_entries: 30,
+        write_entries: 10,
+        read_bytes: 25_600,
+        write_bytes: 10_340,
+        contract_events_size_bytes: 321_654,
+        transaction_size_bytes: 35_721,
+    };
+    let fee_configuration = FeeConfiguration {
+        fee_per_instruction_increment: 1000,
+        fee_per_read_entry: 2000,
+        fee_per_write_entry: 4000,
+        fee_per_read_1kb: 1500,
+        fee_per_write_1kb: 3000,
+        fee_per_historical_1kb: 300,
+        fee_per_contract_event_1kb: 200,
+        fee_per_transaction_size_1kb: 900,
+    };
+    assert_eq!(
+        fee_configuration.resource_fee(&resources),
+        (
+            1_012_346 // instructions
+            + 80_000 // read entries
+            + 40_000 // write entries
+            + 37_500 // read bytes
+            + 30_293 // write bytes
+            + 10_554 // historical
+            + 31_396, // bandwidth
+            62_824 // events
+        )
+    );
+}
+
+#[test]
+fn resource_fee_saturates() {
+    let resources = TransactionResources {
+        instructions: u32::MAX,
+        read_entries: u32::MAX,
+        write_entries: u32::MAX,
+        read_bytes: u32::MAX,
+        write_bytes: u32::MAX,
+        contract_events_size_bytes: u32::MAX,
+        transaction_size_bytes: u32::MAX,
+    };
+    let (non_refundable_fee, refundable_fee) = fee_configuration(i64::MAX).resource_fee(&resources);
+    assert_eq!(non_refundable_fee, i64::MAX);
+    assert_eq!(refundable_fee, i64::MAX / 1024 + 1);
+}
+
+#[test]
+fn write_fee_per_1kb() {
+    let ledger_cost = ledger_cost(1000, 10_000);
+    // Below the target size the fee grows linearly from the low fee.
+    assert_eq!(ledger_cost.write_fee_per_1kb(0), 1000);
+    assert_eq!(ledger_cost.write_fee_per_1kb(50_000), 5500);
+    assert_eq!(ledger_cost.write_fee_per_1kb(50_001), 5501);
+    // Beyond the target size the fee grows faster by the growth factor.
+    assert_eq!(ledger_cost.write_fee_per_1kb(100_000), 10_000);
+    assert_eq!(ledger_cost.write_fee_per_1kb(110_000), 55_000);
+    // The fee is never less than the minimum.
+    assert_eq!(self::ledger_cost(0, 0).write_fee_per_1kb(50_000), 1000);
+}
+
+#[test]
+fn fee_configuration_from_config_settings() {
+    let fee_configuration = FeeConfiguration::from_config_settings(
+        &ConfigSettingContractComputeV0 {
+            ledger_max_instructions: 0,
+            tx_max_instructions: 0,
+            fee_rate_per_instructions_increment: 1000,
+            tx_memory_limit: 0,
+        },
+        &ledger_cost(1000, 10_000),
+        &ConfigSettingContractHistoricalDataV0 {
+            fee_historical1_kb: 300,
+        },
+        &ConfigSettingContractEventsV0 {
+            tx_max_contract_events_size_bytes: 0,
+            fee_contract_events1_kb: 200,
+        },
+        &ConfigSettingContractBandwidthV0 {
+            ledger_max_txs_size_bytes: 0,
+            tx_max_size_bytes: 0,
+            fee_tx_size1_kb: 900,
+        },
+        50_000,
+    );
+    assert_eq!(
+        fee_configuration,
+        FeeConfiguration {
+            fee_per_instruction_increment: 1000,
+            fee_per_read_entry: 2000,
+            fee_per_write_entry: 4000,
+            fee_per_read_1kb: 1500,
+            fee_per_write_1kb: 5500,
+            fee_per_historical_1kb: 300,
+            fee_per_contract_event_1kb: 200,
+            fee_per_transaction_size_1kb: 900,
+        }
+    );
+}
+
+#[test]
+fn check_resource_fee() -> Result<(), Error> {
+    let key = LedgerKey::ContractCode(LedgerKeyContractCode {
+        hash: Hash([0; 32]),
+    });
+    let mut soroban_data = SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: [].try_into()?,
+                read_write: [key].try_into()?,
+            },
+            instructions: 1,
+            read_bytes: 1,
+            write_bytes: 1,
+        },
+        resource_fee: 235,
+    };
+    let fee_configuration = fee_configuration(100);
+    // The same resources as resource_fee_minimal_resources.
+    assert_eq!(
+        soroban_data.check_resource_fee(&fee_configuration, 1, 1),
+        Ok(())
+    );
+    soroban_data.resource_fee = 234;
+    assert_eq!(
+        soroban_data.check_resource_fee(&fee_configuration, 1, 1),
+        Err(Error::Invalid)
+    );
+    Ok(())
+}
+
+fn rent_fee_configuration() -> RentFeeConfiguration {
+    RentFeeConfiguration {
+        fee_per_write_1kb: 1000,
+        fee_per_write_entry: 100,
+        persistent_rent_rate_denominator: 10_000,
+        temporary_rent_rate_denominator: 100_000,
+    }
+}
+
+fn contract_data_entry(durability: ContractDataDurability) -> LedgerEntry {
+    LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: ScAddress::Contract(Hash([0; 32])),
+            key: ScVal::LedgerKeyContractInstance,
+            durability,
+            val: ScVal::U32(1),
+        }),
+        ext: LedgerEntryExt::V0,
+    }
+}
+
+fn ttl(live_until_ledger_seq: u32) -> TtlEntry {
+    TtlEntry {
+        key_hash: Hash([0; 32]),
+        live_until_ledger_seq,
+    }
+}
+
+#[test]
+fn rent_fee_configuration_from_config_settings() {
+    let rent_fee_configuration = RentFeeConfiguration::from_config_settings(
+        &ledger_cost(1000, 10_000),
+        &StateArchivalSettings {
+            max_entry_ttl: 0,
+            min_temporary_ttl: 0,
+            min_persistent_ttl: 0,
+            persistent_rent_rate_denominator: 10_000,
+            temp_rent_rate_denominator: 100_000,
+            max_entries_to_archive: 0,
+            bucket_list_size_window_sample_size: 0,
+            bucket_list_window_sample_period: 0,
+            eviction_scan_size: 0,
+            starting_eviction_scan_level: 0,
+        },
+        50_000,
+    );
+    assert_eq!(
+        rent_fee_configuration,
+        RentFeeConfiguration {
+            fee_per_write_1kb: 5500,
+            fee_per_write_entry: 4000,
+            persistent_rent_rate_denominator: 10_000,
+            temporary_rent_rate_denominator: 100_000,
+        }
+    );
+}
+
+#[test]
+fn rent_fee_for_ledgers() {
+    let rent_fee_configuration = rent_fee_configuration();
+    assert_eq!(
+        rent_fee_configuration.rent_fee_for_ledgers(
+            ContractDataDurability::Persistent,
+            10 * 1024,
+            100_000
+        ),
+        100_000
+    );
+    assert_eq!(
+        rent_fee_configuration.rent_fee_for_ledgers(
+            ContractDataDurability::Temporary,
+            10 * 1024,
+            100_000
+        ),
+        10_000
+    );
+    // The fee is rounded up.
+    assert_eq!(
+        rent_fee_configuration.rent_fee_for_ledgers(ContractDataDurability::Persistent, 1, 1),
+        1
+    );
+}
+
+#[test]
+fn rent_fee() {
+    let rent_fee_configuration = rent_fee_configuration();
+    let new_entry = LedgerEntryRentChange {
+        is_persistent: true,
+        old_size_bytes: 0,
+        new_size_bytes: 1024,
+        old_live_until_ledger: 0,
+        new_live_until_ledger: 100_999,
+    };
+    let grown_entry = LedgerEntryRentChange {
+        is_persistent: true,
+        old_size_bytes: 1024,
+        new_size_bytes: 2048,
+        old_live_until_ledger: 100_999,
+        new_live_until_ledger: 100_999,
+    };
+    let extended_entry = LedgerEntryRentChange {
+        is_persistent: false,
+        old_size_bytes: 1024,
+        new_size_bytes: 1024,
+        old_live_until_ledger: 100_999,
+        new_live_until_ledger: 110_999,
+    };
+    assert_eq!(
+        rent_fee_configuration.rent_fee(
+            &[
+                new_entry.clone(),
+                grown_entry.clone(),
+                extended_entry.clone()
+            ],
+            100_000
+        ),
+        100 + 100 + 100 // entries
+        + 200 // TTL entries
+        + 94 // TTL entries bytes
+    );
+    assert_eq!(
+        rent_fee_configuration.rent_fee(&[new_entry], 100_000),
+        100 // 1000 ledgers at the new size
+        + 100 // TTL entry
+        + 47 // TTL entry bytes
+    );
+    assert_eq!(
+        rent_fee_configuration.rent_fee(&[grown_entry], 100_000),
+        100 // 1000 prepaid ledgers at the increase in size
+    );
+    assert_eq!(
+        rent_fee_configuration.rent_fee(&[extended_entry], 100_000),
+        100 // 10000 ledgers at the temporary rate
+        + 100 // TTL entry
+        + 47 // TTL entry bytes
+    );
+    assert_eq!(rent_fee_configuration.rent_fee(&[], 100_000), 0);
+}
+
+#[test]
+fn extend_footprint_ttl_rent_fee() -> Result<(), Error> {
+    let rent_fee_configuration = rent_fee_configuration();
+    let entry = contract_data_entry(ContractDataDurability::Persistent);
+    let size_bytes = u32::try_from(entry.xdr_len(Limits::none())?).unwrap();
+    let op = ExtendFootprintTtlOp {
+        ext: ExtensionPoint::V0,
+        extend_to: 10_000,
+    };
+    assert_eq!(
+        rent_fee_configuration.extend_footprint_ttl_rent_fee(
+            &op,
+            &[(entry.clone(), ttl(100_999))],
+            100_000
+        ),
+        Ok(rent_fee_configuration.rent_fee(
+            &[LedgerEntryRentChange {
+                is_persistent: true,
+                old_size_bytes: size_bytes,
+                new_size_bytes: size_bytes,
+                old_live_until_ledger: 100_999,
+                new_live_until_ledger: 110_000,
+            }],
+            100_000
+        ))
+    );
+    // Entries that already live long enough, or that are archived, are not
+    // extended.
+    assert_eq!(
+        rent_fee_configuration.extend_footprint_ttl_rent_fee(
+            &op,
+            &[(entry.clone(), ttl(110_000)), (entry, ttl(99_999))],
+            100_000
+        ),
+        Ok(0)
+    );
+    let ttl_entry = LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data: LedgerEntryData::Ttl(ttl(0)),
+        ext: LedgerEntryExt::V0,
+    };
+    assert_eq!(
+        rent_fee_configuration.extend_footprint_ttl_rent_fee(
+            &op,
+            &[(ttl_entry, ttl(100_999))],
+            100_000
+        ),
+        Err(Error::Invalid)
+    );
+    Ok(())
+}
+
+#[test]
+fn restore_footprint_rent_fee() -> Result<(), Error> {
+    let rent_fee_configuration = rent_fee_configuration();
+    let entry = contract_data_entry(ContractDataDurability::Persistent);
+    let size_bytes = u32::try_from(entry.xdr_len(Limits::none())?).unwrap();
+    assert_eq!(
+        rent_fee_configuration.restore_footprint_rent_fee(
+            &[(entry.clone(), ttl(99_999)), (entry, ttl(100_000))],
+            4096,
+            100_000
+        ),
+        Ok(rent_fee_configuration.rent_fee(
+            &[LedgerEntryRentChange {
+                is_persistent: true,
+                old_size_bytes: 0,
+                new_size_bytes: size_bytes,
+                old_live_until_ledger: 0,
+                new_live_until_ledger: 104_095,
+            }],
+            100_000
+        ))
+    );
+    // Temporary entries cannot be restored.
+    assert_eq!(
+        rent_fee_configuration.restore_footprint_rent_fee(
+            &[(
+                contract_data_entry(ContractDataDurability::Temporary),
+                ttl(99_999)
+            )],
+            4096,
+            100_000
+        ),
+        Err(Error::Invalid)
+    );
+    Ok(())
+}
@@ -0,0 +1,179 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Asset, DecoratedSignature, Error, ExtensionPoint, FeeBumpTransaction, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, LedgerFootprint, Memo, MuxedAccount, Operation, OperationBody,
+    PaymentOp, SequenceNumber, Signature, SignatureHint, SorobanResources, SorobanTransactionData,
+    Transaction, TransactionBuilder, TransactionEnvelope, TransactionExt, TransactionV0,
+    TransactionV0Envelope, TransactionV0Ext, TransactionV1Envelope, Uint256, VecM, MIN_BASE_FEE,
+};
+
+fn payment() -> Operation {
+    Operation {
+        source_account: None,
+        body: OperationBody::Payment(PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([1; 32])),
+            asset: Asset::Native,
+            amount: 1,
+        }),
+    }
+}
+
+fn tx(fee: u32, ops: usize) -> Result<Transaction, Error> {
+    let mut builder =
+        TransactionBuilder::from_source_account(MuxedAccount::Ed25519(Uint256([0; 32])), 1)
+            .fee(fee);
+    for _ in 0..ops {
+        builder = builder.operation(payment())?;
+    }
+    builder.build()
+}
+
+fn soroban_tx(fee: u32, resource_fee: i64) -> Result<Transaction, Error> {
+    let mut tx = tx(fee, 1)?;
+    tx.ext = TransactionExt::V1(SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: VecM::default(),
+                read_write: VecM::default(),
+            },
+            instructions: 0,
+            read_bytes: 0,
+            write_bytes: 0,
+        },
+        resource_fee,
+    });
+    Ok(tx)
+}
+
+fn fee_source() -> MuxedAccount {
+    MuxedAccount::Ed25519(Uint256([2; 32]))
+}
+
+#[test]
+fn min_fee() -> Result<(), Error> {
+    // The base fee for each operation plus one.
+    assert_eq!(
+        FeeBumpTransaction::min_fee(&tx(100, 1)?, MIN_BASE_FEE),
+        Some(200)
+    );
+    assert_eq!(
+        FeeBumpTransaction::min_fee(&tx(300, 3)?, MIN_BASE_FEE),
+        Some(400)
+    );
+    // The fee per operation of the transaction, rounded up.
+    assert_eq!(
+        FeeBumpTransaction::min_fee(&tx(300, 2)?, MIN_BASE_FEE),
+        Some(450)
+    );
+    assert_eq!(
+        FeeBumpTransaction::min_fee(&tx(301, 2)?, MIN_BASE_FEE),
+        Some(452)
+    );
+    assert_eq!(FeeBumpTransaction::min_fee(&tx(100, 1)?, 1000), Some(2000));
+    Ok(())
+}
+
+#[test]
+fn min_fee_soroban() -> Result<(), Error> {
+    let tx = soroban_tx(1100, 1000)?;
+    assert_eq!(tx.inclusion_fee(), Some(100));
+    // The resource fee is paid once, in addition to the inclusion fee.
+    assert_eq!(FeeBumpTransaction::min_fee(&tx, MIN_BASE_FEE), Some(1200));
+    Ok(())
+}
+
+#[test]
+fn min_fee_overflow() -> Result<(), Error> {
+    let bump = |tx: Transaction| FeeBumpTransaction {
+        fee_source: fee_source(),
+        fee: i64::MAX,
+        inner_tx: FeeBumpTransactionInnerTx::Tx(TransactionV1Envelope {
+            tx,
+            signatures: VecM::default(),
+        }),
+        ext: FeeBumpTransactionExt::V0,
+    };
+
+    let tx = soroban_tx(100, i64::MAX)?;
+    assert_eq!(tx.inclusion_fee(), Some(100 - i64::MAX));
+    assert_eq!(FeeBumpTransaction::min_fee(&tx, MIN_BASE_FEE), None);
+    assert_eq!(bump(tx).check_fee(MIN_BASE_FEE), Err(Error::Invalid));
+
+    let tx = soroban_tx(100, i64::MIN)?;
+    assert_eq!(tx.inclusion_fee(), None);
+    assert_eq!(FeeBumpTransaction::min_fee(&tx, MIN_BASE_FEE), None);
+    assert_eq!(bump(tx).check_fee(MIN_BASE_FEE), Err(Error::Invalid));
+
+    // An inclusion fee that fits, but overflows when scaled by the number of
+    // operations of the fee bump.
+    let tx = soroban_tx(100, i64::MIN / 2)?;
+    assert_eq!(FeeBumpTransaction::min_fee(&tx, MIN_BASE_FEE), None);
+    assert_eq!(bump(tx).check_fee(MIN_BASE_FEE), Err(Error::Invalid));
+    Ok(())
+}
+
+#[test]
+fn fee_bump() -> Result<(), Error> {
+    let inner = TransactionV1Envelope {
+        tx: tx(100, 1)?,
+        signatures: [DecoratedSignature {
+            hint: SignatureHint([1; 4]),
+            signature: Signature([1; 64].try_into()?),
+        }]
+        .try_into()?,
+    };
+    let te = TransactionEnvelope::Tx(inner.clone());
+
+    assert_eq!(
+        te.clone().fee_bump(fee_source(), 199, MIN_BASE_FEE),
+        Err(Error::Invalid)
+    );
+    let fee_bump = te.fee_bump(fee_source(), 200, MIN_BASE_FEE)?;
+    assert_eq!(fee_bump.tx.fee_source, fee_source());
+    assert_eq!(fee_bump.tx.fee, 200);
+    assert_eq!(
+        fee_bump.tx.inner_tx,
+        FeeBumpTransactionInnerTx::Tx(inner.clone())
+    );
+    assert_eq!(fee_bump.signatures.len(), 0);
+    assert_eq!(fee_bump.tx.check_fee(MIN_BASE_FEE), Ok(()));
+    assert_eq!(fee_bump.tx.check_fee(1000), Err(Error::Invalid));
+
+    // Bumping a fee bump again bumps the same transaction.
+    let fee_bump =
+        TransactionEnvelope::TxFeeBump(fee_bump).fee_bump(fee_source(), 400, MIN_BASE_FEE)?;
+    assert_eq!(fee_bump.tx.fee, 400);
+    assert_eq!(fee_bump.tx.inner_tx, FeeBumpTransactionInnerTx::Tx(inner));
+    Ok(())
+}
+
+#[test]
+fn fee_bump_v0() -> Result<(), Error> {
+    let tx_v0 = TransactionV0 {
+        source_account_ed25519: Uint256([0; 32]),
+        fee: 100,
+        seq_num: SequenceNumber(1),
+        time_bounds: None,
+        memo: Memo::None,
+        operations: [payment()].try_into()?,
+        ext: TransactionV0Ext::V0,
+    };
+    let te = TransactionEnvelope::TxV0(TransactionV0Envelope {
+        tx: tx_v0.clone(),
+        signatures: VecM::default(),
+    });
+    let fee_bump = te.fee_bump(fee_source(), 200, MIN_BASE_FEE)?;
+    assert_eq!(fee_bump.tx.inner_tx(), &Transaction::from(tx_v0));
+    Ok(())
+}
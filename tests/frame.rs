@@ -0,0 +1,180 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Error, Frame, FramedWriter, Limited, Limits, ReadXdr, Type, TypeVariant, Uint256, WriteXdr,
+};
+
+#[test]
+fn read_single_fragment() -> Result<(), Error> {
+    let data = [
+        0x80, 0x00, 0x00, 0x08, // last fragment, length 8
+        0x00, 0x00, 0x00, 0x01, //
+        0x00, 0x00, 0x00, 0x02, //
+    ];
+    let f = Frame::<u64>::from_xdr(data, Limits::none())?;
+    assert_eq!(f, Frame(0x1_0000_0002));
+    Ok(())
+}
+
+#[test]
+fn read_multiple_fragments() -> Result<(), Error> {
+    let data = [
+        0x00, 0x00, 0x00, 0x03, // fragment, length 3
+        0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, // empty fragment
+        0x80, 0x00, 0x00, 0x05, // last fragment, length 5
+        0x01, 0x00, 0x00, 0x00, 0x02, //
+    ];
+    let f = Frame::<u64>::from_xdr(data, Limits::none())?;
+    assert_eq!(f, Frame(0x1_0000_0002));
+    Ok(())
+}
+
+#[test]
+fn read_trailing_empty_fragment() -> Result<(), Error> {
+    let data = [
+        0x00, 0x00, 0x00, 0x04, // fragment, length 4
+        0x00, 0x00, 0x00, 0x07, //
+        0x80, 0x00, 0x00, 0x00, // empty last fragment
+    ];
+    let f = Frame::<u32>::from_xdr(data, Limits::none())?;
+    assert_eq!(f, Frame(7));
+    Ok(())
+}
+
+#[test]
+fn read_length_mismatch() {
+    // Record is shorter than the value.
+    let data = [
+        0x80, 0x00, 0x00, 0x04, // last fragment, length 4
+        0x00, 0x00, 0x00, 0x01, //
+        0x00, 0x00, 0x00, 0x02, //
+    ];
+    let mut r = Limited::new(&data[..], Limits::none());
+    assert_eq!(Frame::<u64>::read_xdr(&mut r), Err(Error::LengthMismatch));
+
+    // Record is longer than the value.
+    let data = [
+        0x00, 0x00, 0x00, 0x04, // fragment, length 4
+        0x00, 0x00, 0x00, 0x07, //
+        0x80, 0x00, 0x00, 0x04, // last fragment, length 4
+        0x00, 0x00, 0x00, 0x08, //
+    ];
+    let mut r = Limited::new(&data[..], Limits::none());
+    assert_eq!(Frame::<u32>::read_xdr(&mut r), Err(Error::LengthMismatch));
+}
+
+#[test]
+fn read_truncated() {
+    let data = [
+        0x00, 0x00, 0x00, 0x04, // fragment, length 4
+        0x00, 0x00, 0x00, 0x07, //
+    ];
+    let mut r = Limited::new(&data[..], Limits::none());
+    assert!(matches!(Frame::<u32>::read_xdr(&mut r), Err(Error::Io(_))));
+}
+
+#[test]
+fn read_len_limit_includes_headers() {
+    let data = [
+        0x00, 0x00, 0x00, 0x04, // fragment, length 4
+        0x00, 0x00, 0x00, 0x07, //
+        0x80, 0x00, 0x00, 0x00, // empty last fragment
+    ];
+    let mut r = Limited::new(&data[..], Limits::len(12));
+    assert_eq!(Frame::<u32>::read_xdr(&mut r), Ok(Frame(7)));
+    let mut r = Limited::new(&data[..], Limits::len(11));
+    assert_eq!(
+        Frame::<u32>::read_xdr(&mut r),
+        Err(Error::LengthLimitExceeded)
+    );
+}
+
+#[test]
+fn read_framed_iter_multiple_fragments() -> Result<(), Error> {
+    let v1 = Uint256([1; 32]);
+    let v2 = Uint256([2; 32]);
+    let b1 = v1.to_xdr(Limits::none())?;
+    let b2 = v2.to_xdr(Limits::none())?;
+
+    let mut data = vec![];
+    data.extend([0x00, 0x00, 0x00, 0x10]);
+    data.extend(&b1[..16]);
+    data.extend([0x80, 0x00, 0x00, 0x10]);
+    data.extend(&b1[16..]);
+    data.extend([0x80, 0x00, 0x00, 0x20]);
+    data.extend(&b2);
+
+    let mut r = Limited::new(&data[..], Limits::none());
+    let values =
+        Type::read_xdr_framed_iter(TypeVariant::Uint256, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        values,
+        [Type::Uint256(Box::new(v1)), Type::Uint256(Box::new(v2))]
+    );
+    Ok(())
+}
+
+#[test]
+fn write_roundtrip() -> Result<(), Error> {
+    let f = Frame(0x1_0000_0002u64);
+    let xdr = f.to_xdr(Limits::none())?;
+    assert_eq!(
+        xdr,
+        [
+            0x80, 0x00, 0x00, 0x08, // last fragment, length 8
+            0x00, 0x00, 0x00, 0x01, //
+            0x00, 0x00, 0x00, 0x02, //
+        ]
+    );
+    assert_eq!(Frame::<u64>::from_xdr(xdr, Limits::none())?, f);
+    Ok(())
+}
+
+#[test]
+fn write_len_limit_includes_header() {
+    let f = Frame(7u32);
+    assert!(f.to_xdr(Limits::len(8)).is_ok());
+    assert_eq!(f.to_xdr(Limits::len(7)), Err(Error::LengthLimitExceeded));
+}
+
+#[test]
+fn write_framed_stream() -> Result<(), Error> {
+    let v1 = Uint256([1; 32]);
+    let v2 = Uint256([2; 32]);
+
+    let mut w = FramedWriter::new(Vec::new(), Limits::none());
+    w.write_iter([v1.clone(), v2.clone()])?;
+    let data = w.into_inner();
+    assert_eq!(data.len(), 2 * (4 + 32));
+
+    let mut r = Limited::new(&data[..], Limits::none());
+    let values =
+        Type::read_xdr_framed_iter(TypeVariant::Uint256, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        values,
+        [Type::Uint256(Box::new(v1)), Type::Uint256(Box::new(v2))]
+    );
+    Ok(())
+}
+
+#[test]
+fn write_framed_type() -> Result<(), Error> {
+    let t = Type::Uint256(Box::new(Uint256([3; 32])));
+    let mut w = Limited::new(Vec::new(), Limits::none());
+    t.write_xdr_framed(&mut w)?;
+    let mut r = Limited::new(&w.inner[..], Limits::none());
+    let values =
+        Type::read_xdr_framed_iter(TypeVariant::Uint256, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, [t]);
+    Ok(())
+}
@@ -0,0 +1,155 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(all(feature = "sha2", feature = "std"))]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use sha2::{Digest, Sha256};
+use stellar_xdr::{
+    AccountId, AlphaNum4, Asset, AssetCode4, ClaimableBalanceId, ContractIdPreimage, Error, Hash,
+    LiquidityPoolConstantProductParameters, LiquidityPoolParameters, PoolId, PublicKey, ScAddress,
+    SequenceNumber, Uint256, NETWORK_PASSPHRASE_PUBLIC, NETWORK_PASSPHRASE_TESTNET,
+};
+
+fn contract(strkey: &str) -> Hash {
+    Hash(stellar_strkey::Contract::from_string(strkey).unwrap().0)
+}
+
+fn account(strkey: &str) -> AccountId {
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(ed25519(strkey))))
+}
+
+#[test]
+fn native_asset_contract_id() -> Result<(), Error> {
+    let preimage = ContractIdPreimage::Asset(Asset::Native);
+    assert_eq!(
+        preimage.contract_id(NETWORK_PASSPHRASE_PUBLIC)?,
+        contract("CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA")
+    );
+    assert_eq!(
+        preimage.contract_address(NETWORK_PASSPHRASE_TESTNET)?,
+        ScAddress::Contract(contract(
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        ))
+    );
+    Ok(())
+}
+
+// The ids below are the sha256 hashes of preimages encoded by hand from the
+// XDR definitions, so that a preimage with a wrong field or discriminant does
+// not hash to the expected id.
+
+const ENVELOPE_TYPE_OP_ID: [u8; 4] = [0, 0, 0, 6];
+const ENVELOPE_TYPE_POOL_REVOKE_OP_ID: [u8; 4] = [0, 0, 0, 7];
+const PUBLIC_KEY_TYPE_ED25519: [u8; 4] = [0, 0, 0, 0];
+const ASSET_TYPE_NATIVE: [u8; 4] = [0, 0, 0, 0];
+const ASSET_TYPE_CREDIT_ALPHANUM4: [u8; 4] = [0, 0, 0, 1];
+const LIQUIDITY_POOL_CONSTANT_PRODUCT: [u8; 4] = [0, 0, 0, 0];
+
+const SOURCE_ACCOUNT: &str = "GB7TAYRUZGE6TVT7NHP5SMIZRNQA6PLM423EYISAOAP3MKYIQMVYP2JO";
+const ISSUER: &str = "GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ";
+
+fn ed25519(strkey: &str) -> [u8; 32] {
+    stellar_strkey::ed25519::PublicKey::from_string(strkey)
+        .unwrap()
+        .0
+}
+
+fn sha256(preimage: &[&[u8]]) -> Hash {
+    Hash(Sha256::digest(preimage.concat()).into())
+}
+
+#[test]
+fn claimable_balance_id() -> Result<(), Error> {
+    let id = ClaimableBalanceId::from_operation(
+        account(SOURCE_ACCOUNT),
+        SequenceNumber(0x0102_0304_0506_0708),
+        2,
+    )?;
+    let expected = sha256(&[
+        &ENVELOPE_TYPE_OP_ID,
+        &PUBLIC_KEY_TYPE_ED25519,
+        &ed25519(SOURCE_ACCOUNT),
+        &[1, 2, 3, 4, 5, 6, 7, 8],
+        &[0, 0, 0, 2],
+    ]);
+    assert_eq!(id, ClaimableBalanceId::ClaimableBalanceIdTypeV0(expected));
+    Ok(())
+}
+
+#[test]
+fn pool_revoke_claimable_balance_id() -> Result<(), Error> {
+    let revoke = |asset| {
+        ClaimableBalanceId::from_pool_revoke(
+            account(SOURCE_ACCOUNT),
+            SequenceNumber(0x0102_0304_0506_0708),
+            3,
+            PoolId(Hash([9; 32])),
+            asset,
+        )
+    };
+    let preimage: [&[u8]; 6] = [
+        &ENVELOPE_TYPE_POOL_REVOKE_OP_ID,
+        &PUBLIC_KEY_TYPE_ED25519,
+        &ed25519(SOURCE_ACCOUNT),
+        &[1, 2, 3, 4, 5, 6, 7, 8],
+        &[0, 0, 0, 3],
+        &[9; 32],
+    ];
+    assert_eq!(
+        revoke(Asset::Native)?,
+        ClaimableBalanceId::ClaimableBalanceIdTypeV0(sha256(
+            &[&preimage[..], &[&ASSET_TYPE_NATIVE]].concat()
+        ))
+    );
+    assert_eq!(
+        revoke(arst())?,
+        ClaimableBalanceId::ClaimableBalanceIdTypeV0(sha256(
+            &[
+                &preimage[..],
+                &[
+                    &ASSET_TYPE_CREDIT_ALPHANUM4,
+                    b"ARST",
+                    &PUBLIC_KEY_TYPE_ED25519,
+                    &ed25519(ISSUER),
+                ]
+            ]
+            .concat()
+        ))
+    );
+    Ok(())
+}
+
+fn arst() -> Asset {
+    Asset::CreditAlphanum4(AlphaNum4 {
+        asset_code: AssetCode4(*b"ARST"),
+        issuer: account(ISSUER),
+    })
+}
+
+#[test]
+fn pool_id() -> Result<(), Error> {
+    let parameters = LiquidityPoolParameters::LiquidityPoolConstantProduct(
+        LiquidityPoolConstantProductParameters {
+            asset_a: Asset::Native,
+            asset_b: arst(),
+            fee: 30,
+        },
+    );
+    let expected = sha256(&[
+        &LIQUIDITY_POOL_CONSTANT_PRODUCT,
+        &ASSET_TYPE_NATIVE,
+        &ASSET_TYPE_CREDIT_ALPHANUM4,
+        b"ARST",
+        &PUBLIC_KEY_TYPE_ED25519,
+        &ed25519(ISSUER),
+        &[0, 0, 0, 30],
+    ]);
+    assert_eq!(PoolId::from_parameters(&parameters)?, PoolId(expected));
+    Ok(())
+}
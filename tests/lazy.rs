@@ -0,0 +1,247 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    borrowed, Error, Hash, Lazy, LedgerCloseMeta, LedgerCloseMetaV0, LedgerEntryChanges,
+    LedgerHeader, LedgerHeaderExt, LedgerHeaderHistoryEntry, LedgerHeaderHistoryEntryExt, Limited,
+    Limits, Memo, MuxedAccount, Preconditions, ReadXdr, ReadXdrBorrowed, ScVal, ScpHistoryEntry,
+    SequenceNumber, StellarValue, StellarValueExt, TimePoint, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionMeta, TransactionResult, TransactionResultExt,
+    TransactionResultMeta, TransactionResultPair, TransactionResultResult, TransactionSet,
+    TransactionV1Envelope, Uint256, UpgradeEntryMeta, VecM, WriteXdr,
+};
+
+fn tx_envelope() -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("Stellar".as_bytes().try_into()?),
+            operations: [].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+#[test]
+fn lazy_read_decode_write() -> Result<(), Error> {
+    let te = tx_envelope()?;
+    let xdr = te.to_xdr(Limits::none())?;
+
+    let lazy = Lazy::<TransactionEnvelope>::from_xdr(&xdr, Limits::none())?;
+    assert_eq!(lazy.as_xdr(), xdr);
+    assert_eq!(lazy.decode(Limits::none())?, te);
+    assert_eq!(lazy.to_xdr(Limits::none())?, xdr);
+    assert_eq!(lazy, Lazy::new(&te, Limits::none())?);
+    Ok(())
+}
+
+#[test]
+fn lazy_read_consumes_only_the_value() -> Result<(), Error> {
+    let mut xdr = ScVal::Bytes(vec![1u8; 5].try_into()?).to_xdr(Limits::none())?;
+    xdr.extend(7u32.to_xdr(Limits::none())?);
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    let lazy = Lazy::<ScVal>::read_xdr(&mut r)?;
+    assert_eq!(lazy.as_xdr().len(), 16);
+    assert_eq!(r.offset(), 16);
+    assert_eq!(u32::read_xdr_to_end(&mut r)?, 7);
+    Ok(())
+}
+
+#[test]
+fn lazy_write_is_verbatim() -> Result<(), Error> {
+    // A bool encoded as 2 decodes as false, and re-encodes as 0. The lazy
+    // value holds, and writes, the bytes it was read from.
+    let xdr = [0u8, 0, 0, 0, 0, 0, 0, 2];
+    let lazy = Lazy::<ScVal>::from_xdr(xdr, Limits::none())?;
+    let v = lazy.decode(Limits::none())?;
+    assert_eq!(v, ScVal::Bool(false));
+    assert_ne!(v.to_xdr(Limits::none())?, xdr);
+    assert_eq!(lazy.to_xdr(Limits::none())?, xdr);
+    Ok(())
+}
+
+#[test]
+fn lazy_read_validates() -> Result<(), Error> {
+    let xdr = [0u8, 0, 0, 0xff];
+    assert_eq!(
+        Lazy::<ScVal>::from_xdr(xdr, Limits::none()),
+        Err(Error::Invalid)
+    );
+
+    let xdr = ScVal::Bytes(vec![1u8; 5].try_into()?).to_xdr(Limits::none())?;
+    assert_eq!(
+        Lazy::<ScVal>::from_xdr(&xdr, Limits::len(15)),
+        Err(Error::LengthLimitExceeded)
+    );
+    assert_eq!(
+        Lazy::<ScVal>::from_xdr(&xdr[..14], Limits::none()),
+        Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    );
+    Ok(())
+}
+
+fn ledger_close_meta(fees: &[i64]) -> Result<LedgerCloseMeta, Error> {
+    let tx_processing = fees
+        .iter()
+        .map(|fee| TransactionResultMeta {
+            result: TransactionResultPair {
+                transaction_hash: Hash([1; 32]),
+                result: TransactionResult {
+                    fee_charged: *fee,
+                    result: TransactionResultResult::TxSuccess([].try_into().unwrap()),
+                    ext: TransactionResultExt::V0,
+                },
+            },
+            fee_processing: LedgerEntryChanges([].try_into().unwrap()),
+            tx_apply_processing: TransactionMeta::V0([].try_into().unwrap()),
+        })
+        .collect::<Vec<_>>();
+    Ok(LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+        ledger_header: LedgerHeaderHistoryEntry {
+            hash: Hash([2; 32]),
+            header: LedgerHeader {
+                ledger_version: 20,
+                previous_ledger_hash: Hash([3; 32]),
+                scp_value: StellarValue {
+                    tx_set_hash: Hash([4; 32]),
+                    close_time: TimePoint(5),
+                    upgrades: [].try_into()?,
+                    ext: StellarValueExt::Basic,
+                },
+                tx_set_result_hash: Hash([6; 32]),
+                bucket_list_hash: Hash([7; 32]),
+                ledger_seq: 8,
+                total_coins: 9,
+                fee_pool: 10,
+                inflation_seq: 11,
+                id_pool: 12,
+                base_fee: 100,
+                base_reserve: 13,
+                max_tx_set_size: 14,
+                skip_list: [
+                    Hash([15; 32]),
+                    Hash([16; 32]),
+                    Hash([17; 32]),
+                    Hash([18; 32]),
+                ],
+                ext: LedgerHeaderExt::V0,
+            },
+            ext: LedgerHeaderHistoryEntryExt::V0,
+        },
+        tx_set: TransactionSet {
+            previous_ledger_hash: Hash([3; 32]),
+            txs: [].try_into()?,
+        },
+        tx_processing: tx_processing.try_into()?,
+        upgrades_processing: [].try_into()?,
+        scp_info: [].try_into()?,
+    }))
+}
+
+#[test]
+fn borrowed_lazy_scan_ledger_close_meta() -> Result<(), Error> {
+    let fees = [100, 200, 300];
+    let lcm = ledger_close_meta(&fees)?;
+    let xdr = lcm.to_xdr(Limits::none())?;
+
+    // Read the header, and hold the transaction metas as XDR borrowed from the
+    // input, decoding only the results of the transactions.
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    assert_eq!(i32::read_xdr(&mut r)?, 0);
+    let header = LedgerHeaderHistoryEntry::read_xdr(&mut r)?;
+    assert_eq!(header.header.ledger_seq, 8);
+    borrowed::Lazy::<TransactionSet>::read_xdr_borrowed(&mut r)?;
+    let tx_processing = VecM::<borrowed::Lazy<TransactionResultMeta>>::read_xdr_borrowed(&mut r)?;
+    borrowed::Lazy::<VecM<UpgradeEntryMeta>>::read_xdr_borrowed(&mut r)?;
+    borrowed::Lazy::<VecM<ScpHistoryEntry>>::read_xdr_borrowed(&mut r)?;
+    assert_eq!(r.inner, &[] as &[u8]);
+
+    let LedgerCloseMeta::V0(v0) = &lcm else {
+        unreachable!()
+    };
+    let mut scanned = Vec::new();
+    for (lazy, meta) in tx_processing.iter().zip(v0.tx_processing.iter()) {
+        assert!(xdr.as_ptr_range().contains(&lazy.as_xdr().as_ptr()));
+        let mut r = Limited::new(lazy.as_xdr(), Limits::none());
+        let result = TransactionResultPair::read_xdr(&mut r)?;
+        scanned.push(result.result.fee_charged);
+
+        assert_eq!(&lazy.decode(Limits::none())?, meta);
+        assert_eq!(
+            lazy.into_owned().to_xdr(Limits::none())?,
+            meta.to_xdr(Limits::none())?
+        );
+    }
+    assert_eq!(scanned, fees);
+    Ok(())
+}
+
+#[test]
+fn borrowed_lazy_read_validates() -> Result<(), Error> {
+    let xdr = [0u8, 0, 0, 0xff];
+    assert_eq!(
+        borrowed::Lazy::<ScVal>::from_xdr_borrowed(&xdr, Limits::none()),
+        Err(Error::Invalid)
+    );
+
+    let xdr = ScVal::Bytes(vec![1u8; 5].try_into()?).to_xdr(Limits::none())?;
+    assert_eq!(
+        borrowed::Lazy::<ScVal>::from_xdr_borrowed(&xdr[..14], Limits::none()),
+        Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+    );
+    let lazy = borrowed::Lazy::<ScVal>::from_xdr_borrowed(&xdr, Limits::none())?;
+    assert_eq!(lazy.as_xdr(), &xdr[..]);
+    Ok(())
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn lazy_matches_read_xdr_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use stellar_xdr::{LedgerCloseMeta, LedgerEntry, SorobanTransactionData};
+
+    fn check<T: for<'a> Arbitrary<'a> + ReadXdr + WriteXdr + PartialEq + std::fmt::Debug>(
+        u: &mut Unstructured,
+    ) {
+        let Ok(v) = T::arbitrary(u) else {
+            return;
+        };
+        let Ok(xdr) = v.to_xdr(Limits::none()) else {
+            return;
+        };
+        // Arbitrary values may not be within the bounds of their types, and
+        // so not be readable.
+        let Ok(lazy) = Lazy::<T>::from_xdr(&xdr, Limits::none()) else {
+            return;
+        };
+        assert_eq!(lazy.decode(Limits::none()).unwrap(), v);
+        assert_eq!(lazy.to_xdr(Limits::none()).unwrap(), xdr);
+    }
+
+    // A deterministic sequence of bytes that varies enough to produce a range
+    // of values.
+    let bytes: Vec<u8> = (0u32..1 << 14)
+        .flat_map(|i| i.wrapping_mul(2_654_435_761).to_be_bytes())
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..50 {
+        check::<TransactionEnvelope>(&mut u);
+        check::<LedgerEntry>(&mut u);
+        check::<SorobanTransactionData>(&mut u);
+        check::<LedgerCloseMeta>(&mut u);
+        check::<ScVal>(&mut u);
+    }
+}
@@ -0,0 +1,126 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    AccountId, Asset, ConfigSettingEntry, ConfigSettingId, ContractCodeEntry,
+    ContractDataDurability, ContractDataEntry, Error, ExtensionPoint, Hash, LedgerEntry,
+    LedgerEntryData, LedgerEntryExt, LedgerKey, LedgerKeyConfigSetting, LedgerKeyContractCode,
+    LedgerKeyContractData, LedgerKeyOffer, LedgerKeyTtl, OfferEntry, OfferEntryExt, Price,
+    PublicKey, ScAddress, ScVal, TtlEntry, Uint256,
+};
+
+fn entry(data: LedgerEntryData) -> LedgerEntry {
+    LedgerEntry {
+        last_modified_ledger_seq: 1,
+        data,
+        ext: LedgerEntryExt::V0,
+    }
+}
+
+fn contract_data() -> LedgerEntryData {
+    LedgerEntryData::ContractData(ContractDataEntry {
+        ext: ExtensionPoint::V0,
+        contract: ScAddress::Contract(Hash([1; 32])),
+        key: ScVal::Symbol("counter".try_into().unwrap()),
+        durability: ContractDataDurability::Persistent,
+        val: ScVal::U32(7),
+    })
+}
+
+#[test]
+fn contract_data_to_key() {
+    assert_eq!(
+        entry(contract_data()).to_key(),
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash([1; 32])),
+            key: ScVal::Symbol("counter".try_into().unwrap()),
+            durability: ContractDataDurability::Persistent,
+        })
+    );
+}
+
+#[test]
+fn other_entries_to_key() -> Result<(), Error> {
+    let seller_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([2; 32])));
+    let offer = LedgerEntryData::Offer(OfferEntry {
+        seller_id: seller_id.clone(),
+        offer_id: 123,
+        selling: Asset::Native,
+        buying: Asset::Native,
+        amount: 1,
+        price: Price { n: 1, d: 1 },
+        flags: 0,
+        ext: OfferEntryExt::V0,
+    });
+    assert_eq!(
+        LedgerKey::from(&offer),
+        LedgerKey::Offer(LedgerKeyOffer {
+            seller_id,
+            offer_id: 123,
+        })
+    );
+
+    let code = LedgerEntryData::ContractCode(ContractCodeEntry {
+        ext: ExtensionPoint::V0,
+        hash: Hash([3; 32]),
+        code: [0, 97, 115, 109].try_into()?,
+    });
+    assert_eq!(
+        code.to_key(),
+        LedgerKey::ContractCode(LedgerKeyContractCode {
+            hash: Hash([3; 32]),
+        })
+    );
+
+    let config_setting =
+        LedgerEntryData::ConfigSetting(ConfigSettingEntry::ContractMaxSizeBytes(65_536));
+    assert_eq!(
+        LedgerKey::from(&entry(config_setting)),
+        LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+            config_setting_id: ConfigSettingId::ContractMaxSizeBytes,
+        })
+    );
+
+    let ttl = LedgerEntryData::Ttl(TtlEntry {
+        key_hash: Hash([4; 32]),
+        live_until_ledger_seq: 100,
+    });
+    assert_eq!(
+        ttl.to_key(),
+        LedgerKey::Ttl(LedgerKeyTtl {
+            key_hash: Hash([4; 32]),
+        })
+    );
+    Ok(())
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn ttl_key() -> Result<(), Error> {
+    use sha2::{Digest, Sha256};
+    use stellar_xdr::{Limits, WriteXdr};
+
+    let key = entry(contract_data()).to_key();
+    let hash: [u8; 32] = Sha256::digest(key.to_xdr(Limits::none())?).into();
+    assert_eq!(
+        key.ttl_key()?,
+        Some(LedgerKeyTtl {
+            key_hash: Hash(hash),
+        })
+    );
+
+    // Only contract data and contract code entries have a TTL.
+    let key = LedgerKey::Ttl(LedgerKeyTtl {
+        key_hash: Hash(hash),
+    });
+    assert_eq!(key.ttl_key()?, None);
+    Ok(())
+}
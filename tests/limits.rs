@@ -0,0 +1,45 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{Error, Limited, Limits, ReadXdr, ScVal, WriteXdr};
+
+#[test]
+fn limits_builders() {
+    let limits = Limits::depth(5)
+        .with_len(10)
+        .with_alloc(20)
+        .with_elements(30);
+    assert_eq!(limits.depth, 5);
+    assert_eq!(limits.len, 10);
+    assert_eq!(limits.alloc, 20);
+    assert_eq!(limits.elements, 30);
+
+    let limits = limits.with_depth(6);
+    assert_eq!(limits.depth, 6);
+    assert_eq!(limits.len, 10);
+    assert_eq!(Limits::none().with_len(10), Limits::len(10));
+}
+
+#[test]
+fn limits_alloc_and_elements() -> Result<(), Error> {
+    let v = ScVal::Vec(Some([ScVal::U32(1), ScVal::U32(2)].to_vec().try_into()?));
+    let xdr = v.to_xdr(Limits::none())?;
+
+    let mut r = Limited::new(&xdr[..], Limits::none().with_elements(2));
+    assert_eq!(ScVal::read_xdr(&mut r)?, v);
+
+    let mut r = Limited::new(&xdr[..], Limits::none().with_elements(1));
+    assert_eq!(ScVal::read_xdr(&mut r), Err(Error::ElementLimitExceeded));
+
+    let mut r = Limited::new(&xdr[..], Limits::depth(10).with_alloc(8));
+    assert_eq!(ScVal::read_xdr(&mut r), Err(Error::AllocLimitExceeded));
+    Ok(())
+}
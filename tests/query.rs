@@ -0,0 +1,171 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use std::str::FromStr;
+
+use stellar_xdr::{
+    schema::TypeRef, Asset, Error, Memo, MuxedAccount, Operation, OperationBody, PathSegment,
+    PaymentOp, Preconditions, Query, QueryParseError, QuerySegment, SequenceNumber, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionV1Envelope, Type, TypeVariant, Uint256,
+};
+
+fn payment(amount: i64) -> Operation {
+    Operation {
+        source_account: None,
+        body: OperationBody::Payment(PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+            asset: Asset::Native,
+            amount,
+        }),
+    }
+}
+
+fn query(s: &str) -> Query {
+    Query::from_str(s).unwrap()
+}
+
+fn tx_envelope() -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("hello".try_into()?),
+            operations: [payment(1), payment(2)].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+#[test]
+fn parse() -> Result<(), QueryParseError> {
+    let q = Query::from_str("tx.operations[*].body[0].payment")?;
+    assert_eq!(
+        q.segments(),
+        [
+            QuerySegment::Name("tx".to_string()),
+            QuerySegment::Name("operations".to_string()),
+            QuerySegment::AnyIndex,
+            QuerySegment::Name("body".to_string()),
+            QuerySegment::Index(0),
+            QuerySegment::Name("payment".to_string()),
+        ]
+    );
+    assert_eq!(q.to_string(), "tx.operations[*].body[0].payment");
+    assert_eq!(Query::from_str("[1].a")?.to_string(), "[1].a");
+    assert_eq!(Query::from_str("")?, Query::default());
+    for invalid in [
+        "a.", ".a", "a..b", "a[", "a[]", "a[x]", "a]", "a[0]b", "a-b",
+    ] {
+        assert_eq!(Query::from_str(invalid), Err(QueryParseError), "{invalid}");
+    }
+    Ok(())
+}
+
+#[test]
+fn matches_snake_case_arms() {
+    let q = query("tx_fee_bump.tx");
+    assert!(q.matches(&[
+        PathSegment::Type("TransactionEnvelope"),
+        PathSegment::Arm("TxFeeBump"),
+        PathSegment::Field("tx"),
+    ]));
+    assert!(!q.matches(&[PathSegment::Arm("TxFeeBump")]));
+    assert!(!q.matches(&[PathSegment::Field("tx_fee_bump"), PathSegment::Index(0)]));
+}
+
+#[test]
+fn select_typed() -> Result<(), Error> {
+    let te = tx_envelope()?;
+    let q = query("tx.tx.operations[*].body.payment.amount");
+    assert_eq!(q.select::<i64>(&te), [1, 2]);
+    let q = query("tx.tx.operations[1].body.payment");
+    assert_eq!(
+        q.select::<PaymentOp>(&te),
+        [PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+            asset: Asset::Native,
+            amount: 2,
+        }]
+    );
+    // Values of other types at the path are not selected.
+    assert_eq!(q.select::<Operation>(&te), []);
+    Ok(())
+}
+
+#[test]
+fn select_types() -> Result<(), Error> {
+    let te = tx_envelope()?;
+    let t = Type::TransactionEnvelope(Box::new(te.clone()));
+
+    let q = query("tx.tx.operations[*].source_account");
+    // Options that are none have no value that is one of the types.
+    assert_eq!(q.select_types(&te), []);
+
+    let q = query("tx.tx.operations[*].body.payment.amount");
+    assert_eq!(
+        q.select_types(&t),
+        [Type::Int64(Box::new(1)), Type::Int64(Box::new(2))]
+    );
+
+    // The outermost value that is one of the types is selected, and not the
+    // i64 the typedef contains.
+    let q = query("tx.tx.seq_num");
+    assert_eq!(
+        q.select_types(&t),
+        [Type::SequenceNumber(Box::new(SequenceNumber(1)))]
+    );
+
+    // Values that are not one of the types are not selected.
+    let q = query("tx.tx.memo.text");
+    assert_eq!(q.select_types(&t), []);
+
+    let q = query("");
+    assert_eq!(q.select_types(&te), [t]);
+    Ok(())
+}
+
+#[test]
+fn resolve() {
+    let q = query("tx.tx.operations[*].body.payment.amount");
+    let types = q.resolve(TypeVariant::TransactionEnvelope).unwrap();
+    assert_eq!(types.len(), 8);
+    assert_eq!(types[0], TypeRef::Type(TypeVariant::TransactionEnvelope));
+    assert_eq!(types[2], TypeRef::Type(TypeVariant::Transaction));
+    assert_eq!(types[4], TypeRef::Type(TypeVariant::Operation));
+    assert_eq!(types[7], TypeRef::Int64);
+
+    // Optionals and typedefs do not add segments to the path.
+    let q = query("tx.tx.operations[0].source_account.ed25519");
+    let types = q.resolve(TypeVariant::TransactionEnvelope).unwrap();
+    assert_eq!(types.last(), Some(&TypeRef::Type(TypeVariant::Uint256)));
+    let q = query("tx.tx.memo.text");
+    let types = q.resolve(TypeVariant::TransactionEnvelope).unwrap();
+    assert_eq!(types.last(), Some(&TypeRef::String(28)));
+
+    // Paths that do not match the type, including void arms and indexes into
+    // values that are not arrays, do not resolve.
+    for unknown in [
+        "tx.tx.nope",
+        "tx.tx.memo.none",
+        "tx.tx.memo[0]",
+        "tx.tx.operations.body",
+        "tx_fee_bump.tx.tx.memo",
+    ] {
+        assert_eq!(
+            query(unknown).resolve(TypeVariant::TransactionEnvelope),
+            None,
+            "{unknown}"
+        );
+    }
+}
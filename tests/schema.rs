@@ -0,0 +1,112 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use std::path::Path;
+
+use stellar_xdr::schema::{EnumCase, Field, Kind, TypeRef, Union, UnionArm};
+use stellar_xdr::TypeVariant;
+
+#[test]
+fn schema_names() {
+    for v in TypeVariant::VARIANTS {
+        let s = v.schema();
+        assert_eq!(s.name, v.name());
+        assert_eq!(Path::new(s.file).extension(), Some("x".as_ref()));
+    }
+}
+
+#[test]
+fn schema_struct() {
+    let s = TypeVariant::ScpBallot.schema();
+    assert!(s.file.ends_with("/Stellar-SCP.x"));
+    assert_eq!(
+        s.kind,
+        Kind::Struct(&[
+            Field {
+                name: "counter",
+                ty: TypeRef::Uint32,
+            },
+            Field {
+                name: "value",
+                ty: TypeRef::Type(TypeVariant::Value),
+            },
+        ])
+    );
+}
+
+#[test]
+fn schema_enum() {
+    let Kind::Enum(cases) = TypeVariant::MemoType.schema().kind else {
+        panic!("expected enum");
+    };
+    assert_eq!(
+        cases[..2],
+        [
+            EnumCase {
+                name: "None",
+                value: 0,
+            },
+            EnumCase {
+                name: "Text",
+                value: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn schema_union() {
+    assert_eq!(
+        TypeVariant::TransactionExt.schema().kind,
+        Kind::Union(Union {
+            discriminant: TypeRef::Int32,
+            arms: &[
+                UnionArm {
+                    name: "V0",
+                    value: 0,
+                    ty: None,
+                },
+                UnionArm {
+                    name: "V1",
+                    value: 1,
+                    ty: Some(TypeRef::Type(TypeVariant::SorobanTransactionData)),
+                },
+            ],
+        })
+    );
+
+    let Kind::Union(u) = TypeVariant::Memo.schema().kind else {
+        panic!("expected union");
+    };
+    assert_eq!(u.discriminant, TypeRef::Type(TypeVariant::MemoType));
+    assert_eq!(
+        u.arms.iter().find(|a| a.name == "Text").map(|a| a.ty),
+        Some(Some(TypeRef::String(28)))
+    );
+}
+
+#[test]
+fn schema_typedef() {
+    assert_eq!(
+        TypeVariant::Signature.schema().kind,
+        Kind::Typedef(TypeRef::VarOpaque(64))
+    );
+    assert_eq!(
+        TypeVariant::Hash.schema().kind,
+        Kind::Typedef(TypeRef::FixedOpaque(32))
+    );
+    assert_eq!(
+        TypeVariant::ScVec.schema().kind,
+        Kind::Typedef(TypeRef::VarArray(
+            &TypeRef::Type(TypeVariant::ScVal),
+            u32::MAX
+        ))
+    );
+}
@@ -0,0 +1,135 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "ed25519")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use sha2::{Digest, Sha256};
+use stellar_xdr::{
+    signing_key_from_secret, DecoratedSignature, Error, Memo, MuxedAccount, Preconditions,
+    SequenceNumber, Signature, SignatureHint, SignerKey, SignerKeyEd25519SignedPayload,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256,
+    NETWORK_PASSPHRASE_PUBLIC, NETWORK_PASSPHRASE_TESTNET,
+};
+
+// The secret seed of the key with seed [1; 32].
+const SECRET: &str = "SAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC5MY";
+
+fn tx_envelope() -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: [].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+#[test]
+fn signing_key_from_secret_seed() -> Result<(), Error> {
+    let signing_key = signing_key_from_secret(SECRET)?;
+    assert_eq!(signing_key.to_bytes(), [1; 32]);
+    assert!(signing_key_from_secret("GAAA").is_err());
+    Ok(())
+}
+
+#[test]
+fn sign_and_verify_ed25519() -> Result<(), Error> {
+    let signing_key = signing_key_from_secret(SECRET)?;
+    let signer = SignerKey::Ed25519(Uint256(signing_key.verifying_key().to_bytes()));
+
+    let mut te = tx_envelope()?;
+    assert!(!te.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &signer)?);
+    te.sign(NETWORK_PASSPHRASE_TESTNET, &signing_key)?;
+    assert_eq!(te.signatures().len(), 1);
+    assert_eq!(te.signatures()[0].hint, signer.hint());
+    assert!(te.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &signer)?);
+    // The signature is only valid for the network it was signed for.
+    assert!(!te.is_signed_by(NETWORK_PASSPHRASE_PUBLIC, &signer)?);
+
+    let other = SignerKey::Ed25519(Uint256([2; 32]));
+    assert!(!te.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &other)?);
+    Ok(())
+}
+
+#[test]
+fn verify_hash_x() -> Result<(), Error> {
+    let preimage = b"preimage";
+    let signer = SignerKey::HashX(Uint256(Sha256::digest(preimage).into()));
+    let signature = DecoratedSignature {
+        hint: signer.hint(),
+        signature: Signature(preimage.try_into()?),
+    };
+    let hash = tx_envelope()?.hash(NETWORK_PASSPHRASE_TESTNET)?;
+    assert!(signature.verify(&signer, &hash));
+
+    let wrong = DecoratedSignature {
+        hint: signer.hint(),
+        signature: Signature(b"wrong".try_into()?),
+    };
+    assert!(!wrong.verify(&signer, &hash));
+    Ok(())
+}
+
+#[test]
+fn verify_signed_payload() -> Result<(), Error> {
+    let signing_key = signing_key_from_secret(SECRET)?;
+    let payload = [1, 2, 3, 4, 5];
+    let signer = SignerKey::Ed25519SignedPayload(SignerKeyEd25519SignedPayload {
+        ed25519: Uint256(signing_key.verifying_key().to_bytes()),
+        payload: payload.try_into()?,
+    });
+    let mut signature = DecoratedSignature::sign(&signing_key, &payload);
+    let hash = tx_envelope()?.hash(NETWORK_PASSPHRASE_TESTNET)?;
+    // The signature of the payload has the hint of the signed payload signer,
+    // not of the ed25519 key.
+    assert!(!signature.verify(&signer, &hash));
+    signature.hint = signer.hint();
+    assert!(signature.verify(&signer, &hash));
+    Ok(())
+}
+
+#[test]
+fn verify_pre_auth_tx() -> Result<(), Error> {
+    let te = tx_envelope()?;
+    let hash = te.hash(NETWORK_PASSPHRASE_TESTNET)?;
+    let signer = SignerKey::PreAuthTx(Uint256(hash.0));
+    assert!(te.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &signer)?);
+    assert!(!te.is_signed_by(NETWORK_PASSPHRASE_PUBLIC, &signer)?);
+    Ok(())
+}
+
+#[test]
+fn signer_hint() -> Result<(), Error> {
+    let mut key = [0; 32];
+    key[28..].copy_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(
+        SignerKey::Ed25519(Uint256(key)).hint(),
+        SignatureHint([1, 2, 3, 4])
+    );
+    let signed_payload = |payload: &[u8]| -> Result<SignerKey, Error> {
+        Ok(SignerKey::Ed25519SignedPayload(
+            SignerKeyEd25519SignedPayload {
+                ed25519: Uint256(key),
+                payload: payload.to_vec().try_into()?,
+            },
+        ))
+    };
+    assert_eq!(
+        signed_payload(&[0, 0, 1, 1, 1, 1])?.hint(),
+        SignatureHint([0, 3, 2, 5])
+    );
+    // Payloads shorter than 4 bytes are padded at the end.
+    assert_eq!(signed_payload(&[1, 1])?.hint(), SignatureHint([0, 3, 3, 4]));
+    Ok(())
+}
@@ -0,0 +1,185 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Error, Frame, FramedWriter, Limited, Limits, PathSegment, ReadXdr, ScVal, Type, TypeVariant,
+    WriteXdr,
+};
+
+fn values() -> Result<Vec<ScVal>, Error> {
+    Ok(vec![
+        ScVal::U32(1),
+        ScVal::Bytes(vec![1u8; 5].try_into()?),
+        ScVal::Vec(Some([ScVal::Bool(true)].to_vec().try_into()?)),
+    ])
+}
+
+#[test]
+fn skip_xdr_to_end() -> Result<(), Error> {
+    let xdr = ScVal::Bytes(vec![1u8; 5].try_into()?).to_xdr(Limits::none())?;
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    Type::skip_xdr_to_end(TypeVariant::ScVal, &mut r)?;
+    assert_eq!(r.offset(), xdr.len());
+
+    let mut padded = xdr.clone();
+    *padded.last_mut().unwrap() = 1;
+    let mut r = Limited::new(&padded[..], Limits::none());
+    assert_eq!(
+        Type::skip_xdr_to_end(TypeVariant::ScVal, &mut r),
+        Err(Error::NonZeroPadding)
+    );
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    assert_eq!(
+        Type::skip_xdr_to_end(TypeVariant::Uint32, &mut r),
+        Err(Error::Invalid)
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_xdr_iter() -> Result<(), Error> {
+    let mut xdr = Vec::new();
+    let mut ranges = Vec::new();
+    for v in values()? {
+        let start = xdr.len();
+        xdr.extend(v.to_xdr(Limits::none())?);
+        ranges.push(start..xdr.len());
+    }
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    let skipped = Type::skip_xdr_iter(TypeVariant::ScVal, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(skipped, ranges);
+    Ok(())
+}
+
+#[test]
+fn skip_xdr_iter_truncated() -> Result<(), Error> {
+    let xdr = ScVal::Bytes(vec![1u8; 5].try_into()?).to_xdr(Limits::none())?;
+    let mut r = Limited::new(&xdr[..xdr.len() - 1], Limits::none()).with_error_context();
+    let skipped = Type::skip_xdr_iter(TypeVariant::ScVal, &mut r).collect::<Vec<_>>();
+    let [Err(Error::Context(c))] = &skipped[..] else {
+        panic!("expected a single error with context");
+    };
+    assert_eq!(
+        c.path(),
+        [PathSegment::Type("ScVal"), PathSegment::Arm("Bytes")]
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_xdr_framed_iter() -> Result<(), Error> {
+    let mut w = FramedWriter::new(Vec::new(), Limits::none());
+    w.write_iter(values()?)?;
+    let xdr = w.into_inner();
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    let skipped =
+        Type::skip_xdr_framed_iter(TypeVariant::ScVal, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(skipped.len(), 3);
+    assert_eq!(skipped.last().map(|r| r.end), Some(xdr.len()));
+    Ok(())
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn skip_xdr_base64() -> Result<(), Error> {
+    let b64 = ScVal::U32(1).to_xdr_base64(Limits::none())?;
+    let mut r = Limited::new(b64.as_bytes(), Limits::none());
+    Type::skip_xdr_base64_to_end(TypeVariant::ScVal, &mut r)?;
+
+    let mut xdr = Vec::new();
+    for v in values()? {
+        xdr.extend(v.to_xdr(Limits::none())?);
+    }
+    let b64 = base64::encode(&xdr);
+    let mut r = Limited::new(b64.as_bytes(), Limits::none());
+    let skipped =
+        Type::skip_xdr_base64_iter(TypeVariant::ScVal, &mut r).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(skipped.len(), 3);
+    assert_eq!(skipped.last().map(|r| r.end), Some(xdr.len()));
+    Ok(())
+}
+
+#[test]
+fn skip_xdr_does_not_allocate() -> Result<(), Error> {
+    let xdr = ScVal::Vec(Some(
+        [ScVal::Bytes(vec![1u8; 100].try_into()?)]
+            .to_vec()
+            .try_into()?,
+    ))
+    .to_xdr(Limits::none())?;
+
+    let mut r = Limited::new(&xdr[..], Limits::alloc(0));
+    assert_eq!(ScVal::read_xdr(&mut r), Err(Error::AllocLimitExceeded));
+
+    let mut r = Limited::new(&xdr[..], Limits::alloc(0));
+    ScVal::skip_xdr(&mut r)?;
+    assert_eq!(r.offset(), xdr.len());
+    Ok(())
+}
+
+#[test]
+fn skip_xdr_framed() -> Result<(), Error> {
+    let mut xdr = Vec::new();
+    for v in values()? {
+        xdr.extend(Frame(v).to_xdr(Limits::none())?);
+    }
+
+    let mut r = Limited::new(&xdr[..], Limits::none());
+    Frame::<ScVal>::skip_xdr(&mut r)?;
+    assert_eq!(r.offset(), 12);
+    Frame::<ScVal>::skip_xdr(&mut r)?;
+    Frame::<ScVal>::skip_xdr(&mut r)?;
+    assert_eq!(r.offset(), xdr.len());
+    Ok(())
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn skip_xdr_matches_read_xdr_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use stellar_xdr::{LedgerCloseMeta, LedgerEntry, SorobanTransactionData, TransactionEnvelope};
+
+    fn check<T: for<'a> Arbitrary<'a> + ReadXdr + WriteXdr + PartialEq + std::fmt::Debug>(
+        u: &mut Unstructured,
+    ) {
+        let Ok(v) = T::arbitrary(u) else {
+            return;
+        };
+        let Ok(xdr) = v.to_xdr(Limits::none()) else {
+            return;
+        };
+        for limits in [Limits::none(), Limits::len(64), Limits::depth(4)] {
+            let mut r = Limited::new(&xdr[..], limits.clone());
+            let skipped = T::skip_xdr(&mut r).map(|()| r.offset());
+            let mut r = Limited::new(&xdr[..], limits.clone());
+            let read = T::read_xdr(&mut r).map(|_| r.offset());
+            assert_eq!(skipped, read);
+        }
+    }
+
+    // A deterministic sequence of bytes that varies enough to produce a range
+    // of values.
+    let bytes: Vec<u8> = (0u32..1 << 14)
+        .flat_map(|i| i.wrapping_mul(2_654_435_761).to_be_bytes())
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..50 {
+        check::<TransactionEnvelope>(&mut u);
+        check::<LedgerEntry>(&mut u);
+        check::<SorobanTransactionData>(&mut u);
+        check::<LedgerCloseMeta>(&mut u);
+        check::<ScVal>(&mut u);
+    }
+}
@@ -0,0 +1,144 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "ed25519")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    signing_key_from_secret, AccountId, Error, Hash, HashIdPreimage,
+    HashIdPreimageSorobanAuthorization, InvokeContractArgs, PublicKey, ScAddress, ScSymbol, ScVal,
+    SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+    SorobanAuthorizedInvocation, SorobanCredentials, Uint256, NETWORK_PASSPHRASE_PUBLIC,
+    NETWORK_PASSPHRASE_TESTNET,
+};
+
+// The secret seeds of the keys with seeds [1; 32] and [2; 32].
+const SECRET_1: &str = "SAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC5MY";
+const SECRET_2: &str = "SABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNE7";
+
+fn invocation() -> Result<SorobanAuthorizedInvocation, Error> {
+    Ok(SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: ScAddress::Contract(Hash([1; 32])),
+            function_name: ScSymbol("transfer".try_into()?),
+            args: [ScVal::U32(1)].try_into()?,
+        }),
+        sub_invocations: [].try_into()?,
+    })
+}
+
+fn entry() -> Result<SorobanAuthorizationEntry, Error> {
+    Ok(SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+                [0; 32],
+            )))),
+            nonce: 7,
+            signature_expiration_ledger: 100,
+            signature: ScVal::Void,
+        }),
+        root_invocation: invocation()?,
+    })
+}
+
+#[test]
+fn signature_payload() -> Result<(), Error> {
+    let entry = entry()?;
+    let payload = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash::network_id(NETWORK_PASSPHRASE_TESTNET),
+        nonce: 7,
+        signature_expiration_ledger: 100,
+        invocation: invocation()?,
+    });
+    assert_eq!(
+        entry.signature_payload(NETWORK_PASSPHRASE_TESTNET),
+        Some(payload.clone())
+    );
+    assert_eq!(
+        entry.signature_payload_hash(NETWORK_PASSPHRASE_TESTNET)?,
+        Some(payload.hash()?)
+    );
+    Ok(())
+}
+
+#[test]
+fn source_account_credentials() -> Result<(), Error> {
+    let mut entry = SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::SourceAccount,
+        root_invocation: invocation()?,
+    };
+    assert_eq!(entry.signature_payload(NETWORK_PASSPHRASE_TESTNET), None);
+    assert_eq!(entry.signatures(), Err(Error::Unsupported));
+    let signing_key = signing_key_from_secret(SECRET_1)?;
+    assert_eq!(
+        entry.sign(NETWORK_PASSPHRASE_TESTNET, &signing_key),
+        Err(Error::Unsupported)
+    );
+    Ok(())
+}
+
+#[test]
+fn sign_and_verify() -> Result<(), Error> {
+    let key_1 = signing_key_from_secret(SECRET_1)?;
+    let key_2 = signing_key_from_secret(SECRET_2)?;
+    let public_key_1 = Uint256(key_1.verifying_key().to_bytes());
+    let public_key_2 = Uint256(key_2.verifying_key().to_bytes());
+
+    let mut entry = entry()?;
+    assert_eq!(entry.signatures()?, []);
+    assert!(!entry.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &public_key_1)?);
+
+    entry.sign(NETWORK_PASSPHRASE_TESTNET, &key_1)?;
+    entry.sign(NETWORK_PASSPHRASE_TESTNET, &key_2)?;
+    assert!(entry.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &public_key_1)?);
+    assert!(entry.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &public_key_2)?);
+    assert!(!entry.is_signed_by(NETWORK_PASSPHRASE_PUBLIC, &public_key_1)?);
+
+    // Signatures are ordered by public key, and signing again replaces the
+    // signature of the key.
+    let signatures = entry.signatures()?;
+    assert_eq!(signatures.len(), 2);
+    assert!(signatures[0].0 < signatures[1].0);
+    entry.sign(NETWORK_PASSPHRASE_TESTNET, &key_1)?;
+    assert_eq!(entry.signatures()?, signatures);
+
+    // Changing the signed credentials invalidates the signatures.
+    if let SorobanCredentials::Address(credentials) = &mut entry.credentials {
+        credentials.signature_expiration_ledger = 101;
+    }
+    assert!(!entry.is_signed_by(NETWORK_PASSPHRASE_TESTNET, &public_key_1)?);
+    Ok(())
+}
+
+#[test]
+fn signature_format() -> Result<(), Error> {
+    let mut entry = entry()?;
+    entry.sign(
+        NETWORK_PASSPHRASE_TESTNET,
+        &signing_key_from_secret(SECRET_1)?,
+    )?;
+    let SorobanCredentials::Address(credentials) = &mut entry.credentials else {
+        unreachable!()
+    };
+    let ScVal::Vec(Some(signatures)) = &credentials.signature else {
+        panic!("signature is not a vec: {:?}", credentials.signature);
+    };
+    let ScVal::Map(Some(map)) = &signatures[0] else {
+        panic!("signature is not a map: {:?}", signatures[0]);
+    };
+    assert_eq!(map.len(), 2);
+    assert_eq!(
+        map[0].key,
+        ScVal::Symbol(ScSymbol("public_key".try_into()?))
+    );
+    assert_eq!(map[1].key, ScVal::Symbol(ScSymbol("signature".try_into()?)));
+
+    credentials.signature = ScVal::U32(1);
+    assert_eq!(entry.signatures(), Err(Error::Invalid));
+    Ok(())
+}
@@ -0,0 +1,196 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Asset, Duration, Error, ExtensionPoint, LedgerBounds, LedgerFootprint, Memo, MuxedAccount,
+    MuxedAccountMed25519, Operation, OperationBody, PaymentOp, Preconditions, PreconditionsV2,
+    SequenceNumber, SorobanResources, SorobanTransactionData, TimeBounds, TimePoint, Transaction,
+    TransactionBuilder, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, VecM,
+    MAX_OPERATIONS,
+};
+
+const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+const MUXED_ACCOUNT: &str = "MAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAB5IG";
+
+fn payment() -> Operation {
+    Operation {
+        source_account: None,
+        body: OperationBody::Payment(PaymentOp {
+            destination: MuxedAccount::Ed25519(Uint256([1; 32])),
+            asset: Asset::Native,
+            amount: 1,
+        }),
+    }
+}
+
+#[test]
+fn build_small_tx() -> Result<(), Error> {
+    // The same transaction as tests/tx_small.rs builds by hand, with a fee
+    // and an operation.
+    let te = TransactionBuilder::new(ACCOUNT, 1)?
+        .fee(100)
+        .memo_text("Stellar")?
+        .operation(payment())?
+        .build_envelope()?;
+    assert_eq!(
+        te,
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::Text("Stellar".as_bytes().try_into()?),
+                operations: [payment()].to_vec().try_into()?,
+                ext: TransactionExt::V0,
+            },
+            signatures: [].try_into()?,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn build_tx() -> Result<(), Error> {
+    let tx = TransactionBuilder::new(MUXED_ACCOUNT, 2)?
+        .fee(200)
+        .memo_id(7)
+        .operation(payment())?
+        .operation(payment())?
+        .build()?;
+    assert_eq!(
+        tx.source_account,
+        MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+            id: 0,
+            ed25519: Uint256([0; 32]),
+        })
+    );
+    assert_eq!(tx.fee, 200);
+    assert_eq!(tx.seq_num, SequenceNumber(2));
+    assert_eq!(tx.memo, Memo::Id(7));
+    assert_eq!(tx.operations.as_slice(), [payment(), payment()]);
+    Ok(())
+}
+
+#[test]
+fn build_preconditions() -> Result<(), Error> {
+    let builder = TransactionBuilder::new(ACCOUNT, 1)?
+        .fee(100)
+        .operation(payment())?;
+    let time_bounds = TimeBounds {
+        min_time: TimePoint(1),
+        max_time: TimePoint(2),
+    };
+    let ledger_bounds = LedgerBounds {
+        min_ledger: 3,
+        max_ledger: 0,
+    };
+
+    let tx = builder.clone().time_bounds(1, 2).build()?;
+    assert_eq!(tx.cond, Preconditions::Time(time_bounds.clone()));
+
+    let tx = builder.clone().ledger_bounds(3, 0).build()?;
+    assert_eq!(
+        tx.cond,
+        Preconditions::V2(PreconditionsV2 {
+            time_bounds: None,
+            ledger_bounds: Some(ledger_bounds.clone()),
+            min_seq_num: None,
+            min_seq_age: Duration(0),
+            min_seq_ledger_gap: 0,
+            extra_signers: VecM::default(),
+        })
+    );
+
+    let tx = builder
+        .clone()
+        .time_bounds(1, 2)
+        .ledger_bounds(3, 0)
+        .build()?;
+    let Preconditions::V2(cond) = tx.cond else {
+        panic!("preconditions are not v2: {:?}", tx.cond);
+    };
+    assert_eq!(cond.time_bounds, Some(time_bounds));
+    assert_eq!(cond.ledger_bounds, Some(ledger_bounds));
+
+    assert_eq!(
+        builder.clone().time_bounds(2, 1).build(),
+        Err(Error::Invalid)
+    );
+    assert_eq!(builder.ledger_bounds(4, 3).build(), Err(Error::Invalid));
+    Ok(())
+}
+
+#[test]
+fn build_soroban_tx() -> Result<(), Error> {
+    let soroban_data = SorobanTransactionData {
+        ext: ExtensionPoint::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: VecM::default(),
+                read_write: VecM::default(),
+            },
+            instructions: 1,
+            read_bytes: 2,
+            write_bytes: 3,
+        },
+        resource_fee: 4,
+    };
+    let builder = TransactionBuilder::new(ACCOUNT, 1)?
+        .operation(payment())?
+        .soroban_data(soroban_data.clone());
+    let tx = builder.clone().fee(104).build()?;
+    assert_eq!(tx.ext, TransactionExt::V1(soroban_data));
+    // The resource fee does not count towards the minimum fee.
+    assert_eq!(builder.fee(103).build(), Err(Error::Invalid));
+    Ok(())
+}
+
+#[test]
+fn build_limits() -> Result<(), Error> {
+    assert_eq!(
+        TransactionBuilder::new("GAAA", 1).err(),
+        Some(Error::Invalid)
+    );
+
+    let builder = TransactionBuilder::new(ACCOUNT, 1)?.fee(10_000);
+    assert!(builder.clone().memo_text(&"a".repeat(28)).is_ok());
+    assert_eq!(
+        builder.clone().memo_text(&"a".repeat(29)).err(),
+        Some(Error::LengthExceedsMax)
+    );
+
+    // A transaction without operations is never valid.
+    assert_eq!(builder.clone().build(), Err(Error::Invalid));
+
+    let mut builder = builder;
+    for _ in 0..MAX_OPERATIONS {
+        builder = builder.operation(payment())?;
+    }
+    assert_eq!(
+        builder.clone().operation(payment()).err(),
+        Some(Error::LengthExceedsMax)
+    );
+    assert_eq!(builder.build()?.operations.len(), MAX_OPERATIONS);
+    Ok(())
+}
+
+#[test]
+fn build_min_fee() -> Result<(), Error> {
+    let builder = TransactionBuilder::new(ACCOUNT, 1)?.operation(payment())?;
+    assert_eq!(builder.clone().fee(99).build(), Err(Error::Invalid));
+    assert!(builder.clone().fee(100).build().is_ok());
+
+    let builder = builder.operation(payment())?;
+    assert_eq!(builder.clone().fee(199).build(), Err(Error::Invalid));
+    assert!(builder.fee(200).build().is_ok());
+    Ok(())
+}
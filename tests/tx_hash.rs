@@ -0,0 +1,130 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(all(feature = "sha2", feature = "alloc"))]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use std::str::FromStr;
+
+use stellar_xdr::{
+    Error, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, Hash, Memo, MuxedAccount, Preconditions, SequenceNumber,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV0, TransactionV0Envelope, TransactionV0Ext, TransactionV1Envelope, Uint256,
+    NETWORK_PASSPHRASE_FUTURENET, NETWORK_PASSPHRASE_PUBLIC, NETWORK_PASSPHRASE_TESTNET,
+};
+
+fn tx() -> Result<Transaction, Error> {
+    Ok(Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: 0,
+        seq_num: SequenceNumber(1),
+        cond: Preconditions::None,
+        memo: Memo::Text("Stellar".as_bytes().try_into()?),
+        operations: [].to_vec().try_into()?,
+        ext: TransactionExt::V0,
+    })
+}
+
+#[test]
+fn network_id() -> Result<(), Error> {
+    assert_eq!(
+        Hash::network_id(NETWORK_PASSPHRASE_PUBLIC),
+        Hash::from_str("7ac33997544e3175d266bd022439b22cdb16508c01163f26e5cb2a3e1045a979")?
+    );
+    assert_eq!(
+        Hash::network_id(NETWORK_PASSPHRASE_TESTNET),
+        Hash::from_str("cee0302d59844d32bdca915c8203dd44b33fbb7edc19051ea37abedf28ecd472")?
+    );
+    assert_eq!(
+        Hash::network_id(NETWORK_PASSPHRASE_FUTURENET),
+        Hash::from_str("a3a1c6a78286713e29be0e9785670fa838d13917cd8eaeb4a3579ff1debc7fd5")?
+    );
+    Ok(())
+}
+
+#[test]
+fn tx_hash() -> Result<(), Error> {
+    let tx = tx()?;
+    let expected =
+        Hash::from_str("4abb176339fcd6ba3d754f720a00b9c4623a28b34ffe0725e645156a1c5d5837")?;
+    assert_eq!(tx.hash(NETWORK_PASSPHRASE_TESTNET)?, expected);
+
+    let payload = tx.signature_payload(NETWORK_PASSPHRASE_TESTNET);
+    assert_eq!(
+        payload.network_id,
+        Hash::network_id(NETWORK_PASSPHRASE_TESTNET)
+    );
+    assert_eq!(
+        payload.tagged_transaction,
+        TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone())
+    );
+
+    let te = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: [].try_into()?,
+    });
+    assert_eq!(te.hash(NETWORK_PASSPHRASE_TESTNET)?, expected);
+    Ok(())
+}
+
+#[test]
+fn tx_v0_hash() -> Result<(), Error> {
+    let tx = tx()?;
+    let te = TransactionEnvelope::TxV0(TransactionV0Envelope {
+        tx: TransactionV0 {
+            source_account_ed25519: Uint256([0; 32]),
+            fee: tx.fee,
+            seq_num: tx.seq_num.clone(),
+            time_bounds: None,
+            memo: tx.memo.clone(),
+            operations: tx.operations.clone(),
+            ext: TransactionV0Ext::V0,
+        },
+        signatures: [].try_into()?,
+    });
+    // A v0 transaction is hashed as the equivalent v1 transaction.
+    assert_eq!(
+        te.hash(NETWORK_PASSPHRASE_TESTNET)?,
+        tx.hash(NETWORK_PASSPHRASE_TESTNET)?
+    );
+    assert_eq!(
+        te.signature_payload(NETWORK_PASSPHRASE_TESTNET),
+        tx.signature_payload(NETWORK_PASSPHRASE_TESTNET)
+    );
+    Ok(())
+}
+
+#[test]
+fn fee_bump_hash() -> Result<(), Error> {
+    let fee_bump = FeeBumpTransaction {
+        fee_source: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: 200,
+        inner_tx: FeeBumpTransactionInnerTx::Tx(TransactionV1Envelope {
+            tx: tx()?,
+            signatures: [].try_into()?,
+        }),
+        ext: FeeBumpTransactionExt::V0,
+    };
+    let expected =
+        Hash::from_str("1bf7bf37517119ad3fb6afc78e2a8c83c271260775a4bdbdda0bf5b81d9e4ba3")?;
+    assert_eq!(fee_bump.hash(NETWORK_PASSPHRASE_TESTNET)?, expected);
+    assert_eq!(
+        fee_bump
+            .signature_payload(NETWORK_PASSPHRASE_TESTNET)
+            .tagged_transaction,
+        TransactionSignaturePayloadTaggedTransaction::TxFeeBump(fee_bump.clone())
+    );
+
+    let te = TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+        tx: fee_bump,
+        signatures: [].try_into()?,
+    });
+    assert_eq!(te.hash(NETWORK_PASSPHRASE_TESTNET)?, expected);
+    Ok(())
+}
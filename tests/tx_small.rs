@@ -96,3 +96,78 @@ fn test_build_small_tx_with_alloc() -> Result<(), Error> {
     });
     Ok(())
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_encode_decode_small_tx_with_alloc() -> Result<(), Error> {
+    use stellar_xdr::{Limits, ReadXdr, WriteXdr};
+
+    let te = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("Stellar".as_bytes().try_into()?),
+            operations: [].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    });
+
+    let xdr = te.to_xdr(Limits::none())?;
+    assert_eq!(xdr.len(), 84);
+    assert_eq!(TransactionEnvelope::from_xdr(&xdr, Limits::none())?, te);
+
+    let mut buf = [0u8; 100];
+    let n = te.write_xdr_to_slice(&mut buf, Limits::none())?;
+    assert_eq!(&buf[..n], &xdr[..]);
+
+    assert_eq!(
+        TransactionEnvelope::from_xdr(&xdr, Limits::len(xdr.len() - 1)),
+        Err(Error::LengthLimitExceeded)
+    );
+    assert!(matches!(
+        TransactionEnvelope::from_xdr(&xdr[..xdr.len() - 1], Limits::none()),
+        Err(Error::Io(_))
+    ));
+    Ok(())
+}
+
+#[cfg(not(feature = "alloc"))]
+#[test]
+fn test_encode_small_tx_to_slice_without_alloc() -> Result<(), Error> {
+    use stellar_xdr::{Limits, WriteXdr};
+
+    let te = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("Stellar".as_bytes().try_into()?),
+            operations: (&[]).try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: (&[]).try_into()?,
+    });
+
+    let mut buf = [0u8; 100];
+    let n = te.write_xdr_to_slice(&mut buf, Limits::none())?;
+    assert_eq!(n, 84);
+    assert_eq!(
+        &buf[60..72],
+        &[0, 0, 0, 7, 83, 116, 101, 108, 108, 97, 114, 0]
+    );
+
+    let mut small = [0u8; 83];
+    assert!(matches!(
+        te.write_xdr_to_slice(&mut small, Limits::none()),
+        Err(Error::Io(_))
+    ));
+    assert_eq!(
+        te.write_xdr_to_slice(&mut buf, Limits::len(83)),
+        Err(Error::LengthLimitExceeded)
+    );
+    Ok(())
+}
@@ -0,0 +1,128 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use std::any::Any;
+
+use stellar_xdr::{
+    Asset, Error, Memo, MuxedAccount, Operation, OperationBody, PathSegment, PaymentOp,
+    Preconditions, ScVal, SequenceNumber, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Type, Uint256, Visit, VisitMut,
+};
+
+fn tx_envelope() -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: [Operation {
+                source_account: None,
+                body: OperationBody::Payment(PaymentOp {
+                    destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+                    asset: Asset::Native,
+                    amount: 1,
+                }),
+            }]
+            .to_vec()
+            .try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    }))
+}
+
+#[test]
+fn visit_collects_with_paths() -> Result<(), Error> {
+    let te = tx_envelope()?;
+    let mut found = Vec::new();
+    te.visit(&mut |path: &[PathSegment], v: &dyn Any| {
+        if let Some(m) = v.downcast_ref::<MuxedAccount>() {
+            found.push((path.to_vec(), m.clone()));
+        }
+    });
+    assert_eq!(
+        found,
+        [
+            (
+                vec![
+                    PathSegment::Arm("Tx"),
+                    PathSegment::Field("tx"),
+                    PathSegment::Field("source_account"),
+                ],
+                MuxedAccount::Ed25519(Uint256([1; 32])),
+            ),
+            (
+                vec![
+                    PathSegment::Arm("Tx"),
+                    PathSegment::Field("tx"),
+                    PathSegment::Field("operations"),
+                    PathSegment::Index(0),
+                    PathSegment::Field("body"),
+                    PathSegment::Arm("Payment"),
+                    PathSegment::Field("destination"),
+                ],
+                MuxedAccount::Ed25519(Uint256([2; 32])),
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn visit_order() -> Result<(), Error> {
+    let v = ScVal::Vec(Some([ScVal::U32(1), ScVal::U32(2)].to_vec().try_into()?));
+    let mut seen = Vec::new();
+    v.visit(&mut |_: &[PathSegment], v: &dyn Any| {
+        if let Some(u) = v.downcast_ref::<u32>() {
+            seen.push(*u);
+        } else if v.is::<ScVal>() {
+            seen.push(0);
+        }
+    });
+    // Values are visited before the values nested within them.
+    assert_eq!(seen, [0, 0, 1, 0, 2]);
+    Ok(())
+}
+
+#[test]
+fn visit_mut_replaces() -> Result<(), Error> {
+    let mut te = tx_envelope()?;
+    te.visit_mut(&mut |_: &[PathSegment], v: &mut dyn Any| {
+        if let Some(m) = v.downcast_mut::<MuxedAccount>() {
+            *m = MuxedAccount::Ed25519(Uint256([3; 32]));
+        }
+    });
+
+    let mut found = Vec::new();
+    te.visit(&mut |_: &[PathSegment], v: &dyn Any| {
+        if let Some(m) = v.downcast_ref::<Uint256>() {
+            found.push(m.clone());
+        }
+    });
+    assert_eq!(found, [Uint256([3; 32]), Uint256([3; 32])]);
+    Ok(())
+}
+
+#[test]
+fn visit_type() {
+    let t = Type::Memo(Box::new(Memo::Id(7)));
+    let mut paths = Vec::new();
+    t.visit(&mut |path: &[PathSegment], _: &dyn Any| paths.push(path.to_vec()));
+    assert_eq!(
+        paths,
+        [
+            vec![PathSegment::Type("Memo")],
+            vec![PathSegment::Type("Memo"), PathSegment::Arm("Id")],
+        ]
+    );
+}
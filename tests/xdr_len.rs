@@ -0,0 +1,125 @@
+#![cfg(all(
+    any(feature = "curr", feature = "next"),
+    not(all(feature = "curr", feature = "next"))
+))]
+#![cfg(feature = "std")]
+
+#[cfg(feature = "curr")]
+use stellar_xdr::curr as stellar_xdr;
+#[cfg(feature = "next")]
+use stellar_xdr::next as stellar_xdr;
+
+use stellar_xdr::{
+    Error, Frame, Hash, Lazy, Limits, Memo, MemoType, MuxedAccount, Preconditions, ScVal,
+    SequenceNumber, TimeBounds, TimePoint, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Type, Uint256, WriteXdr,
+};
+
+#[test]
+fn xdr_len_matches_to_xdr() -> Result<(), Error> {
+    let te = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 0,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::Text("Stellar".as_bytes().try_into()?),
+            operations: [].to_vec().try_into()?,
+            ext: TransactionExt::V0,
+        },
+        signatures: [].try_into()?,
+    });
+    assert_eq!(te.xdr_len(Limits::none())?, 84);
+    assert_eq!(
+        te.xdr_len(Limits::none())?,
+        te.to_xdr(Limits::none())?.len()
+    );
+
+    let t = Type::TransactionEnvelope(Box::new(te));
+    assert_eq!(t.xdr_len(Limits::none())?, 84);
+    Ok(())
+}
+
+#[test]
+fn xdr_len_includes_padding() -> Result<(), Error> {
+    for n in 0..8 {
+        let v = ScVal::Bytes(vec![1u8; n].try_into()?);
+        let len = v.xdr_len(Limits::none())?;
+        assert_eq!(len, 4 + 4 + n.div_ceil(4) * 4);
+        assert_eq!(len, v.to_xdr(Limits::none())?.len());
+    }
+    Ok(())
+}
+
+#[test]
+fn xdr_len_limits() -> Result<(), Error> {
+    let v = ScVal::Bytes(vec![1u8; 5].try_into()?);
+    assert_eq!(v.xdr_len(Limits::len(16))?, 16);
+    assert_eq!(v.xdr_len(Limits::len(15)), Err(Error::LengthLimitExceeded));
+    assert_eq!(v.xdr_len(Limits::depth(1)), Err(Error::DepthLimitExceeded));
+    Ok(())
+}
+
+#[test]
+fn xdr_len_fixed_length_types() -> Result<(), Error> {
+    assert_eq!(Hash([0; 32]).xdr_len(Limits::none())?, 32);
+    assert_eq!(MemoType::Text.xdr_len(Limits::none())?, 4);
+    let tb = TimeBounds {
+        min_time: TimePoint(1),
+        max_time: TimePoint(2),
+    };
+    assert_eq!(tb.xdr_len(Limits::none())?, 16);
+    // The depth limit applies to fixed length types as it does when writing.
+    for depth in 0..4 {
+        assert_eq!(
+            tb.xdr_len(Limits::depth(depth)),
+            tb.to_xdr(Limits::depth(depth)).map(|xdr| xdr.len()),
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn xdr_len_frame_and_lazy() -> Result<(), Error> {
+    let v = ScVal::Bytes(vec![1u8; 5].try_into()?);
+    let f = Frame(v.clone());
+    assert_eq!(f.xdr_len(Limits::none())?, 4 + 16);
+    assert_eq!(f.xdr_len(Limits::none())?, f.to_xdr(Limits::none())?.len());
+    let lazy = Lazy::new(&v, Limits::none())?;
+    assert_eq!(lazy.xdr_len(Limits::none())?, 16);
+    assert_eq!(lazy.xdr_len(Limits::len(15)), Err(Error::LengthLimitExceeded));
+    Ok(())
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn xdr_len_matches_to_xdr_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use stellar_xdr::{LedgerCloseMeta, LedgerEntry, SorobanTransactionData};
+
+    fn check<T: for<'a> Arbitrary<'a> + WriteXdr>(u: &mut Unstructured) {
+        let Ok(v) = T::arbitrary(u) else {
+            return;
+        };
+        for limits in [Limits::none(), Limits::len(64), Limits::depth(4)] {
+            assert_eq!(
+                v.xdr_len(limits.clone()),
+                v.to_xdr(limits).map(|xdr| xdr.len()),
+            );
+        }
+    }
+
+    // A deterministic sequence of bytes that varies enough to produce a range
+    // of values.
+    let bytes: Vec<u8> = (0u32..1 << 14)
+        .flat_map(|i| i.wrapping_mul(2_654_435_761).to_be_bytes())
+        .collect();
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..50 {
+        check::<TransactionEnvelope>(&mut u);
+        check::<LedgerEntry>(&mut u);
+        check::<SorobanTransactionData>(&mut u);
+        check::<LedgerCloseMeta>(&mut u);
+        check::<ScVal>(&mut u);
+    }
+}